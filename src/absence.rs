@@ -0,0 +1,142 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{GestionTempsApp, HoraireHebdo, RegleCategorie, ReglesCategories, ResultatCalcul};
+
+/// Règles reconnaissant les lignes hors clientèle qui sont des absences et non du travail.
+/// Même format que les règles de catégories : "Type;mots-clés;préfixes de code".
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReglesAbsences {
+    pub regles: ReglesCategories,
+}
+
+impl Default for ReglesAbsences {
+    fn default() -> Self {
+        ReglesAbsences {
+            regles: ReglesCategories {
+                regles: vec![
+                    RegleCategorie::new("Congés payés", &["congé payé", "congés payés", "conge paye", "conges payes"], &[]),
+                    RegleCategorie::new("RTT", &["rtt"], &[]),
+                    RegleCategorie::new("Maladie", &["maladie"], &[]),
+                    RegleCategorie::new("Récupération", &["récupération", "recuperation", "récup"], &[]),
+                ],
+            },
+        }
+    }
+}
+
+impl ReglesAbsences {
+    pub fn type_absence(&self, code: &str, description: &str) -> Option<String> {
+        self.regles.trouver(code, description).map(|r| r.categorie.clone())
+    }
+}
+
+/// Absence d'un type donné sur une journée.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Absence {
+    pub date: Option<NaiveDate>,
+    pub type_absence: String,
+    pub heures: f64,
+    /// Décompte en jours : 0, 0.5 ou 1 ; 0 sur un jour où rien n'est prévu ou pour moins d'un quart de journée
+    pub jours: f64,
+}
+
+/// Arrondit une absence à la demi-journée la plus proche selon les heures prévues ce jour-là,
+/// sans dépasser une journée.
+pub fn decompter_jours(heures: f64, heures_prevues: f64) -> f64 {
+    if heures <= 0.0 || heures_prevues <= 0.0 {
+        return 0.0;
+    }
+    ((heures / heures_prevues * 2.0).round() / 2.0).min(1.0)
+}
+
+/// Regroupe les heures d'absence par jour et par type, puis les décompte en jours.
+pub fn regrouper_absences(
+    heures: BTreeMap<(Option<NaiveDate>, String), f64>,
+    horaire: &HoraireHebdo,
+) -> Vec<Absence> {
+    heures
+        .into_iter()
+        .map(|((date, type_absence), heures)| {
+            // Sans date lisible, on compte une journée pleine de 7h
+            let prevues = date.map(|d| horaire.heures_du_jour(d)).unwrap_or(7.0);
+            Absence { date, type_absence, heures, jours: decompter_jours(heures, prevues) }
+        })
+        .collect()
+}
+
+impl ResultatCalcul {
+    /// Heures et jours d'absence par type.
+    pub fn absences_par_type(&self) -> BTreeMap<String, (f64, f64)> {
+        let mut totaux = BTreeMap::new();
+        for absence in &self.absences {
+            let total = totaux.entry(absence.type_absence.clone()).or_insert((0.0, 0.0));
+            total.0 += absence.heures;
+            total.1 += absence.jours;
+        }
+        totaux
+    }
+
+    pub fn absences_du_jour(&self, date: NaiveDate) -> impl Iterator<Item = &Absence> {
+        self.absences.iter().filter(move |a| a.date == Some(date))
+    }
+}
+
+impl GestionTempsApp {
+    pub fn appliquer_regles_absences(&mut self) {
+        match ReglesCategories::depuis_texte(&self.texte_regles_absences) {
+            Ok(regles) => {
+                self.regles_absences = ReglesAbsences { regles };
+                self.status_message = "Règles d'absence appliquées, cliquez sur Calculer pour mettre à jour les résultats".to_string();
+            }
+            Err(e) => self.status_message = format!("Règles d'absence invalides: {}", e),
+        }
+    }
+
+    pub(crate) fn afficher_regles_absences(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("🌴 Règles d'absence", |ui| {
+            if self.texte_regles_absences.is_empty() {
+                self.texte_regles_absences = self.regles_absences.regles.vers_texte();
+            }
+
+            ui.small("Les lignes hors clientèle reconnues ici sont des absences : exclues du temps travaillé, décomptées en jours");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.texte_regles_absences)
+                    .id_source("regles_absences")
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(4),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("✔ Appliquer").clicked() {
+                    self.appliquer_regles_absences();
+                }
+                if ui.button("↺ Par défaut").clicked() {
+                    self.regles_absences = ReglesAbsences::default();
+                    self.texte_regles_absences = self.regles_absences.regles.vers_texte();
+                }
+            });
+        });
+    }
+
+    pub(crate) fn afficher_absences(&self, ui: &mut egui::Ui, resultat: &ResultatCalcul) {
+        let par_type = resultat.absences_par_type();
+        if par_type.is_empty() {
+            return;
+        }
+
+        ui.collapsing("🌴 Absences (non comptées comme travail)", |ui| {
+            egui::Grid::new("absences_par_type").striped(true).show(ui, |ui| {
+                for (type_absence, (heures, jours)) in &par_type {
+                    ui.label(type_absence);
+                    ui.label(format!("{} j", jours));
+                    ui.label(format!("{:.2}h", heures));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,345 @@
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::entree::date_heure_de;
+use crate::fichier::lire_fichier_texte;
+use crate::{EntreeSaisie, GestionTempsApp, ReglesCategories, TypeEntree};
+
+/// Code ABS des lignes hors clientèle créées depuis un agenda, quand l'événement n'en porte pas.
+pub const CODE_AGENDA: &str = "AGENDA";
+/// Propriété ajoutée aux événements exportés pour reconnaître la nature de l'entrée à l'import.
+const PROPRIETE_TYPE: &str = "X-GESTION-TEMPS-TYPE";
+const FORMAT_ICS: &str = "%Y%m%dT%H%M%S";
+
+fn echapper(texte: &str) -> String {
+    texte
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn desechapper(texte: &str) -> String {
+    let mut resultat = String::new();
+    let mut caracteres = texte.chars();
+    while let Some(c) = caracteres.next() {
+        if c != '\\' {
+            resultat.push(c);
+            continue;
+        }
+        match caracteres.next() {
+            Some('n') | Some('N') => resultat.push(' '),
+            Some(autre) => resultat.push(autre),
+            None => {}
+        }
+    }
+    resultat
+}
+
+/// Coupe les lignes à 75 octets comme l'exige la RFC 5545, sans couper un caractère.
+fn plier(ligne: &str) -> String {
+    let mut resultat = String::new();
+    let mut longueur = 0;
+    for c in ligne.chars() {
+        if longueur + c.len_utf8() > 75 {
+            resultat.push_str("\r\n ");
+            longueur = 1;
+        }
+        resultat.push(c);
+        longueur += c.len_utf8();
+    }
+    resultat.push_str("\r\n");
+    resultat
+}
+
+/// Événement lu ou écrit dans un agenda, en heure locale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvenementAgenda {
+    pub uid: String,
+    pub resume: String,
+    pub description: String,
+    pub categorie: String,
+    pub debut: NaiveDateTime,
+    pub fin: NaiveDateTime,
+    /// Nature de l'entrée exportée par l'application, vide pour un événement d'un autre agenda
+    pub type_entree: String,
+}
+
+impl EvenementAgenda {
+    fn vers_vevent(&self, horodatage: &str) -> String {
+        let mut vevent = String::from("BEGIN:VEVENT\r\n");
+        let mut proprietes = vec![
+            format!("UID:{}", self.uid),
+            format!("DTSTAMP:{}", horodatage),
+            format!("DTSTART:{}", self.debut.format(FORMAT_ICS)),
+            format!("DTEND:{}", self.fin.format(FORMAT_ICS)),
+            format!("SUMMARY:{}", echapper(&self.resume)),
+        ];
+        if !self.description.is_empty() {
+            proprietes.push(format!("DESCRIPTION:{}", echapper(&self.description)));
+        }
+        if !self.categorie.is_empty() {
+            proprietes.push(format!("CATEGORIES:{}", echapper(&self.categorie)));
+        }
+        if !self.type_entree.is_empty() {
+            proprietes.push(format!("{}:{}", PROPRIETE_TYPE, self.type_entree));
+        }
+        for propriete in proprietes {
+            vevent.push_str(&plier(&propriete));
+        }
+        vevent.push_str("END:VEVENT\r\n");
+        vevent
+    }
+
+    /// Code d'origine d'une activité exportée par l'application ("Code ABS--..." en description),
+    /// sinon `CODE_AGENDA`.
+    pub fn code_source(&self) -> &str {
+        self.description
+            .strip_prefix("Code ")
+            .map(str::trim)
+            .filter(|code| !code.is_empty() && !code.contains(char::is_whitespace))
+            .unwrap_or(CODE_AGENDA)
+    }
+
+    /// Ligne au format hors clientèle : Code, Description, Début, Fin.
+    pub fn vers_ligne_hors_clientele(&self) -> String {
+        let resume = self.resume.replace(['\t', '\r', '\n'], " ");
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.code_source(),
+            resume.trim(),
+            self.debut.format("%d/%m/%Y %H:%M"),
+            self.fin.format("%d/%m/%Y %H:%M")
+        )
+    }
+}
+
+/// Début et description d'une ligne hors clientèle, pour repérer un événement déjà saisi.
+fn cle_ligne_hors_clientele(ligne: &str) -> Option<(NaiveDateTime, String)> {
+    let parties: Vec<&str> = ligne.split('\t').collect();
+    let debut = date_heure_de(parties.get(2)?.trim())?;
+    Some((debut, parties[1].trim().to_string()))
+}
+
+/// Début et fin d'une entrée ; la fin d'une intervention manquante est déduite de sa durée.
+fn plage_entree(entree: &EntreeSaisie) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let debut = date_heure_de(&entree.debut)?;
+    match date_heure_de(&entree.fin) {
+        Some(fin) if fin > debut => Some((debut, fin)),
+        _ if entree.duree > 0.0 => Some((debut, debut + chrono::Duration::minutes((entree.duree * 60.0).round() as i64))),
+        _ => None,
+    }
+}
+
+/// Événements d'agenda des entrées : un créneau par activité hors clientèle, et pour une
+/// intervention un créneau de trajet (si ses horaires sont connus) et un créneau d'intervention.
+pub fn evenements_des_entrees(entrees: &[&EntreeSaisie], categories: &ReglesCategories) -> Vec<EvenementAgenda> {
+    let mut evenements = Vec::new();
+
+    for entree in entrees {
+        let uid = |suffixe: &str| format!("{}-{}-{}@gestion-temps", entree.code, entree.ligne, suffixe);
+
+        match entree.type_entree {
+            TypeEntree::HorsClientele => {
+                if let Some((debut, fin)) = plage_entree(entree) {
+                    evenements.push(EvenementAgenda {
+                        uid: uid("activite"),
+                        resume: entree.description.clone(),
+                        description: format!("Code {}", entree.code),
+                        categorie: categories.categoriser(&entree.code, &entree.description),
+                        debut,
+                        fin,
+                        type_entree: "hors-clientele".to_string(),
+                    });
+                }
+            }
+            TypeEntree::Clientele => {
+                if let (Some(debut), Some(fin)) = (date_heure_de(&entree.debut_trajet), date_heure_de(&entree.fin_trajet)) {
+                    if fin > debut {
+                        evenements.push(EvenementAgenda {
+                            uid: uid("trajet"),
+                            resume: format!("Trajet - {}", entree.client),
+                            description: format!("WO {}", entree.code),
+                            categorie: "Trajet".to_string(),
+                            debut,
+                            fin,
+                            type_entree: "trajet".to_string(),
+                        });
+                    }
+                }
+                if let Some((debut, fin)) = plage_entree(entree) {
+                    evenements.push(EvenementAgenda {
+                        uid: uid("intervention"),
+                        resume: format!("{} - {}", entree.client, entree.description),
+                        description: format!("WO {}", entree.code),
+                        categorie: "Clientèle".to_string(),
+                        debut,
+                        fin,
+                        type_entree: "intervention".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    evenements
+}
+
+pub fn vers_ics(evenements: &[EvenementAgenda]) -> String {
+    let horodatage = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut ics = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Gestion Temps//Entrées déclarées//FR\r\nCALSCALE:GREGORIAN\r\n");
+    for evenement in evenements {
+        ics.push_str(&evenement.vers_vevent(&horodatage));
+    }
+    ics.push_str("END:VCALENDAR\r\n");
+    ics
+}
+
+/// Date et heure d'une valeur DTSTART/DTEND. Les heures UTC sont converties en heure locale,
+/// celles avec TZID ou flottantes sont prises telles quelles ; une date seule renvoie None.
+fn date_heure_ics(parametres: &str, valeur: &str) -> Option<NaiveDateTime> {
+    if parametres.to_uppercase().contains("VALUE=DATE") && !parametres.to_uppercase().contains("VALUE=DATE-TIME") {
+        return None;
+    }
+    match valeur.strip_suffix('Z') {
+        Some(utc) => {
+            let utc = NaiveDateTime::parse_from_str(utc, FORMAT_ICS).ok()?;
+            Some(Local.from_utc_datetime(&utc).naive_local())
+        }
+        None => NaiveDateTime::parse_from_str(valeur, FORMAT_ICS).ok(),
+    }
+}
+
+/// Lignes dépliées : une ligne commençant par un espace ou une tabulation prolonge la précédente.
+fn deplier(texte: &str) -> Vec<String> {
+    let mut lignes: Vec<String> = Vec::new();
+    for ligne in texte.lines() {
+        match ligne.strip_prefix([' ', '\t']) {
+            Some(suite) if !lignes.is_empty() => lignes.last_mut().unwrap().push_str(suite),
+            _ => lignes.push(ligne.to_string()),
+        }
+    }
+    lignes
+}
+
+/// Lit les événements VEVENT d'un agenda. Les événements sur des journées entières ou
+/// sans horaire lisible sont écartés avec un avertissement.
+pub fn lire_ics(texte: &str) -> (Vec<EvenementAgenda>, Vec<String>) {
+    let mut evenements = Vec::new();
+    let mut avertissements = Vec::new();
+    let mut courant: Option<(EvenementAgenda, Option<NaiveDateTime>, Option<NaiveDateTime>)> = None;
+
+    for ligne in deplier(texte) {
+        let Some((nom, valeur)) = ligne.split_once(':') else {
+            continue;
+        };
+        let (nom, parametres) = nom.split_once(';').unwrap_or((nom, ""));
+
+        match (nom.to_uppercase().as_str(), courant.as_mut()) {
+            ("BEGIN", _) if valeur.eq_ignore_ascii_case("VEVENT") => {
+                let vide = EvenementAgenda {
+                    uid: String::new(),
+                    resume: String::new(),
+                    description: String::new(),
+                    categorie: String::new(),
+                    debut: NaiveDateTime::MIN,
+                    fin: NaiveDateTime::MIN,
+                    type_entree: String::new(),
+                };
+                courant = Some((vide, None, None));
+            }
+            ("END", Some(_)) if valeur.eq_ignore_ascii_case("VEVENT") => {
+                let (mut evenement, debut, fin) = courant.take().unwrap();
+                match (debut, fin) {
+                    (Some(debut), Some(fin)) if fin > debut => {
+                        evenement.debut = debut;
+                        evenement.fin = fin;
+                        evenements.push(evenement);
+                    }
+                    _ => avertissements.push(format!("Événement '{}' ignoré (journée entière ou horaire illisible)", evenement.resume)),
+                }
+            }
+            ("UID", Some((evenement, _, _))) => evenement.uid = valeur.to_string(),
+            ("SUMMARY", Some((evenement, _, _))) => evenement.resume = desechapper(valeur),
+            ("DESCRIPTION", Some((evenement, _, _))) => evenement.description = desechapper(valeur),
+            ("CATEGORIES", Some((evenement, _, _))) => evenement.categorie = desechapper(valeur),
+            (PROPRIETE_TYPE, Some((evenement, _, _))) => evenement.type_entree = valeur.to_string(),
+            ("DTSTART", Some((_, debut, _))) => *debut = date_heure_ics(parametres, valeur),
+            ("DTEND", Some((_, _, fin))) => *fin = date_heure_ics(parametres, valeur),
+            _ => {}
+        }
+    }
+
+    (evenements, avertissements)
+}
+
+impl GestionTempsApp {
+    /// Écrit les entrées retenues dans un fichier .ics.
+    pub fn exporter_agenda(&mut self, chemin: &Path) {
+        let evenements = evenements_des_entrees(&self.entrees_retenues(), &self.regles_categories);
+        self.status_message = match fs::write(chemin, vers_ics(&evenements)) {
+            Ok(()) => format!("{} événement(s) exporté(s) dans {}", evenements.len(), chemin.display()),
+            Err(e) => format!("Impossible d'écrire {}: {}", chemin.display(), e),
+        };
+    }
+
+    /// Ajoute les événements d'un agenda à la zone hors clientèle. Les interventions et
+    /// trajets exportés par l'application ne sont pas réimportés, ni les activités déjà
+    /// présentes dans la zone (même début et même description).
+    pub fn importer_agenda(&mut self, chemin: &Path) {
+        let texte = match lire_fichier_texte(chemin) {
+            Ok(contenu) => contenu.texte,
+            Err(e) => {
+                self.status_message = format!("Erreur lors de la lecture de {}: {}", chemin.display(), e);
+                return;
+            }
+        };
+
+        let (evenements, avertissements) = lire_ics(&texte);
+        for avertissement in avertissements {
+            self.signaler(avertissement);
+        }
+
+        let mut lignes: Vec<String> = evenements
+            .iter()
+            .filter(|e| e.type_entree.is_empty() || e.type_entree == "hors-clientele")
+            .map(EvenementAgenda::vers_ligne_hors_clientele)
+            .collect();
+        lignes.sort_by_key(|ligne| ligne.split('\t').nth(2).and_then(date_heure_de));
+
+        let mut deja_saisies: HashSet<_> = self.donnees_hors_clientele.lines().filter_map(cle_ligne_hors_clientele).collect();
+        let nombre_lues = lignes.len();
+        lignes.retain(|ligne| cle_ligne_hors_clientele(ligne).is_none_or(|cle| deja_saisies.insert(cle)));
+        let doublons = nombre_lues - lignes.len();
+
+        if !lignes.is_empty() && !self.donnees_hors_clientele.is_empty() && !self.donnees_hors_clientele.ends_with('\n') {
+            self.donnees_hors_clientele.push('\n');
+        }
+        for ligne in &lignes {
+            self.donnees_hors_clientele.push_str(ligne);
+            self.donnees_hors_clientele.push('\n');
+        }
+        self.status_message = format!("{} événement(s) importé(s) depuis {}", lignes.len(), chemin.display());
+        if doublons > 0 {
+            self.status_message = format!("{} ({} déjà présent(s) ignoré(s))", self.status_message, doublons);
+        }
+    }
+
+    pub(crate) fn ouvrir_agenda(&mut self) {
+        if let Some(chemin) = rfd::FileDialog::new().add_filter("Agenda", &["ics"]).pick_file() {
+            self.importer_agenda(&chemin);
+        }
+    }
+
+    pub(crate) fn enregistrer_agenda(&mut self) {
+        let choix = rfd::FileDialog::new()
+            .add_filter("Agenda", &["ics"])
+            .set_file_name("temps_declare.ics")
+            .save_file();
+        if let Some(chemin) = choix {
+            self.exporter_agenda(&chemin);
+        }
+    }
+}
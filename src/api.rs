@@ -0,0 +1,177 @@
+use chrono::NaiveDate;
+use std::error::Error;
+use std::io::Read;
+use std::net::SocketAddr;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::fichier::decoder_texte;
+use crate::saisie_unifiee::repartir_lignes;
+use crate::{camembert_png, FiltreDates, GestionTempsApp, Periode, RapportJson, ZoneSaisie};
+
+/// Adresse d'écoute par défaut de `gestion_temps --api`, limitée à la machine locale.
+pub const ADRESSE_PAR_DEFAUT: &str = "127.0.0.1:8080";
+
+/// Taille maximale du corps d'une requête (10 Mo) ; au-delà, réponse 413.
+pub const TAILLE_MAX_CORPS: u64 = 10 * 1024 * 1024;
+
+/// Réponse d'une route, avant envoi.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReponseApi {
+    pub statut: u16,
+    pub type_contenu: &'static str,
+    pub corps: Vec<u8>,
+}
+
+impl ReponseApi {
+    fn json(statut: u16, json: String) -> Self {
+        ReponseApi { statut, type_contenu: "application/json; charset=utf-8", corps: json.into_bytes() }
+    }
+
+    fn erreur(statut: u16, message: &str) -> Self {
+        Self::json(statut, serde_json::json!({ "erreur": message }).to_string())
+    }
+
+    fn rapport(rapport: &RapportJson) -> Self {
+        match rapport.vers_json() {
+            Ok(json) => Self::json(200, json),
+            Err(e) => Self::erreur(500, &format!("rapport non sérialisable: {}", e)),
+        }
+    }
+}
+
+/// Paramètres "du" et "au" (YYYY-MM-DD) de la requête, en filtre de dates.
+fn filtre_de_la_requete(requete: &str) -> Result<FiltreDates, String> {
+    let mut filtre = FiltreDates::default();
+    for (cle, valeur) in requete.split('&').filter_map(|p| p.split_once('=')) {
+        let borne = match cle {
+            "du" => &mut filtre.du,
+            "au" => &mut filtre.au,
+            _ => continue,
+        };
+        let date = NaiveDate::parse_from_str(valeur, "%Y-%m-%d")
+            .map_err(|_| format!("date invalide pour \"{}\": {} (attendu YYYY-MM-DD)", cle, valeur))?;
+        *borne = date.format("%d/%m/%Y").to_string();
+        filtre.periode = Periode::Personnalisee;
+    }
+    Ok(filtre)
+}
+
+/// Routes de l'API ; garde le contenu des deux zones, pour qu'un envoi par zone complète l'autre,
+/// et le dernier rapport calculé pour GET /rapport et GET /graphique.png.
+#[derive(Debug, Default)]
+pub struct EtatApi {
+    pub donnees_hors_clientele: String,
+    pub donnees_clientele: String,
+    pub dernier_rapport: Option<RapportJson>,
+}
+
+impl EtatApi {
+    /// Calcule le rapport des deux zones après y avoir placé un export TSV brut.
+    /// Avec une zone, seule celle-ci est remplacée ; sans zone, les deux le sont et
+    /// chaque ligne est rangée dans la zone détectée, comme la saisie unifiée.
+    fn calculer(&mut self, zone: Option<ZoneSaisie>, requete: &str, corps: &[u8]) -> ReponseApi {
+        let filtre_dates = match filtre_de_la_requete(requete) {
+            Ok(filtre) => filtre,
+            Err(message) => return ReponseApi::erreur(400, &message),
+        };
+        let texte = decoder_texte(corps).texte;
+        if texte.trim().is_empty() {
+            return ReponseApi::erreur(400, "corps de la requête vide : envoyez l'export TSV brut");
+        }
+
+        match zone {
+            Some(ZoneSaisie::HorsClientele) => self.donnees_hors_clientele = texte,
+            Some(ZoneSaisie::Clientele) => self.donnees_clientele = texte,
+            None => {
+                let repartition = repartir_lignes(&texte);
+                self.donnees_hors_clientele = repartition.hors_clientele;
+                self.donnees_clientele = repartition.clientele;
+            }
+        }
+
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: self.donnees_hors_clientele.clone(),
+            donnees_clientele: self.donnees_clientele.clone(),
+            filtre_dates,
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let rapport = app.rapport_json();
+        let reponse = ReponseApi::rapport(&rapport);
+        self.dernier_rapport = Some(rapport);
+        reponse
+    }
+
+    fn graphique(&self) -> ReponseApi {
+        let Some(resultat) = self.dernier_rapport.as_ref().and_then(|r| r.resultat.as_ref()) else {
+            return ReponseApi::erreur(404, "aucun calcul : envoyez d'abord un export avec POST /rapport");
+        };
+        match camembert_png(resultat) {
+            Ok(png) => ReponseApi { statut: 200, type_contenu: "image/png", corps: png },
+            Err(e) => ReponseApi::erreur(500, &format!("graphique non généré: {}", e)),
+        }
+    }
+
+    pub fn repondre(&mut self, methode: &Method, url: &str, corps: &[u8]) -> ReponseApi {
+        let (chemin, requete) = url.split_once('?').unwrap_or((url, ""));
+
+        match (methode, chemin) {
+            (Method::Post, "/rapport") => self.calculer(None, requete, corps),
+            (Method::Post, "/rapport/hors-clientele") => self.calculer(Some(ZoneSaisie::HorsClientele), requete, corps),
+            (Method::Post, "/rapport/clientele") => self.calculer(Some(ZoneSaisie::Clientele), requete, corps),
+            (Method::Get, "/rapport") => match self.dernier_rapport {
+                Some(ref rapport) => ReponseApi::rapport(rapport),
+                None => ReponseApi::erreur(404, "aucun calcul : envoyez d'abord un export avec POST /rapport"),
+            },
+            (Method::Get, "/graphique.png") => self.graphique(),
+            (_, "/rapport" | "/rapport/hors-clientele" | "/rapport/clientele" | "/graphique.png") => {
+                ReponseApi::erreur(405, "méthode non autorisée")
+            }
+            _ => ReponseApi::erreur(404, &format!("route inconnue: {}", chemin)),
+        }
+    }
+}
+
+/// Serveur HTTP local exposant l'analyse et le calcul, sans interface graphique.
+pub struct ServeurApi {
+    serveur: Server,
+    pub etat: EtatApi,
+}
+
+impl ServeurApi {
+    /// Écoute sur `adresse` ("127.0.0.1:0" choisit un port libre).
+    pub fn demarrer(adresse: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(ServeurApi { serveur: Server::http(adresse)?, etat: EtatApi::default() })
+    }
+
+    pub fn adresse(&self) -> Option<SocketAddr> {
+        self.serveur.server_addr().to_ip()
+    }
+
+    /// Traite les requêtes une par une, jusqu'à l'arrêt du processus.
+    pub fn servir(mut self) {
+        for requete in self.serveur.incoming_requests() {
+            if let Err(e) = traiter_requete(&mut self.etat, requete) {
+                eprintln!("Réponse non envoyée: {}", e);
+            }
+        }
+    }
+}
+
+fn traiter_requete(etat: &mut EtatApi, mut requete: Request) -> std::io::Result<()> {
+    let mut corps = Vec::new();
+    // Un octet de plus que la limite suffit à savoir qu'elle est dépassée
+    let lecture = requete.as_reader().take(TAILLE_MAX_CORPS + 1).read_to_end(&mut corps);
+    let reponse = match lecture {
+        Ok(taille) if taille as u64 > TAILLE_MAX_CORPS => ReponseApi::erreur(
+            413,
+            &format!("corps trop volumineux (limite de {} Mo)", TAILLE_MAX_CORPS / (1024 * 1024)),
+        ),
+        Ok(_) => etat.repondre(requete.method(), requete.url(), &corps),
+        Err(e) => ReponseApi::erreur(400, &format!("corps illisible: {}", e)),
+    };
+
+    let type_contenu = Header::from_bytes("Content-Type", reponse.type_contenu).expect("en-tête valide");
+    requete.respond(Response::from_data(reponse.corps).with_status_code(reponse.statut).with_header(type_contenu))
+}
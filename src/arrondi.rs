@@ -0,0 +1,322 @@
+use eframe::egui;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{EntreeSaisie, GestionTempsApp, ReglesCategories, ResultatCalcul, TypeEntree};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SensArrondi {
+    #[default]
+    Superieur,
+    PlusProche,
+    Inferieur,
+}
+
+impl SensArrondi {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SensArrondi::Superieur => "supérieur",
+            SensArrondi::PlusProche => "proche",
+            SensArrondi::Inferieur => "inférieur",
+        }
+    }
+
+    fn depuis_code(code: &str) -> Option<SensArrondi> {
+        match code.to_lowercase().as_str() {
+            "" | "supérieur" | "superieur" | "sup" => Some(SensArrondi::Superieur),
+            "proche" | "plus proche" => Some(SensArrondi::PlusProche),
+            "inférieur" | "inferieur" | "inf" => Some(SensArrondi::Inferieur),
+            _ => None,
+        }
+    }
+}
+
+/// Arrondi d'une durée d'intervention : au pas indiqué (aucun si 0), puis au minimum facturé.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PolitiqueArrondi {
+    pub pas_minutes: u32,
+    pub sens: SensArrondi,
+    /// Durée minimale facturée, en heures (0 pour aucune)
+    pub minimum: f64,
+}
+
+impl PolitiqueArrondi {
+    pub fn quart_d_heure_superieur() -> Self {
+        PolitiqueArrondi { pas_minutes: 15, sens: SensArrondi::Superieur, minimum: 0.0 }
+    }
+
+    pub fn six_minutes_plus_proches() -> Self {
+        PolitiqueArrondi { pas_minutes: 6, sens: SensArrondi::PlusProche, minimum: 0.0 }
+    }
+
+    pub fn minimum_une_heure() -> Self {
+        PolitiqueArrondi { pas_minutes: 0, sens: SensArrondi::Superieur, minimum: 1.0 }
+    }
+
+    pub fn appliquer(&self, heures: f64) -> f64 {
+        if heures <= 0.0 {
+            return heures;
+        }
+
+        let mut arrondi = heures;
+        if self.pas_minutes > 0 {
+            let pas = self.pas_minutes as f64;
+            // La tolérance évite qu'une durée déjà ronde (ex: 0.25h) passe au pas suivant
+            let nombre_de_pas = heures * 60.0 / pas;
+            let nombre_de_pas = match self.sens {
+                SensArrondi::Superieur => (nombre_de_pas - 1e-6).ceil(),
+                SensArrondi::PlusProche => nombre_de_pas.round(),
+                SensArrondi::Inferieur => (nombre_de_pas + 1e-6).floor(),
+            };
+            arrondi = nombre_de_pas * pas / 60.0;
+        }
+        arrondi.max(self.minimum)
+    }
+
+    pub fn libelle(&self) -> String {
+        let mut parties = Vec::new();
+        if self.pas_minutes > 0 {
+            parties.push(format!("{} min ({})", self.pas_minutes, self.sens.code()));
+        }
+        if self.minimum > 0.0 {
+            parties.push(format!("minimum {:.2}h", self.minimum));
+        }
+        if parties.is_empty() { "aucun arrondi".to_string() } else { parties.join(", ") }
+    }
+}
+
+/// Ce à quoi s'applique une politique : les interventions d'un client ou les activités
+/// hors clientèle d'une catégorie.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CibleArrondi {
+    Client(String),
+    Categorie(String),
+}
+
+impl CibleArrondi {
+    pub fn libelle(&self) -> String {
+        match self {
+            CibleArrondi::Client(nom) => format!("Client {}", nom),
+            CibleArrondi::Categorie(nom) => format!("Catégorie {}", nom),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegleArrondi {
+    pub cible: CibleArrondi,
+    pub politique: PolitiqueArrondi,
+}
+
+/// Durées réelles et arrondies cumulées pour une cible.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EcartArrondi {
+    /// Nombre d'entrées arrondies
+    pub entrees: usize,
+    /// Heures avant arrondi
+    pub reel: f64,
+    /// Heures après arrondi, retenues dans les totaux
+    pub arrondi: f64,
+}
+
+impl EcartArrondi {
+    pub fn ecart(&self) -> f64 {
+        self.arrondi - self.reel
+    }
+
+    fn ajouter(&mut self, reel: f64, arrondi: f64) {
+        self.entrees += 1;
+        self.reel += reel;
+        self.arrondi += arrondi;
+    }
+}
+
+/// Règles d'arrondi appliquées entrée par entrée avant le calcul des totaux ; la première
+/// règle dont la cible correspond l'emporte, sans règle la durée est conservée.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReglesArrondi {
+    pub regles: Vec<RegleArrondi>,
+}
+
+fn meme_nom(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+impl ReglesArrondi {
+    pub fn trouver(&self, entree: &EntreeSaisie, categories: &ReglesCategories) -> Option<&RegleArrondi> {
+        let categorie = match entree.type_entree {
+            TypeEntree::HorsClientele => Some(categories.categoriser(&entree.code, &entree.description)),
+            TypeEntree::Clientele => None,
+        };
+
+        self.regles.iter().find(|regle| match (&regle.cible, entree.type_entree) {
+            (CibleArrondi::Client(nom), TypeEntree::Clientele) => meme_nom(nom, &entree.client),
+            (CibleArrondi::Categorie(nom), TypeEntree::HorsClientele) => {
+                categorie.as_deref().is_some_and(|c| meme_nom(nom, c))
+            }
+            _ => false,
+        })
+    }
+
+    /// Durée arrondie de l'entrée et cible de la règle appliquée, si une règle correspond.
+    pub fn arrondir(&self, entree: &EntreeSaisie, categories: &ReglesCategories) -> Option<(String, f64)> {
+        self.trouver(entree, categories)
+            .map(|regle| (regle.cible.libelle(), regle.politique.appliquer(entree.duree)))
+    }
+
+    /// Lit des règles au format "client|catégorie;Nom;pas en minutes;sens;minimum en heures".
+    /// Les lignes vides et celles commençant par '#' sont ignorées.
+    pub fn depuis_texte(texte: &str) -> Result<Self, String> {
+        let mut regles = Vec::new();
+
+        for (numero, ligne) in texte.lines().enumerate() {
+            let ligne = ligne.trim();
+            if ligne.is_empty() || ligne.starts_with('#') {
+                continue;
+            }
+
+            let erreur = |detail: &str| format!("Ligne {}: {}", numero + 1, detail);
+            let parties: Vec<&str> = ligne.split(';').map(str::trim).collect();
+            if parties.len() < 3 || parties.len() > 5 || parties[1].is_empty() {
+                return Err(erreur("format attendu 'client|catégorie;Nom;pas en minutes;sens;minimum en heures'"));
+            }
+
+            let cible = match parties[0].to_lowercase().as_str() {
+                "client" => CibleArrondi::Client(parties[1].to_string()),
+                "catégorie" | "categorie" => CibleArrondi::Categorie(parties[1].to_string()),
+                autre => return Err(erreur(&format!("cible '{}' inconnue (client ou catégorie)", autre))),
+            };
+            let pas_minutes = match parties[2] {
+                "" => 0,
+                pas => pas.parse().map_err(|_| erreur(&format!("pas '{}' invalide", pas)))?,
+            };
+            let sens = SensArrondi::depuis_code(parties.get(3).unwrap_or(&""))
+                .ok_or_else(|| erreur(&format!("sens '{}' inconnu (supérieur, proche ou inférieur)", parties[3])))?;
+            let minimum = match parties.get(4).copied().unwrap_or("") {
+                "" => 0.0,
+                minimum => minimum
+                    .replace(',', ".")
+                    .parse()
+                    .map_err(|_| erreur(&format!("minimum '{}' invalide", minimum)))?,
+            };
+
+            regles.push(RegleArrondi { cible, politique: PolitiqueArrondi { pas_minutes, sens, minimum } });
+        }
+
+        Ok(ReglesArrondi { regles })
+    }
+
+    pub fn vers_texte(&self) -> String {
+        let mut texte = String::from("# client|catégorie;Nom;pas en minutes;sens (supérieur, proche, inférieur);minimum en heures\n");
+        for regle in &self.regles {
+            let (cible, nom) = match &regle.cible {
+                CibleArrondi::Client(nom) => ("client", nom),
+                CibleArrondi::Categorie(nom) => ("catégorie", nom),
+            };
+            texte.push_str(&format!(
+                "{};{};{};{};{}\n",
+                cible,
+                nom,
+                regle.politique.pas_minutes,
+                regle.politique.sens.code(),
+                regle.politique.minimum
+            ));
+        }
+        texte
+    }
+}
+
+/// Exemples proposés dans le panneau quand aucune règle n'est définie.
+const EXEMPLE_REGLES: &str = "# client|catégorie;Nom;pas en minutes;sens (supérieur, proche, inférieur);minimum en heures
+# client;Client A;15;supérieur;0
+# client;Client B;6;proche;0
+# client;Client C;0;;1
+";
+
+impl ResultatCalcul {
+    /// Écart total entre le temps arrondi (facturé) et le temps réel.
+    pub fn ecart_arrondi(&self) -> f64 {
+        self.arrondis.values().map(EcartArrondi::ecart).sum()
+    }
+}
+
+/// Cumule l'arrondi d'une entrée dans `arrondis` et renvoie la durée à retenir.
+pub(crate) fn duree_arrondie(
+    regles: &ReglesArrondi,
+    categories: &ReglesCategories,
+    entree: &EntreeSaisie,
+    arrondis: &mut BTreeMap<String, EcartArrondi>,
+) -> f64 {
+    match regles.arrondir(entree, categories) {
+        Some((cible, arrondi)) => {
+            arrondis.entry(cible).or_default().ajouter(entree.duree, arrondi);
+            arrondi
+        }
+        None => entree.duree,
+    }
+}
+
+impl GestionTempsApp {
+    pub fn appliquer_regles_arrondi(&mut self) {
+        match ReglesArrondi::depuis_texte(&self.texte_regles_arrondi) {
+            Ok(regles) => {
+                self.regles_arrondi = regles;
+                self.status_message = "Règles d'arrondi appliquées, cliquez sur Calculer pour mettre à jour les résultats".to_string();
+            }
+            Err(e) => self.status_message = format!("Règles d'arrondi invalides: {}", e),
+        }
+    }
+
+    pub(crate) fn afficher_regles_arrondi(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("⏱ Règles d'arrondi", |ui| {
+            if self.texte_regles_arrondi.is_empty() {
+                self.texte_regles_arrondi = if self.regles_arrondi.regles.is_empty() {
+                    EXEMPLE_REGLES.to_string()
+                } else {
+                    self.regles_arrondi.vers_texte()
+                };
+            }
+
+            ui.small("Chaque intervention d'un client ou activité d'une catégorie est arrondie avant le calcul des totaux");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.texte_regles_arrondi)
+                    .id_source("regles_arrondi")
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(4),
+            );
+            if ui.button("✔ Appliquer").clicked() {
+                self.appliquer_regles_arrondi();
+            }
+
+            for regle in &self.regles_arrondi.regles {
+                ui.label(format!("{} : {}", regle.cible.libelle(), regle.politique.libelle()));
+            }
+        });
+    }
+
+    pub(crate) fn afficher_arrondis(&self, ui: &mut egui::Ui, resultat: &ResultatCalcul) {
+        if resultat.arrondis.is_empty() {
+            return;
+        }
+
+        ui.collapsing(format!("⏱ Arrondis : {:+.2}h facturées en plus du temps réel", resultat.ecart_arrondi()), |ui| {
+            egui::Grid::new("ecarts_arrondi").striped(true).show(ui, |ui| {
+                for titre in ["Cible", "Entrées", "Réel", "Arrondi", "Écart"] {
+                    ui.strong(titre);
+                }
+                ui.end_row();
+
+                for (cible, ecart) in &resultat.arrondis {
+                    ui.label(cible);
+                    ui.label(ecart.entrees.to_string());
+                    ui.label(format!("{:.2}h", ecart.reel));
+                    ui.label(format!("{:.2}h", ecart.arrondi));
+                    ui.label(format!("{:+.2}h", ecart.ecart()));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
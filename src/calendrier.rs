@@ -0,0 +1,232 @@
+use eframe::egui;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::entree::date_de;
+use crate::GestionTempsApp;
+
+/// Nature d'un jour du calendrier de l'entreprise.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TypeJour {
+    Ouvre,
+    WeekEnd,
+    Ferie(String),
+    Fermeture(String),
+}
+
+impl TypeJour {
+    pub fn est_ouvre(&self) -> bool {
+        *self == TypeJour::Ouvre
+    }
+
+    pub fn libelle(&self) -> String {
+        match self {
+            TypeJour::Ouvre => String::new(),
+            TypeJour::WeekEnd => "Week-end".to_string(),
+            TypeJour::Ferie(nom) => format!("Férié : {}", nom),
+            TypeJour::Fermeture(motif) => format!("Fermeture : {}", motif),
+        }
+    }
+}
+
+/// Dimanche de Pâques (calendrier grégorien, algorithme de Meeus/Jones/Butcher).
+pub fn paques(annee: i32) -> NaiveDate {
+    let a = annee % 19;
+    let b = annee / 100;
+    let c = annee % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let mois = (h + l - 7 * m + 114) / 31;
+    let jour = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(annee, mois as u32, jour as u32).unwrap()
+}
+
+/// Jours fériés légaux en France pour une année, avec les deux jours
+/// supplémentaires d'Alsace-Moselle si demandé.
+pub fn jours_feries(annee: i32, alsace_moselle: bool) -> Vec<(NaiveDate, &'static str)> {
+    let date = |mois, jour| NaiveDate::from_ymd_opt(annee, mois, jour).unwrap();
+    let paques = paques(annee);
+
+    let mut feries = vec![
+        (date(1, 1), "Jour de l'an"),
+        (paques + Duration::days(1), "Lundi de Pâques"),
+        (date(5, 1), "Fête du Travail"),
+        (date(5, 8), "Victoire 1945"),
+        (paques + Duration::days(39), "Ascension"),
+        (paques + Duration::days(50), "Lundi de Pentecôte"),
+        (date(7, 14), "Fête nationale"),
+        (date(8, 15), "Assomption"),
+        (date(11, 1), "Toussaint"),
+        (date(11, 11), "Armistice 1918"),
+        (date(12, 25), "Noël"),
+    ];
+
+    if alsace_moselle {
+        feries.push((paques - Duration::days(2), "Vendredi saint"));
+        feries.push((date(12, 26), "Saint-Étienne"));
+    }
+
+    feries.sort();
+    feries
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Calendrier {
+    pub alsace_moselle: bool,
+    /// Jours de fermeture de l'entreprise et leur motif
+    pub fermetures: BTreeMap<NaiveDate, String>,
+}
+
+impl Calendrier {
+    pub fn type_jour(&self, date: NaiveDate) -> TypeJour {
+        if let Some((_, nom)) = jours_feries(date.year(), self.alsace_moselle).into_iter().find(|(d, _)| *d == date) {
+            return TypeJour::Ferie(nom.to_string());
+        }
+        if let Some(motif) = self.fermetures.get(&date) {
+            return TypeJour::Fermeture(motif.clone());
+        }
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return TypeJour::WeekEnd;
+        }
+        TypeJour::Ouvre
+    }
+
+    pub fn ajouter_fermeture(&mut self, date: NaiveDate, motif: &str) {
+        let motif = if motif.trim().is_empty() { "Fermeture" } else { motif.trim() };
+        self.fermetures.insert(date, motif.to_string());
+    }
+}
+
+/// Heures effectuées sur des jours non ouvrés, à majorer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeuresNonOuvrees {
+    pub week_end: f64,
+    pub feries: f64,
+    pub fermetures: f64,
+}
+
+impl HeuresNonOuvrees {
+    pub fn total(&self) -> f64 {
+        self.week_end + self.feries + self.fermetures
+    }
+}
+
+pub fn heures_non_ouvrees(calendrier: &Calendrier, temps_par_jour: &HashMap<NaiveDate, f64>) -> HeuresNonOuvrees {
+    let mut heures = HeuresNonOuvrees::default();
+
+    for (date, &total) in temps_par_jour {
+        match calendrier.type_jour(*date) {
+            TypeJour::Ouvre => {}
+            TypeJour::WeekEnd => heures.week_end += total,
+            TypeJour::Ferie(_) => heures.feries += total,
+            TypeJour::Fermeture(_) => heures.fermetures += total,
+        }
+    }
+    heures
+}
+
+fn couleur_jour(type_jour: &TypeJour) -> egui::Color32 {
+    match type_jour {
+        TypeJour::Ouvre => egui::Color32::GRAY,
+        TypeJour::WeekEnd => egui::Color32::from_rgb(70, 130, 180),
+        TypeJour::Ferie(_) => egui::Color32::from_rgb(200, 60, 140),
+        TypeJour::Fermeture(_) => egui::Color32::from_rgb(255, 140, 0),
+    }
+}
+
+impl GestionTempsApp {
+    pub fn heures_non_ouvrees(&self) -> HeuresNonOuvrees {
+        heures_non_ouvrees(&self.calendrier, &self.temps_par_jour)
+    }
+
+    pub(crate) fn afficher_temps_par_jour(&self, ui: &mut egui::Ui) {
+        ui.separator();
+        ui.heading("Temps de travail par jour :");
+
+        // Les jours d'absence complète n'ont pas de temps travaillé mais sont affichés
+        let mut trie_par_date: BTreeMap<NaiveDate, f64> = self.temps_par_jour.iter().map(|(d, t)| (*d, *t)).collect();
+        if let Some(ref resultat) = self.resultat {
+            for date in resultat.absences.iter().filter_map(|a| a.date) {
+                trie_par_date.entry(date).or_insert(0.0);
+            }
+        }
+
+        for (date, &total) in &trie_par_date {
+            let type_jour = self.calendrier.type_jour(*date);
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Date: {} - Temps total: {} ( {:.2}h )",
+                    date,
+                    self.format_duree_en_heures(&total),
+                    total
+                ));
+                if !type_jour.est_ouvre() {
+                    ui.colored_label(couleur_jour(&type_jour), type_jour.libelle());
+                }
+                if let Some(ref resultat) = self.resultat {
+                    for absence in resultat.absences_du_jour(*date) {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(60, 160, 90),
+                            format!("🌴 {} ({} j, {:.2}h)", absence.type_absence, absence.jours, absence.heures),
+                        );
+                    }
+                }
+            });
+        }
+
+        let non_ouvrees = self.heures_non_ouvrees();
+        if non_ouvrees.total() > 0.0 {
+            ui.group(|ui| {
+                ui.strong(format!("Travail sur jours non ouvrés (à majorer): {:.2} heures", non_ouvrees.total()));
+                ui.label(format!("Week-end: {:.2}h", non_ouvrees.week_end));
+                ui.label(format!("Jours fériés: {:.2}h", non_ouvrees.feries));
+                ui.label(format!("Fermetures: {:.2}h", non_ouvrees.fermetures));
+            });
+        }
+    }
+
+    pub(crate) fn afficher_calendrier(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("📆 Jours fériés et fermetures", |ui| {
+            ui.checkbox(&mut self.calendrier.alsace_moselle, "Alsace-Moselle (Vendredi saint et Saint-Étienne)");
+
+            ui.horizontal(|ui| {
+                ui.label("Fermeture le");
+                ui.add(egui::TextEdit::singleline(&mut self.date_fermeture).desired_width(90.0).hint_text("DD/MM/YYYY"));
+                ui.add(egui::TextEdit::singleline(&mut self.motif_fermeture).desired_width(160.0).hint_text("Motif"));
+                if ui.button("➕ Ajouter").clicked() {
+                    match date_de(&self.date_fermeture) {
+                        Some(date) => {
+                            self.calendrier.ajouter_fermeture(date, &self.motif_fermeture);
+                            self.date_fermeture.clear();
+                            self.motif_fermeture.clear();
+                        }
+                        None => self.status_message = format!("Date de fermeture invalide '{}'", self.date_fermeture),
+                    }
+                }
+            });
+
+            let mut a_supprimer = None;
+            for (date, motif) in &self.calendrier.fermetures {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} - {}", date.format("%d/%m/%Y"), motif));
+                    if ui.small_button("🗑").clicked() {
+                        a_supprimer = Some(*date);
+                    }
+                });
+            }
+            if let Some(date) = a_supprimer {
+                self.calendrier.fermetures.remove(&date);
+            }
+        });
+    }
+}
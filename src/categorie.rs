@@ -0,0 +1,286 @@
+use eframe::egui;
+use plotters::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{GestionTempsApp, ResultatCalcul};
+
+/// Fichier de règles lu et écrit depuis le panneau des catégories.
+pub const FICHIER_REGLES: &str = "categories.txt";
+pub const FICHIER_GRAPHIQUE_CATEGORIES: &str = "temps_par_categorie.png";
+pub const CATEGORIE_PAR_DEFAUT: &str = "Autre";
+
+/// Règle d'attribution d'une catégorie : un préfixe de code ABS ou un mot-clé
+/// présent dans la description suffit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegleCategorie {
+    pub categorie: String,
+    pub mots_cles: Vec<String>,
+    pub prefixes_code: Vec<String>,
+}
+
+impl RegleCategorie {
+    pub fn new(categorie: &str, mots_cles: &[&str], prefixes_code: &[&str]) -> Self {
+        RegleCategorie {
+            categorie: categorie.to_string(),
+            mots_cles: mots_cles.iter().map(|m| m.to_lowercase()).collect(),
+            prefixes_code: prefixes_code.iter().map(|p| p.to_uppercase()).collect(),
+        }
+    }
+
+    pub fn correspond(&self, code: &str, description: &str) -> bool {
+        let code = code.trim().to_uppercase();
+        let description = description.to_lowercase();
+
+        self.prefixes_code.iter().any(|p| !p.is_empty() && code.starts_with(p.as_str()))
+            || self.mots_cles.iter().any(|m| !m.is_empty() && description.contains(m.as_str()))
+    }
+}
+
+/// Règles appliquées dans l'ordre : la première qui correspond l'emporte.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReglesCategories {
+    pub regles: Vec<RegleCategorie>,
+}
+
+impl Default for ReglesCategories {
+    fn default() -> Self {
+        ReglesCategories {
+            regles: vec![
+                RegleCategorie::new("Absence", &["absence", "congé", "conge", "maladie", "rtt"], &[]),
+                RegleCategorie::new("Formation", &["formation"], &[]),
+                RegleCategorie::new("Réunion", &["réunion", "reunion"], &[]),
+                RegleCategorie::new("Maintenance véhicule", &["véhicule", "vehicule"], &[]),
+                RegleCategorie::new(
+                    "Préparation matériel",
+                    &["préparation", "preparation", "matériel", "materiel", "outillage", "inventaire", "stock"],
+                    &[],
+                ),
+                RegleCategorie::new("Administratif", &["rapport", "administratif"], &[]),
+            ],
+        }
+    }
+}
+
+fn liste(texte: &str) -> Vec<String> {
+    texte.split(',').map(str::trim).filter(|m| !m.is_empty()).map(str::to_string).collect()
+}
+
+impl ReglesCategories {
+    /// Première règle correspondant à la ligne, s'il y en a une.
+    pub fn trouver(&self, code: &str, description: &str) -> Option<&RegleCategorie> {
+        self.regles.iter().find(|r| r.correspond(code, description))
+    }
+
+    pub fn categoriser(&self, code: &str, description: &str) -> String {
+        self.trouver(code, description)
+            .map(|r| r.categorie.clone())
+            .unwrap_or_else(|| CATEGORIE_PAR_DEFAUT.to_string())
+    }
+
+    /// Lit des règles au format "Catégorie;mot-clé, mot-clé;PRÉFIXE, PRÉFIXE".
+    /// Les lignes vides et celles commençant par '#' sont ignorées.
+    pub fn depuis_texte(texte: &str) -> Result<Self, String> {
+        let mut regles = Vec::new();
+
+        for (numero, ligne) in texte.lines().enumerate() {
+            let ligne = ligne.trim();
+            if ligne.is_empty() || ligne.starts_with('#') {
+                continue;
+            }
+
+            let parties: Vec<&str> = ligne.split(';').map(str::trim).collect();
+            if parties[0].is_empty() || parties.len() > 3 {
+                return Err(format!("Ligne {}: format attendu 'Catégorie;mots-clés;préfixes de code'", numero + 1));
+            }
+
+            let mots_cles = liste(parties.get(1).unwrap_or(&""));
+            let prefixes_code = liste(parties.get(2).unwrap_or(&""));
+            if mots_cles.is_empty() && prefixes_code.is_empty() {
+                return Err(format!("Ligne {}: aucun mot-clé ni préfixe de code pour '{}'", numero + 1, parties[0]));
+            }
+
+            regles.push(RegleCategorie {
+                categorie: parties[0].to_string(),
+                mots_cles: mots_cles.iter().map(|m| m.to_lowercase()).collect(),
+                prefixes_code: prefixes_code.iter().map(|p| p.to_uppercase()).collect(),
+            });
+        }
+
+        Ok(ReglesCategories { regles })
+    }
+
+    pub fn vers_texte(&self) -> String {
+        let mut texte = String::from("# Catégorie;mots-clés de la description;préfixes de code ABS\n");
+        for regle in &self.regles {
+            texte.push_str(&format!(
+                "{};{};{}\n",
+                regle.categorie,
+                regle.mots_cles.join(", "),
+                regle.prefixes_code.join(", ")
+            ));
+        }
+        texte
+    }
+
+    pub fn charger(chemin: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let texte = fs::read_to_string(chemin)?;
+        Ok(Self::depuis_texte(&texte)?)
+    }
+
+    pub fn enregistrer(&self, chemin: &Path) -> io::Result<()> {
+        fs::write(chemin, self.vers_texte())
+    }
+}
+
+impl ResultatCalcul {
+    /// Sous-totaux du temps hors clientèle par catégorie d'activité.
+    pub fn par_categorie(&self) -> BTreeMap<String, f64> {
+        let mut sous_totaux = BTreeMap::new();
+        for detail in &self.details_hors_clientele {
+            *sous_totaux.entry(detail.categorie.clone()).or_insert(0.0) += detail.duree;
+        }
+        sous_totaux
+    }
+}
+
+/// Histogramme des heures hors clientèle par catégorie, écrit en PNG dans `chemin`.
+pub fn creer_histogramme(sous_totaux: &BTreeMap<String, f64>, chemin: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(chemin, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let categories: Vec<&String> = sous_totaux.keys().collect();
+    let maximum = sous_totaux.values().cloned().fold(0.0, f64::max);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Temps hors clientèle par catégorie", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        // L'axe segmenté inclut la borne haute : n catégories occupent 0..n-1
+        .build_cartesian_2d((0..categories.len() as u32 - 1).into_segmented(), 0.0..maximum * 1.15)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .y_desc("Heures")
+        .x_label_formatter(&|x| match x {
+            SegmentValue::CenterOf(i) => categories.get(*i as usize).map(|c| c.to_string()).unwrap_or_default(),
+            _ => String::new(),
+        })
+        .draw()?;
+
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(BLUE.mix(0.7).filled())
+            .margin(20)
+            .data(sous_totaux.values().enumerate().map(|(i, heures)| (i as u32, *heures))),
+    )?;
+
+    root.present()?;
+
+    Ok(())
+}
+
+impl GestionTempsApp {
+    /// Applique le texte du panneau des règles ; en cas d'erreur les règles courantes sont conservées.
+    pub fn appliquer_regles_categories(&mut self) {
+        match ReglesCategories::depuis_texte(&self.texte_regles_categories) {
+            Ok(regles) => {
+                self.regles_categories = regles;
+                self.recategoriser();
+                self.status_message = "Règles de catégories appliquées".to_string();
+            }
+            Err(e) => self.status_message = format!("Règles de catégories invalides: {}", e),
+        }
+    }
+
+    /// Recalcule la catégorie des détails déjà affichés après un changement de règles.
+    pub fn recategoriser(&mut self) {
+        if let Some(ref mut resultat) = self.resultat {
+            for detail in &mut resultat.details_hors_clientele {
+                detail.categorie = self.regles_categories.categoriser(&detail.code, &detail.description);
+            }
+        }
+    }
+
+    pub fn generer_graphique_categories(&mut self) {
+        let Some(ref resultat) = self.resultat else {
+            return;
+        };
+        let sous_totaux = resultat.par_categorie();
+        if sous_totaux.is_empty() {
+            self.status_message = "Aucune entrée hors clientèle à représenter".to_string();
+            return;
+        }
+        self.status_message = match creer_histogramme(&sous_totaux, Path::new(FICHIER_GRAPHIQUE_CATEGORIES)) {
+            Ok(()) => format!("Graphique généré avec succès : {}", FICHIER_GRAPHIQUE_CATEGORIES),
+            Err(e) => format!("Erreur lors de la génération du graphique: {}", e),
+        };
+    }
+
+    pub(crate) fn afficher_regles_categories(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("🏷️ Règles de catégories", |ui| {
+            if self.texte_regles_categories.is_empty() {
+                self.texte_regles_categories = self.regles_categories.vers_texte();
+            }
+
+            ui.small("Une règle par ligne : Catégorie;mots-clés de la description;préfixes de code ABS");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.texte_regles_categories)
+                    .id_source("regles_categories")
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(6),
+            );
+
+            ui.horizontal(|ui| {
+                if ui.button("✔ Appliquer").clicked() {
+                    self.appliquer_regles_categories();
+                }
+                if ui.button("📂 Charger").clicked() {
+                    match ReglesCategories::charger(Path::new(FICHIER_REGLES)) {
+                        Ok(regles) => {
+                            self.texte_regles_categories = regles.vers_texte();
+                            self.regles_categories = regles;
+                            self.recategoriser();
+                            self.status_message = format!("Règles chargées depuis {}", FICHIER_REGLES);
+                        }
+                        Err(e) => self.status_message = format!("Impossible de charger {}: {}", FICHIER_REGLES, e),
+                    }
+                }
+                if ui.button("💾 Enregistrer").clicked() {
+                    self.status_message = match self.regles_categories.enregistrer(Path::new(FICHIER_REGLES)) {
+                        Ok(()) => format!("Règles enregistrées dans {}", FICHIER_REGLES),
+                        Err(e) => format!("Impossible d'écrire {}: {}", FICHIER_REGLES, e),
+                    };
+                }
+                if ui.button("↺ Par défaut").clicked() {
+                    self.regles_categories = ReglesCategories::default();
+                    self.texte_regles_categories = self.regles_categories.vers_texte();
+                    self.recategoriser();
+                }
+            });
+        });
+    }
+
+    pub(crate) fn afficher_categories(&self, ui: &mut egui::Ui, resultat: &ResultatCalcul) {
+        let sous_totaux = resultat.par_categorie();
+        if sous_totaux.is_empty() {
+            return;
+        }
+
+        ui.collapsing("🏷️ Hors clientèle par catégorie", |ui| {
+            egui::Grid::new("sous_totaux_categories").striped(true).show(ui, |ui| {
+                for (categorie, heures) in &sous_totaux {
+                    ui.label(categorie);
+                    ui.label(format!("{:.2}h", heures));
+                    ui.label(self.format_duree_en_heures(heures));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
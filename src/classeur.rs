@@ -0,0 +1,276 @@
+use eframe::egui;
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use crate::{GestionTempsApp, ZoneSaisie};
+
+/// Extensions lues comme classeurs plutôt que comme texte.
+pub const EXTENSIONS_CLASSEUR: [&str; 2] = ["xlsx", "ods"];
+
+/// Nombre de lignes montrées dans l'aperçu d'import.
+const LIGNES_APERCU: usize = 8;
+
+/// Import de classeur en attente du choix de la feuille et de la ligne d'en-tête.
+#[derive(Clone, Debug)]
+pub struct ImportClasseur {
+    pub chemin: PathBuf,
+    pub zone: ZoneSaisie,
+    pub feuilles: Vec<String>,
+    pub feuille: usize,
+    /// Numéro (à partir de 1) de la ligne d'en-tête, 0 si la feuille n'en a pas
+    pub ligne_entete: usize,
+    pub lignes: Vec<Vec<String>>,
+}
+
+pub fn est_classeur(chemin: &Path) -> bool {
+    chemin
+        .extension()
+        .map(|e| EXTENSIONS_CLASSEUR.contains(&e.to_string_lossy().to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Convertit un numéro de série Excel (jours depuis le 30/12/1899) en date et heure.
+pub fn date_excel_vers_datetime(serie: f64) -> Option<NaiveDateTime> {
+    if !serie.is_finite() || serie < 0.0 {
+        return None;
+    }
+
+    let origine = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    // Arrondi à la seconde : Excel stocke 08:00 comme 0.33333...
+    let secondes = (serie * 86_400.0).round() as i64;
+    origine.checked_add_signed(Duration::seconds(secondes))
+}
+
+/// Reformate une date ISO 8601 (cellules ODS) au format des exports.
+fn date_iso_vers_texte(iso: &str) -> Option<String> {
+    NaiveDateTime::parse_from_str(iso, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(iso, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(iso, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.format("%d/%m/%Y %H:%M").to_string())
+}
+
+/// En-têtes des colonnes d'horodatage des exports.
+const ENTETES_DATE: [&str; 10] = [
+    "date",
+    "début",
+    "debut",
+    "fin",
+    "date début",
+    "date debut",
+    "date de début",
+    "date de debut",
+    "date fin",
+    "date de fin",
+];
+
+/// Les colonnes de dates non formatées arrivent comme de simples nombres.
+/// L'en-tête doit correspondre en entier : "Heures travail" ou "Durée fin de trajet"
+/// sont des durées décimales, pas des dates.
+fn est_colonne_date(entete: &str) -> bool {
+    let entete = entete.trim().to_lowercase();
+    ENTETES_DATE.contains(&entete.as_str())
+}
+
+fn cellule_vers_texte(cellule: &Data, colonne_date: bool) -> String {
+    match cellule {
+        Data::DateTime(valeur) => date_excel_vers_datetime(valeur.as_f64())
+            .map(|dt| dt.format("%d/%m/%Y %H:%M").to_string())
+            .unwrap_or_else(|| valeur.to_string()),
+        Data::DateTimeIso(iso) => date_iso_vers_texte(iso).unwrap_or_else(|| iso.clone()),
+        Data::Float(valeur) if colonne_date => date_excel_vers_datetime(*valeur)
+            .map(|dt| dt.format("%d/%m/%Y %H:%M").to_string())
+            .unwrap_or_else(|| valeur.to_string()),
+        autre => autre.to_string(),
+    }
+}
+
+pub fn lister_feuilles(chemin: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let classeur = open_workbook_auto(chemin)?;
+    Ok(classeur.sheet_names().to_vec())
+}
+
+/// Lit une feuille en texte. Les lignes situées avant l'en-tête (titres d'export)
+/// sont conservées ; l'en-tête sert à repérer les colonnes de dates.
+pub fn lire_feuille(chemin: &Path, feuille: &str, ligne_entete: usize) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let mut classeur = open_workbook_auto(chemin)?;
+    let plage = classeur.worksheet_range(feuille)?;
+
+    let colonnes_date: Vec<bool> = match ligne_entete.checked_sub(1).and_then(|i| plage.rows().nth(i)) {
+        Some(entete) => entete.iter().map(|c| est_colonne_date(&c.to_string())).collect(),
+        None => Vec::new(),
+    };
+
+    Ok(plage
+        .rows()
+        .enumerate()
+        .map(|(numero, ligne)| {
+            ligne
+                .iter()
+                .enumerate()
+                .map(|(colonne, cellule)| {
+                    let colonne_date = numero + 1 > ligne_entete && colonnes_date.get(colonne).copied().unwrap_or(false);
+                    cellule_vers_texte(cellule, colonne_date)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Texte tabulé des lignes situées après l'en-tête, prêt pour les parsers.
+pub fn lignes_vers_tsv(lignes: &[Vec<String>], ligne_entete: usize) -> String {
+    lignes
+        .iter()
+        .skip(ligne_entete)
+        .filter(|ligne| ligne.iter().any(|c| !c.trim().is_empty()))
+        .map(|ligne| ligne.join("\t"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Une première ligne sans aucune date est très probablement l'en-tête : renvoie 1, sinon 0.
+fn detecter_ligne_entete(lignes: &[Vec<String>]) -> usize {
+    let sans_date = lignes.first().is_some_and(|l| !l.iter().any(|c| c.contains('/') && c.contains(':')));
+    usize::from(sans_date)
+}
+
+/// Lit la première feuille d'un classeur, en détectant la ligne d'en-tête comme l'import guidé.
+pub fn lire_premiere_feuille(chemin: &Path) -> Result<String, Box<dyn Error>> {
+    let feuilles = lister_feuilles(chemin)?;
+    let premiere = feuilles.first().ok_or("Le classeur ne contient aucune feuille")?;
+
+    let mut lignes = lire_feuille(chemin, premiere, 0)?;
+    let ligne_entete = detecter_ligne_entete(&lignes);
+    if ligne_entete > 0 {
+        lignes = lire_feuille(chemin, premiere, ligne_entete)?;
+    }
+    Ok(lignes_vers_tsv(&lignes, ligne_entete))
+}
+
+impl ImportClasseur {
+    pub fn ouvrir(chemin: &Path, zone: ZoneSaisie) -> Result<Self, Box<dyn Error>> {
+        let feuilles = lister_feuilles(chemin)?;
+        if feuilles.is_empty() {
+            return Err("Le classeur ne contient aucune feuille".into());
+        }
+
+        let mut import = ImportClasseur {
+            chemin: chemin.to_path_buf(),
+            zone,
+            feuilles,
+            feuille: 0,
+            ligne_entete: 0,
+            lignes: Vec::new(),
+        };
+        import.recharger()?;
+
+        import.ligne_entete = detecter_ligne_entete(&import.lignes);
+        if import.ligne_entete > 0 {
+            import.recharger()?;
+        }
+        Ok(import)
+    }
+
+    pub fn recharger(&mut self) -> Result<(), Box<dyn Error>> {
+        self.lignes = lire_feuille(&self.chemin, &self.feuilles[self.feuille], self.ligne_entete)?;
+        Ok(())
+    }
+
+    pub fn texte(&self) -> String {
+        lignes_vers_tsv(&self.lignes, self.ligne_entete)
+    }
+}
+
+impl GestionTempsApp {
+    pub fn ouvrir_import_classeur(&mut self, zone: ZoneSaisie, chemin: &Path) {
+        match ImportClasseur::ouvrir(chemin, zone) {
+            Ok(import) => self.import_classeur = Some(import),
+            Err(e) => {
+                self.status_message = format!("Erreur lors de la lecture de {}: {}", chemin.display(), e);
+            }
+        }
+    }
+
+    /// Verse la feuille choisie dans la zone de saisie visée.
+    pub fn valider_import_classeur(&mut self) {
+        if let Some(import) = self.import_classeur.take() {
+            let texte = import.texte();
+            let nombre_lignes = texte.lines().count();
+            *self.zone_mut(import.zone) = texte;
+            self.status_message = format!(
+                "{} ligne(s) importée(s) depuis la feuille '{}'",
+                nombre_lignes, import.feuilles[import.feuille]
+            );
+        }
+    }
+
+    pub(crate) fn afficher_import_classeur(&mut self, ctx: &egui::Context) {
+        let Some(import) = self.import_classeur.as_mut() else {
+            return;
+        };
+
+        let mut ouverte = true;
+        let mut valider = false;
+        let mut erreur = None;
+
+        egui::Window::new("📗 Import de classeur")
+            .open(&mut ouverte)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(import.chemin.display().to_string());
+
+                let feuille_avant = import.feuille;
+                let entete_avant = import.ligne_entete;
+
+                egui::ComboBox::from_label("Feuille")
+                    .selected_text(import.feuilles[import.feuille].clone())
+                    .show_ui(ui, |ui| {
+                        for (index, nom) in import.feuilles.iter().enumerate() {
+                            ui.selectable_value(&mut import.feuille, index, nom);
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    ui.label("Ligne d'en-tête (0 = aucune):");
+                    ui.add(egui::DragValue::new(&mut import.ligne_entete).clamp_range(0..=50));
+                });
+
+                if import.feuille != feuille_avant || import.ligne_entete != entete_avant {
+                    if let Err(e) = import.recharger() {
+                        erreur = Some(e.to_string());
+                    }
+                }
+
+                ui.separator();
+                egui::ScrollArea::both().max_height(200.0).show(ui, |ui| {
+                    egui::Grid::new("apercu_classeur").striped(true).show(ui, |ui| {
+                        for (numero, ligne) in import.lignes.iter().take(import.ligne_entete + LIGNES_APERCU).enumerate() {
+                            for cellule in ligne {
+                                if numero + 1 == import.ligne_entete {
+                                    ui.strong(cellule);
+                                } else if numero < import.ligne_entete {
+                                    ui.weak(cellule);
+                                } else {
+                                    ui.label(cellule);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                ui.separator();
+                valider = ui.button("✅ Importer").clicked();
+            });
+
+        if let Some(e) = erreur {
+            self.status_message = format!("Erreur lors de la lecture du classeur: {}", e);
+        }
+        if valider {
+            self.valider_import_classeur();
+        } else if !ouverte {
+            self.import_classeur = None;
+        }
+    }
+}
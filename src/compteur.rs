@@ -0,0 +1,340 @@
+use eframe::egui;
+use chrono::{Local, NaiveDate};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::GestionTempsApp;
+
+/// Fichier du compteur d'heures, relu au démarrage de l'application.
+pub const FICHIER_COMPTEUR: &str = "compteur_heures.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeMouvement {
+    /// Écart de la période calculée par rapport à l'horaire du contrat
+    Periode,
+    JoursRecuperes,
+    HeuresPayees,
+    Ajustement,
+}
+
+impl TypeMouvement {
+    pub const MANUELS: [TypeMouvement; 3] =
+        [TypeMouvement::JoursRecuperes, TypeMouvement::HeuresPayees, TypeMouvement::Ajustement];
+
+    pub fn libelle(&self) -> &'static str {
+        match self {
+            TypeMouvement::Periode => "Période",
+            TypeMouvement::JoursRecuperes => "Jours récupérés",
+            TypeMouvement::HeuresPayees => "Heures payées",
+            TypeMouvement::Ajustement => "Ajustement",
+        }
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            TypeMouvement::Periode => "periode",
+            TypeMouvement::JoursRecuperes => "recuperation",
+            TypeMouvement::HeuresPayees => "paiement",
+            TypeMouvement::Ajustement => "ajustement",
+        }
+    }
+
+    fn depuis_code(code: &str) -> Option<Self> {
+        [TypeMouvement::Periode, TypeMouvement::JoursRecuperes, TypeMouvement::HeuresPayees, TypeMouvement::Ajustement]
+            .into_iter()
+            .find(|t| t.code() == code)
+    }
+}
+
+/// Ligne du compteur : `heures` est positif quand il crédite le compteur.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MouvementCompteur {
+    pub employe: String,
+    pub date: NaiveDate,
+    pub type_mouvement: TypeMouvement,
+    /// Période concernée, pour un report d'écart
+    pub periode: Option<(NaiveDate, NaiveDate)>,
+    pub heures: f64,
+    pub libelle: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompteurHeures {
+    pub mouvements: Vec<MouvementCompteur>,
+}
+
+fn date_texte(date: NaiveDate) -> String {
+    date.format("%d/%m/%Y").to_string()
+}
+
+fn lire_date(texte: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(texte.trim(), "%d/%m/%Y").ok()
+}
+
+/// Échappe un champ texte du compteur : '%', ';' et les retours à la ligne sont codés
+/// en %XX, ainsi qu'un '#' initial qui ferait prendre la ligne pour un commentaire.
+fn echapper(champ: &str) -> String {
+    let mut texte = String::with_capacity(champ.len());
+    for (position, c) in champ.chars().enumerate() {
+        match c {
+            '%' => texte.push_str("%25"),
+            ';' => texte.push_str("%3B"),
+            '\n' => texte.push_str("%0A"),
+            '\r' => texte.push_str("%0D"),
+            '#' if position == 0 => texte.push_str("%23"),
+            autre => texte.push(autre),
+        }
+    }
+    texte
+}
+
+fn desechapper(champ: &str) -> String {
+    let mut texte = String::with_capacity(champ.len());
+    let mut reste = champ;
+    while let Some(position) = reste.find('%') {
+        texte.push_str(&reste[..position]);
+        let code = reste.get(position + 1..position + 3);
+        match code.and_then(|code| u8::from_str_radix(code, 16).ok()) {
+            Some(octet @ (b'%' | b';' | b'\n' | b'\r' | b'#')) => {
+                texte.push(octet as char);
+                reste = &reste[position + 3..];
+            }
+            _ => {
+                texte.push('%');
+                reste = &reste[position + 1..];
+            }
+        }
+    }
+    texte.push_str(reste);
+    texte
+}
+
+impl CompteurHeures {
+    pub fn solde(&self, employe: &str) -> f64 {
+        self.mouvements.iter().filter(|m| m.employe == employe).map(|m| m.heures).sum()
+    }
+
+    /// Mouvements d'un employé dans l'ordre d'enregistrement, avec le solde après chacun.
+    pub fn historique(&self, employe: &str) -> Vec<(&MouvementCompteur, f64)> {
+        let mut solde = 0.0;
+        self.mouvements
+            .iter()
+            .filter(|m| m.employe == employe)
+            .map(|m| {
+                solde += m.heures;
+                (m, solde)
+            })
+            .collect()
+    }
+
+    /// Reporte l'écart d'une période ; un nouveau report de la même période remplace l'ancien.
+    pub fn reporter_periode(&mut self, employe: &str, debut: NaiveDate, fin: NaiveDate, ecart: f64, date: NaiveDate) {
+        self.mouvements.retain(|m| {
+            !(m.employe == employe && m.type_mouvement == TypeMouvement::Periode && m.periode == Some((debut, fin)))
+        });
+        self.mouvements.push(MouvementCompteur {
+            employe: employe.to_string(),
+            date,
+            type_mouvement: TypeMouvement::Periode,
+            periode: Some((debut, fin)),
+            heures: ecart,
+            libelle: format!("Du {} au {}", date_texte(debut), date_texte(fin)),
+        });
+    }
+
+    /// Lit le compteur au format "employé;date;type;du;au;heures;libellé",
+    /// employé et libellé échappés en %XX.
+    pub fn depuis_texte(texte: &str) -> Result<Self, String> {
+        let mut mouvements = Vec::new();
+
+        for (numero, ligne) in texte.lines().enumerate() {
+            if ligne.trim().is_empty() || ligne.starts_with('#') {
+                continue;
+            }
+
+            let erreur = || format!("Ligne {} du compteur illisible: {}", numero + 1, ligne);
+            let parties: Vec<&str> = ligne.splitn(7, ';').collect();
+            if parties.len() != 7 {
+                return Err(erreur());
+            }
+
+            let periode = match (lire_date(parties[3]), lire_date(parties[4])) {
+                (Some(debut), Some(fin)) => Some((debut, fin)),
+                _ => None,
+            };
+            mouvements.push(MouvementCompteur {
+                employe: desechapper(parties[0]),
+                date: lire_date(parties[1]).ok_or_else(erreur)?,
+                type_mouvement: TypeMouvement::depuis_code(parties[2]).ok_or_else(erreur)?,
+                periode,
+                heures: parties[5].trim().parse().map_err(|_| erreur())?,
+                libelle: desechapper(parties[6]),
+            });
+        }
+
+        Ok(CompteurHeures { mouvements })
+    }
+
+    pub fn vers_texte(&self) -> String {
+        let mut texte = String::from("# employé;date;type;du;au;heures;libellé\n");
+        for m in &self.mouvements {
+            let (du, au) = match m.periode {
+                Some((debut, fin)) => (date_texte(debut), date_texte(fin)),
+                None => (String::new(), String::new()),
+            };
+            texte.push_str(&format!(
+                "{};{};{};{};{};{};{}\n",
+                echapper(&m.employe),
+                date_texte(m.date),
+                m.type_mouvement.code(),
+                du,
+                au,
+                m.heures,
+                echapper(&m.libelle)
+            ));
+        }
+        texte
+    }
+
+    pub fn charger(chemin: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let texte = fs::read_to_string(chemin)?;
+        Ok(Self::depuis_texte(&texte)?)
+    }
+
+    pub fn enregistrer(&self, chemin: &Path) -> io::Result<()> {
+        fs::write(chemin, self.vers_texte())
+    }
+}
+
+/// Ajustement manuel en cours de saisie.
+#[derive(Clone, Debug)]
+pub struct FormulaireMouvement {
+    pub type_mouvement: TypeMouvement,
+    /// Heures saisies en positif ; récupérations et paiements sont débités
+    pub heures: f64,
+    pub libelle: String,
+}
+
+impl Default for FormulaireMouvement {
+    fn default() -> Self {
+        FormulaireMouvement {
+            type_mouvement: TypeMouvement::JoursRecuperes,
+            heures: 0.0,
+            libelle: String::new(),
+        }
+    }
+}
+
+impl GestionTempsApp {
+    /// Clé du compteur : matricule de l'employé actif, sinon son nom ; vide sans employé.
+    pub fn cle_compteur(&self) -> String {
+        match self.employe_actif.and_then(|i| self.employes.get(i)) {
+            Some(dossier) if !dossier.profil.matricule.trim().is_empty() => dossier.profil.matricule.trim().to_string(),
+            Some(dossier) => dossier.profil.nom.trim().to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Active l'enregistrement du compteur dans `chemin` et relit son contenu s'il existe.
+    pub fn charger_compteur(&mut self, chemin: PathBuf) {
+        if chemin.exists() {
+            match CompteurHeures::charger(&chemin) {
+                Ok(compteur) => self.compteur = compteur,
+                Err(e) => self.signaler(format!("Compteur d'heures non chargé ({}): {}", chemin.display(), e)),
+            }
+        }
+        self.fichier_compteur = Some(chemin);
+    }
+
+    fn enregistrer_compteur(&mut self) {
+        if let Some(ref chemin) = self.fichier_compteur {
+            if let Err(e) = self.compteur.enregistrer(chemin) {
+                self.status_message = format!("Impossible d'écrire {}: {}", chemin.display(), e);
+            }
+        }
+    }
+
+    /// Reporte le solde prévu / réalisé de la période affichée dans le compteur.
+    pub fn reporter_periode_au_compteur(&mut self) -> Option<f64> {
+        let lignes = self.ecarts_horaire();
+        let (premiere, derniere) = (lignes.first()?, lignes.last()?);
+        let (debut, fin, ecart) = (premiere.date, derniere.date, derniere.solde);
+
+        let cle = self.cle_compteur();
+        self.compteur.reporter_periode(&cle, debut, fin, ecart, Local::now().date_naive());
+        self.enregistrer_compteur();
+        Some(ecart)
+    }
+
+    pub fn ajouter_mouvement_compteur(&mut self, type_mouvement: TypeMouvement, heures: f64, libelle: &str) {
+        let heures = match type_mouvement {
+            TypeMouvement::JoursRecuperes | TypeMouvement::HeuresPayees => -heures.abs(),
+            TypeMouvement::Periode | TypeMouvement::Ajustement => heures,
+        };
+
+        self.compteur.mouvements.push(MouvementCompteur {
+            employe: self.cle_compteur(),
+            date: Local::now().date_naive(),
+            type_mouvement,
+            periode: None,
+            heures,
+            libelle: libelle.trim().to_string(),
+        });
+        self.enregistrer_compteur();
+    }
+
+    pub(crate) fn afficher_compteur(&mut self, ui: &mut egui::Ui) {
+        let cle = self.cle_compteur();
+        let solde = self.compteur.solde(&cle);
+
+        ui.collapsing(format!("🏦 Compteur d'heures (solde: {:+.2}h)", solde), |ui| {
+            if let Some(derniere) = self.ecarts_horaire().last() {
+                if ui
+                    .button(format!("➕ Reporter l'écart de la période ({:+.2}h)", derniere.solde))
+                    .on_hover_text("Un nouveau report de la même période remplace le précédent")
+                    .clicked()
+                {
+                    self.reporter_periode_au_compteur();
+                }
+            }
+
+            ui.horizontal(|ui| {
+                let formulaire = &mut self.formulaire_mouvement;
+                egui::ComboBox::from_id_source("type_mouvement")
+                    .selected_text(formulaire.type_mouvement.libelle())
+                    .show_ui(ui, |ui| {
+                        for choix in TypeMouvement::MANUELS {
+                            ui.selectable_value(&mut formulaire.type_mouvement, choix, choix.libelle());
+                        }
+                    });
+                ui.add(egui::DragValue::new(&mut formulaire.heures).speed(0.25).suffix(" h"));
+                ui.add(egui::TextEdit::singleline(&mut formulaire.libelle).desired_width(160.0).hint_text("Libellé"));
+                if ui.button("Enregistrer").clicked() && formulaire.heures != 0.0 {
+                    let formulaire = std::mem::take(&mut self.formulaire_mouvement);
+                    self.ajouter_mouvement_compteur(formulaire.type_mouvement, formulaire.heures, &formulaire.libelle);
+                }
+            });
+
+            let historique = self.compteur.historique(&cle);
+            if historique.is_empty() {
+                return;
+            }
+            egui::Grid::new("historique_compteur").striped(true).show(ui, |ui| {
+                for titre in ["Date", "Mouvement", "Libellé", "Heures", "Solde"] {
+                    ui.strong(titre);
+                }
+                ui.end_row();
+
+                for (mouvement, solde) in historique {
+                    ui.label(date_texte(mouvement.date));
+                    ui.label(mouvement.type_mouvement.libelle());
+                    ui.label(&mouvement.libelle);
+                    ui.label(format!("{:+.2}", mouvement.heures));
+                    ui.strong(format!("{:+.2}", solde));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
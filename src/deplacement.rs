@@ -0,0 +1,248 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+use crate::{EntreeSaisie, GestionTempsApp, TypeEntree};
+
+/// Nombre de trajets affichés dans le classement des plus longs.
+const TRAJETS_AFFICHES: usize = 5;
+
+/// Déplacements cumulés pour un client sur la période.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatistiquesClient {
+    pub client: String,
+    pub interventions: usize,
+    pub travail: f64,
+    pub deplacement: f64,
+    pub trajet_max: f64,
+}
+
+impl StatistiquesClient {
+    pub fn trajet_moyen(&self) -> f64 {
+        if self.interventions == 0 { 0.0 } else { self.deplacement / self.interventions as f64 }
+    }
+
+    /// Heures de trajet par heure de travail.
+    pub fn ratio(&self) -> f64 {
+        if self.travail > 0.0 { self.deplacement / self.travail } else { 0.0 }
+    }
+}
+
+fn interventions<'a, 'b>(entrees: &'b [&'a EntreeSaisie]) -> impl Iterator<Item = &'a EntreeSaisie> + 'b {
+    entrees.iter().copied().filter(|e| e.type_entree == TypeEntree::Clientele)
+}
+
+/// Statistiques de déplacement par client, du plus gros temps de trajet au plus petit.
+pub fn statistiques_par_client(entrees: &[&EntreeSaisie]) -> Vec<StatistiquesClient> {
+    let mut par_client: BTreeMap<String, StatistiquesClient> = BTreeMap::new();
+
+    for entree in interventions(entrees) {
+        let stats = par_client.entry(entree.client.clone()).or_insert_with(|| StatistiquesClient {
+            client: entree.client.clone(),
+            interventions: 0,
+            travail: 0.0,
+            deplacement: 0.0,
+            trajet_max: 0.0,
+        });
+        stats.interventions += 1;
+        stats.travail += entree.duree;
+        stats.deplacement += entree.deplacement;
+        stats.trajet_max = stats.trajet_max.max(entree.deplacement);
+    }
+
+    let mut statistiques: Vec<StatistiquesClient> = par_client.into_values().collect();
+    statistiques.sort_by(|a, b| b.deplacement.total_cmp(&a.deplacement));
+    statistiques
+}
+
+pub fn deplacement_par_jour(entrees: &[&EntreeSaisie]) -> BTreeMap<NaiveDate, f64> {
+    let mut par_jour = BTreeMap::new();
+    for entree in interventions(entrees) {
+        if let Some(date) = entree.date {
+            *par_jour.entry(date).or_insert(0.0) += entree.deplacement;
+        }
+    }
+    par_jour
+}
+
+pub fn trajets_les_plus_longs<'a>(entrees: &[&'a EntreeSaisie], nombre: usize) -> Vec<&'a EntreeSaisie> {
+    let mut trajets: Vec<&EntreeSaisie> = interventions(entrees).filter(|e| e.deplacement > 0.0).collect();
+    trajets.sort_by(|a, b| b.deplacement.total_cmp(&a.deplacement));
+    trajets.truncate(nombre);
+    trajets
+}
+
+/// Barème kilométrique des voitures (URSSAF 2024, reconduit en 2025), selon la puissance fiscale et
+/// la distance parcourue sur l'année.
+pub fn bareme_kilometrique(puissance_fiscale: u8, km: f64) -> f64 {
+    // (jusqu'à 5 000 km, de 5 001 à 20 000 km : coefficient et fixe, au-delà de 20 000 km)
+    let (jusqu_a_5000, coefficient, fixe, au_dela) = match puissance_fiscale {
+        0..=3 => (0.529, 0.316, 1065.0, 0.370),
+        4 => (0.606, 0.340, 1330.0, 0.407),
+        5 => (0.636, 0.357, 1395.0, 0.427),
+        6 => (0.665, 0.374, 1457.0, 0.447),
+        _ => (0.697, 0.394, 1515.0, 0.470),
+    };
+
+    if km <= 5000.0 {
+        km * jusqu_a_5000
+    } else if km <= 20000.0 {
+        km * coefficient + fixe
+    } else {
+        km * au_dela
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParametresIndemnites {
+    pub actif: bool,
+    pub puissance_fiscale: u8,
+    /// Kilomètres professionnels prévus sur l'année, 0 si inconnus
+    pub km_annuels: f64,
+    /// Kilomètres aller-retour pour une intervention chez chaque client
+    pub km_par_client: BTreeMap<String, f64>,
+}
+
+impl Default for ParametresIndemnites {
+    fn default() -> Self {
+        ParametresIndemnites {
+            actif: false,
+            puissance_fiscale: 5,
+            km_annuels: 0.0,
+            km_par_client: BTreeMap::new(),
+        }
+    }
+}
+
+/// Kilomètres et montant des indemnités de la période.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NoteDeFrais {
+    /// Client, nombre d'interventions et kilomètres parcourus
+    pub lignes: Vec<(String, usize, f64)>,
+    pub km_total: f64,
+    pub montant: f64,
+    /// Vrai si le barème annuel a été appliqué aux seuls kilomètres de la période,
+    /// faute de kilométrage annuel : le montant n'est alors qu'une estimation
+    pub estimation: bool,
+    /// Clients visités sans distance configurée
+    pub clients_sans_distance: Vec<String>,
+}
+
+pub fn note_de_frais(entrees: &[&EntreeSaisie], parametres: &ParametresIndemnites) -> NoteDeFrais {
+    let mut note = NoteDeFrais::default();
+
+    for stats in statistiques_par_client(entrees) {
+        match parametres.km_par_client.get(&stats.client) {
+            Some(&km) if km > 0.0 => {
+                let km = km * stats.interventions as f64;
+                note.km_total += km;
+                note.lignes.push((stats.client, stats.interventions, km));
+            }
+            _ => note.clients_sans_distance.push(stats.client),
+        }
+    }
+
+    // Le barème s'applique aux kilomètres de l'année ; la période en reçoit sa part
+    if parametres.km_annuels > note.km_total {
+        let annuel = bareme_kilometrique(parametres.puissance_fiscale, parametres.km_annuels);
+        note.montant = annuel * note.km_total / parametres.km_annuels;
+    } else {
+        note.montant = bareme_kilometrique(parametres.puissance_fiscale, note.km_total);
+        note.estimation = note.km_total > 0.0;
+    }
+    note
+}
+
+impl GestionTempsApp {
+    pub fn note_de_frais(&self) -> NoteDeFrais {
+        note_de_frais(&self.entrees_retenues(), &self.indemnites)
+    }
+
+    pub(crate) fn afficher_deplacements(&mut self, ui: &mut egui::Ui) {
+        let entrees = self.entrees_retenues();
+        let statistiques = statistiques_par_client(&entrees);
+        if statistiques.is_empty() {
+            return;
+        }
+        let par_jour = deplacement_par_jour(&entrees);
+        let plus_longs: Vec<EntreeSaisie> = trajets_les_plus_longs(&entrees, TRAJETS_AFFICHES).into_iter().cloned().collect();
+        let note = note_de_frais(&entrees, &self.indemnites);
+
+        ui.collapsing("🚗 Analyse des déplacements", |ui| {
+            ui.strong("Par client");
+            egui::Grid::new("deplacements_par_client").striped(true).show(ui, |ui| {
+                for titre in ["Client", "Interventions", "Travail", "Trajet", "Trajet moyen", "Trajet / travail", "Plus long"] {
+                    ui.strong(titre);
+                }
+                ui.end_row();
+
+                for stats in &statistiques {
+                    ui.label(&stats.client);
+                    ui.label(stats.interventions.to_string());
+                    ui.label(format!("{:.2}h", stats.travail));
+                    ui.label(format!("{:.2}h", stats.deplacement));
+                    ui.label(format!("{:.2}h", stats.trajet_moyen()));
+                    ui.label(format!("{:.0}%", stats.ratio() * 100.0));
+                    ui.label(format!("{:.2}h", stats.trajet_max));
+                    ui.end_row();
+                }
+            });
+
+            ui.add_space(5.0);
+            ui.strong("Par jour");
+            for (date, heures) in &par_jour {
+                ui.label(format!("{} : {:.2}h de trajet", date.format("%d/%m/%Y"), heures));
+            }
+
+            ui.add_space(5.0);
+            ui.strong("Trajets les plus longs");
+            for entree in &plus_longs {
+                ui.label(format!("{:.2}h - {} ({}, {})", entree.deplacement, entree.client, entree.code, entree.debut));
+            }
+
+            ui.add_space(5.0);
+            ui.checkbox(&mut self.indemnites.actif, "Calculer les indemnités kilométriques");
+            if !self.indemnites.actif {
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Puissance fiscale:");
+                ui.add(egui::DragValue::new(&mut self.indemnites.puissance_fiscale).clamp_range(3..=7).suffix(" CV"));
+                ui.label("Kilométrage annuel:");
+                ui.add(egui::DragValue::new(&mut self.indemnites.km_annuels).speed(100.0).clamp_range(0.0..=100000.0).suffix(" km"));
+            });
+            egui::Grid::new("km_par_client").show(ui, |ui| {
+                for stats in &statistiques {
+                    ui.label(&stats.client);
+                    // La distance n'est enregistrée qu'une fois saisie : sinon le client reste "non renseigné"
+                    let mut km = self.indemnites.km_par_client.get(&stats.client).copied().unwrap_or(0.0);
+                    let champ = egui::DragValue::new(&mut km).speed(1.0).clamp_range(0.0..=2000.0).suffix(" km A/R");
+                    if ui.add(champ).changed() {
+                        self.indemnites.km_par_client.insert(stats.client.clone(), km);
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.group(|ui| {
+                for (client, nombre, km) in &note.lignes {
+                    ui.label(format!("{} : {} intervention(s), {:.0} km", client, nombre, km));
+                }
+                ui.strong(format!("Total : {:.0} km, {:.2} €", note.km_total, note.montant));
+                if note.estimation {
+                    ui.small(
+                        "Estimation : barème appliqué aux seuls kilomètres de la période. \
+                         Renseignez le kilométrage annuel pour en obtenir la part exacte.",
+                    );
+                }
+                if !note.clients_sans_distance.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 165, 0),
+                        format!("Distance non renseignée : {}", note.clients_sans_distance.join(", ")),
+                    );
+                }
+            });
+        });
+    }
+}
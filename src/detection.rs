@@ -0,0 +1,161 @@
+use eframe::egui;
+use chrono::NaiveDateTime;
+
+use crate::{GestionTempsApp, ZoneSaisie};
+
+/// Nature d'une ligne collée, déduite de sa forme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeLigne {
+    /// Code ABS, description, début, fin
+    HorsClientele,
+    /// Ligne d'intervention (WO) avec durées décimales en fin de ligne
+    Clientele,
+    EnTete,
+    Invalide,
+}
+
+/// Nature dominante d'un bloc de données collé.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeDonnees {
+    HorsClientele,
+    Clientele,
+    Inconnu,
+    Vide,
+}
+
+fn est_date_heure(texte: &str) -> bool {
+    let texte = texte.trim();
+    ["%d/%m/%Y %H:%M", "%d/%m/%Y %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .any(|format| NaiveDateTime::parse_from_str(texte, format).is_ok())
+}
+
+fn est_decimal(texte: &str) -> bool {
+    texte.trim().replace(',', ".").parse::<f64>().is_ok()
+}
+
+pub fn classer_ligne(ligne: &str) -> TypeLigne {
+    let parties: Vec<&str> = ligne.split('\t').map(str::trim).collect();
+    let code = parties[0];
+
+    if parties.len() >= 9 {
+        let durees = est_decimal(parties[parties.len() - 2]) && est_decimal(parties[parties.len() - 1]);
+        if durees && parties.iter().any(|p| est_date_heure(p)) {
+            return TypeLigne::Clientele;
+        }
+    }
+
+    if (4..9).contains(&parties.len()) {
+        let abs = code.to_uppercase().starts_with("ABS");
+        if abs || (est_date_heure(parties[2]) && (parties[3].is_empty() || est_date_heure(parties[3]))) {
+            return TypeLigne::HorsClientele;
+        }
+    }
+
+    // Une ligne de titres n'a ni date ni nombre
+    let remplies: Vec<&&str> = parties.iter().filter(|p| !p.is_empty() && **p != "-").collect();
+    if remplies.len() >= 2 && remplies.iter().all(|p| !est_date_heure(p) && !est_decimal(p)) {
+        return TypeLigne::EnTete;
+    }
+
+    TypeLigne::Invalide
+}
+
+/// Détermine le type d'un bloc à partir de la majorité de ses lignes reconnues.
+pub fn detecter_type(donnees: &str) -> TypeDonnees {
+    let (mut hors_clientele, mut clientele) = (0, 0);
+    let mut lignes = 0;
+
+    for ligne in donnees.lines().filter(|l| !l.trim().is_empty()) {
+        lignes += 1;
+        match classer_ligne(ligne) {
+            TypeLigne::HorsClientele => hors_clientele += 1,
+            TypeLigne::Clientele => clientele += 1,
+            TypeLigne::EnTete | TypeLigne::Invalide => {}
+        }
+    }
+
+    if lignes == 0 {
+        TypeDonnees::Vide
+    } else if hors_clientele == 0 && clientele == 0 {
+        TypeDonnees::Inconnu
+    } else if hors_clientele >= clientele {
+        TypeDonnees::HorsClientele
+    } else {
+        TypeDonnees::Clientele
+    }
+}
+
+/// Vrai si le contenu des deux zones semble avoir été collé à l'envers.
+pub fn zones_inversees(donnees_hors_clientele: &str, donnees_clientele: &str) -> bool {
+    matches!(
+        (detecter_type(donnees_hors_clientele), detecter_type(donnees_clientele)),
+        (TypeDonnees::Clientele, TypeDonnees::HorsClientele | TypeDonnees::Vide | TypeDonnees::Inconnu)
+            | (TypeDonnees::Vide | TypeDonnees::Inconnu, TypeDonnees::HorsClientele)
+    )
+}
+
+/// Zone à laquelle un contenu devrait être destiné, si elle peut être déduite.
+pub fn zone_pour(donnees: &str) -> Option<ZoneSaisie> {
+    match detecter_type(donnees) {
+        TypeDonnees::HorsClientele => Some(ZoneSaisie::HorsClientele),
+        TypeDonnees::Clientele => Some(ZoneSaisie::Clientele),
+        TypeDonnees::Inconnu | TypeDonnees::Vide => None,
+    }
+}
+
+impl GestionTempsApp {
+    pub fn inverser_zones(&mut self) {
+        std::mem::swap(&mut self.donnees_hors_clientele, &mut self.donnees_clientele);
+        self.alerte_zones_inversees = false;
+    }
+
+    /// Vérifie le contenu des zones avant le calcul : inverse automatiquement si
+    /// le routage automatique est activé, sinon lève une alerte.
+    /// Renvoie vrai si les zones ont été inversées.
+    pub fn verifier_zones(&mut self) -> bool {
+        self.alerte_zones_inversees = zones_inversees(&self.donnees_hors_clientele, &self.donnees_clientele);
+
+        if self.alerte_zones_inversees && self.routage_automatique {
+            self.inverser_zones();
+            return true;
+        }
+        false
+    }
+
+    /// Calcul déclenché depuis l'interface, précédé de la vérification des zones.
+    pub fn calculer_avec_verification(&mut self) {
+        let inversees = self.verifier_zones();
+        self.calculer_resultats();
+
+        if inversees {
+            self.status_message = format!(
+                "{} (données clientèle et hors clientèle remises dans la bonne zone)",
+                self.status_message
+            );
+        }
+    }
+
+    pub(crate) fn afficher_alerte_zones(&mut self, ui: &mut egui::Ui) {
+        if !self.alerte_zones_inversees {
+            return;
+        }
+
+        ui.group(|ui| {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 100, 0),
+                "⚠️ Les données semblent inversées : des lignes d'intervention (WO) sont dans la zone hors clientèle, \
+                 ou des lignes ABS dans la zone clientèle.",
+            );
+            ui.horizontal(|ui| {
+                if ui.button("🔁 Inverser les zones").clicked() {
+                    self.inverser_zones();
+                    self.calculer_et_enregistrer();
+                }
+                if ui.button("Ignorer").clicked() {
+                    self.alerte_zones_inversees = false;
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,384 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::{
+    Calendrier, EntreeSaisie, FiltreDates, GestionTempsApp, Historique, HoraireHebdo, ReglesAbsences, ReglesArrondi, ReglesCategories,
+    ReglesTrajet, ResultatCalcul,
+};
+
+/// Profil d'un technicien suivi dans l'espace de travail.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfilEmploye {
+    pub nom: String,
+    pub matricule: String,
+    /// Heures hebdomadaires prévues au contrat (ex: 35.0)
+    pub heures_contrat: f64,
+    pub equipe: String,
+    pub horaire: HoraireHebdo,
+}
+
+impl Default for ProfilEmploye {
+    /// Le contrat par défaut correspond à l'horaire par défaut.
+    fn default() -> Self {
+        let horaire = HoraireHebdo::default();
+        ProfilEmploye {
+            nom: String::new(),
+            matricule: String::new(),
+            heures_contrat: horaire.total(),
+            equipe: String::new(),
+            horaire,
+        }
+    }
+}
+
+/// Données collées et résultats d'un employé.
+#[derive(Clone, Debug, Default)]
+pub struct DossierEmploye {
+    pub profil: ProfilEmploye,
+    pub donnees_hors_clientele: String,
+    pub donnees_clientele: String,
+    /// Collage combiné de la zone de saisie unique
+    pub donnees_melangees: String,
+    pub resultat: Option<ResultatCalcul>,
+    pub temps_par_jour: HashMap<NaiveDate, f64>,
+    pub entrees: Vec<EntreeSaisie>,
+    pub source_entrees: Option<(String, String)>,
+}
+
+/// Règles communes à tous les employés, appliquées au calcul de chaque dossier.
+#[derive(Clone, Debug, Default)]
+pub struct ReglagesCalcul {
+    pub regles_categories: ReglesCategories,
+    pub regles_absences: ReglesAbsences,
+    pub regles_arrondi: ReglesArrondi,
+    pub regles_trajet: ReglesTrajet,
+    pub calendrier: Calendrier,
+    pub filtre_dates: FiltreDates,
+}
+
+impl ReglagesCalcul {
+    /// Application de calcul pour les deux zones données, avec ces règles.
+    pub fn application(&self, donnees_hors_clientele: String, donnees_clientele: String) -> GestionTempsApp {
+        GestionTempsApp {
+            donnees_hors_clientele,
+            donnees_clientele,
+            regles_categories: self.regles_categories.clone(),
+            regles_absences: self.regles_absences.clone(),
+            regles_arrondi: self.regles_arrondi.clone(),
+            regles_trajet: self.regles_trajet.clone(),
+            filtre_dates: self.filtre_dates.clone(),
+            calendrier: self.calendrier.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Ligne du tableau de synthèse de l'équipe.
+#[derive(Clone, Debug)]
+pub struct LigneEquipe {
+    pub nom: String,
+    pub matricule: String,
+    pub equipe: String,
+    pub heures_contrat: f64,
+    pub hors_clientele: f64,
+    pub travail_clientele: f64,
+    pub deplacement: f64,
+    pub total: f64,
+    pub jours_travailles: usize,
+}
+
+impl DossierEmploye {
+    pub fn new(profil: ProfilEmploye) -> Self {
+        DossierEmploye {
+            profil,
+            ..Default::default()
+        }
+    }
+
+    /// Calcule les résultats du dossier à partir de ses propres données et de son horaire,
+    /// avec les règles et le filtre de dates communs, puis l'enregistre dans `historique`.
+    pub fn calculer(
+        &mut self,
+        reglages: &ReglagesCalcul,
+        historique: Option<&mut Historique>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = GestionTempsApp {
+            entrees: std::mem::take(&mut self.entrees),
+            source_entrees: self.source_entrees.take(),
+            horaire: self.profil.horaire.clone(),
+            // Le rapport enregistré porte le nom et le matricule du dossier
+            employes: vec![DossierEmploye::new(self.profil.clone())],
+            employe_actif: Some(0),
+            ..reglages.application(self.donnees_hors_clientele.clone(), self.donnees_clientele.clone())
+        };
+        app.calculer_resultats();
+
+        let enregistrement = match historique {
+            Some(historique) => app.enregistrer_calcul(historique).map(|_| ()),
+            None => Ok(()),
+        };
+
+        self.resultat = app.resultat;
+        self.temps_par_jour = app.temps_par_jour;
+        self.entrees = app.entrees;
+        self.source_entrees = app.source_entrees;
+        enregistrement
+    }
+
+    pub fn ligne_equipe(&self) -> LigneEquipe {
+        let (hors_clientele, travail_clientele, deplacement) = match self.resultat {
+            Some(ref r) => (r.hors_clientele, r.travail_clientele, r.deplacement),
+            None => (0.0, 0.0, 0.0),
+        };
+
+        LigneEquipe {
+            nom: self.profil.nom.clone(),
+            matricule: self.profil.matricule.clone(),
+            equipe: self.profil.equipe.clone(),
+            heures_contrat: self.profil.heures_contrat,
+            hors_clientele,
+            travail_clientele,
+            deplacement,
+            total: hors_clientele + travail_clientele + deplacement,
+            jours_travailles: self.temps_par_jour.values().filter(|&&h| h > 0.0).count(),
+        }
+    }
+}
+
+impl GestionTempsApp {
+    pub fn reglages_calcul(&self) -> ReglagesCalcul {
+        ReglagesCalcul {
+            regles_categories: self.regles_categories.clone(),
+            regles_absences: self.regles_absences.clone(),
+            regles_arrondi: self.regles_arrondi.clone(),
+            regles_trajet: self.regles_trajet.clone(),
+            calendrier: self.calendrier.clone(),
+            filtre_dates: self.filtre_dates.clone(),
+        }
+    }
+
+    /// Ajoute un employé à l'espace de travail et le sélectionne.
+    /// Sans employé actif, la saisie en cours est rangée dans le nouveau dossier.
+    pub fn ajouter_employe(&mut self, profil: ProfilEmploye) -> usize {
+        self.employes.push(DossierEmploye::new(profil));
+        let index = self.employes.len() - 1;
+        if self.employe_actif.is_none() {
+            self.employe_actif = Some(index);
+            self.sauvegarder_employe_actif();
+        } else {
+            self.selectionner_employe(index);
+        }
+        index
+    }
+
+    pub fn supprimer_employe(&mut self, index: usize) {
+        if index >= self.employes.len() {
+            return;
+        }
+
+        self.employes.remove(index);
+        self.employe_actif = match self.employe_actif {
+            Some(actif) if actif == index => None,
+            Some(actif) if actif > index => Some(actif - 1),
+            autre => autre,
+        };
+
+        if self.employe_actif.is_none() {
+            self.vider_saisie();
+        }
+    }
+
+    /// Range la saisie courante dans le dossier actif puis charge celui demandé.
+    pub fn selectionner_employe(&mut self, index: usize) {
+        if index >= self.employes.len() {
+            return;
+        }
+
+        self.sauvegarder_employe_actif();
+
+        let dossier = &self.employes[index];
+        self.donnees_hors_clientele = dossier.donnees_hors_clientele.clone();
+        self.donnees_clientele = dossier.donnees_clientele.clone();
+        self.donnees_melangees = dossier.donnees_melangees.clone();
+        self.resultat = dossier.resultat.clone();
+        self.temps_par_jour = dossier.temps_par_jour.clone();
+        self.entrees = dossier.entrees.clone();
+        self.source_entrees = dossier.source_entrees.clone();
+        self.status_message.clear();
+        self.diagnostics.clear();
+        self.employe_actif = Some(index);
+    }
+
+    /// Range la saisie courante dans le dossier actif et repasse en saisie libre.
+    pub fn deselectionner_employe(&mut self) {
+        self.sauvegarder_employe_actif();
+        self.employe_actif = None;
+    }
+
+    /// Recopie la saisie et les résultats affichés dans le dossier de l'employé actif.
+    pub fn sauvegarder_employe_actif(&mut self) {
+        if let Some(dossier) = self.employe_actif.and_then(|i| self.employes.get_mut(i)) {
+            dossier.donnees_hors_clientele = self.donnees_hors_clientele.clone();
+            dossier.donnees_clientele = self.donnees_clientele.clone();
+            dossier.donnees_melangees = self.donnees_melangees.clone();
+            dossier.resultat = self.resultat.clone();
+            dossier.temps_par_jour = self.temps_par_jour.clone();
+            dossier.entrees = self.entrees.clone();
+            dossier.source_entrees = self.source_entrees.clone();
+        }
+    }
+
+    pub fn calculer_tous_les_employes(&mut self) {
+        self.sauvegarder_employe_actif();
+
+        let reglages = self.reglages_calcul();
+        let mut non_enregistres = Vec::new();
+        for dossier in &mut self.employes {
+            if let Err(e) = dossier.calculer(&reglages, self.historique.as_mut()) {
+                non_enregistres.push(format!("{}: {}", dossier.profil.nom, e));
+            }
+        }
+
+        if let Some(actif) = self.employe_actif {
+            self.resultat = self.employes[actif].resultat.clone();
+            self.temps_par_jour = self.employes[actif].temps_par_jour.clone();
+        }
+        self.actualiser_historique();
+
+        self.status_message = if non_enregistres.is_empty() {
+            format!("Calculs terminés pour {} employé(s) !", self.employes.len())
+        } else {
+            format!(
+                "Calculs terminés pour {} employé(s), non enregistrés dans l'historique: {}",
+                self.employes.len(),
+                non_enregistres.join(", ")
+            )
+        };
+    }
+
+    /// Tableau comparatif des totaux par employé.
+    pub fn vue_equipe(&self) -> Vec<LigneEquipe> {
+        self.employes
+            .iter()
+            .enumerate()
+            .map(|(index, dossier)| {
+                // Le dossier actif peut avoir été recalculé sans être encore sauvegardé
+                if Some(index) == self.employe_actif {
+                    let mut courant = dossier.clone();
+                    courant.resultat = self.resultat.clone();
+                    courant.temps_par_jour = self.temps_par_jour.clone();
+                    courant.ligne_equipe()
+                } else {
+                    dossier.ligne_equipe()
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn vider_saisie(&mut self) {
+        self.donnees_melangees.clear();
+        self.donnees_hors_clientele.clear();
+        self.donnees_clientele.clear();
+        self.resultat = None;
+        self.show_result = false;
+        self.status_message.clear();
+        self.temps_par_jour.clear();
+        self.entrees.clear();
+        self.source_entrees = None;
+        self.diagnostics.clear();
+        self.alerte_zones_inversees = false;
+        self.entrees_hors_periode = 0;
+    }
+
+    pub(crate) fn afficher_panneau_employes(&mut self, ui: &mut egui::Ui) {
+        ui.heading("👤 Employés");
+        ui.separator();
+
+        let mut a_selectionner = None;
+        let mut a_supprimer = None;
+
+        for (index, dossier) in self.employes.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let libelle = if dossier.profil.matricule.is_empty() {
+                    dossier.profil.nom.clone()
+                } else {
+                    format!("{} ({})", dossier.profil.nom, dossier.profil.matricule)
+                };
+                if ui.selectable_label(self.employe_actif == Some(index), libelle).clicked() {
+                    a_selectionner = Some(index);
+                }
+                if ui.small_button("🗑").clicked() {
+                    a_supprimer = Some(index);
+                }
+            });
+        }
+
+        if let Some(index) = a_selectionner {
+            self.selectionner_employe(index);
+        }
+        if let Some(index) = a_supprimer {
+            self.supprimer_employe(index);
+        }
+
+        ui.add_space(10.0);
+        ui.collapsing("➕ Nouvel employé", |ui| {
+            egui::Grid::new("formulaire_employe").num_columns(2).show(ui, |ui| {
+                ui.label("Nom:");
+                ui.text_edit_singleline(&mut self.nouveau_profil.nom);
+                ui.end_row();
+
+                ui.label("Matricule:");
+                ui.text_edit_singleline(&mut self.nouveau_profil.matricule);
+                ui.end_row();
+
+                ui.label("Heures contrat:");
+                ui.add(egui::DragValue::new(&mut self.nouveau_profil.heures_contrat).speed(0.5).suffix(" h/sem"));
+                ui.end_row();
+
+                ui.label("Équipe:");
+                ui.text_edit_singleline(&mut self.nouveau_profil.equipe);
+                ui.end_row();
+            });
+
+            if ui.button("Ajouter").clicked() && !self.nouveau_profil.nom.trim().is_empty() {
+                let mut profil = std::mem::take(&mut self.nouveau_profil);
+                if profil.heures_contrat > 0.0 {
+                    profil.horaire = HoraireHebdo::depuis_contrat(profil.heures_contrat);
+                }
+                self.ajouter_employe(profil);
+            }
+        });
+
+        if !self.employes.is_empty() {
+            ui.add_space(10.0);
+            if ui.button("🔄 Calculer tout").clicked() {
+                self.calculer_tous_les_employes();
+            }
+        }
+    }
+
+    pub(crate) fn afficher_vue_equipe(&self, ui: &mut egui::Ui) {
+        ui.collapsing("👥 Vue d'équipe", |ui| {
+            egui::Grid::new("vue_equipe").striped(true).show(ui, |ui| {
+                for titre in ["Nom", "Matricule", "Équipe", "Contrat", "Hors clientèle", "Travail", "Déplacement", "Total", "Jours"] {
+                    ui.strong(titre);
+                }
+                ui.end_row();
+
+                for ligne in self.vue_equipe() {
+                    ui.label(&ligne.nom);
+                    ui.label(&ligne.matricule);
+                    ui.label(&ligne.equipe);
+                    ui.label(format!("{:.1} h/sem", ligne.heures_contrat));
+                    ui.label(format!("{:.2}", ligne.hors_clientele));
+                    ui.label(format!("{:.2}", ligne.travail_clientele));
+                    ui.label(format!("{:.2}", ligne.deplacement));
+                    ui.strong(format!("{:.2}", ligne.total));
+                    ui.label(ligne.jours_travailles.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
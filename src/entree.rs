@@ -0,0 +1,255 @@
+use eframe::egui;
+use chrono::{NaiveDate, NaiveDateTime};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{EntreeHorsClientele, GestionTempsApp, ReglesCategories};
+
+/// Formats de date acceptés dans les exports, du plus courant au plus rare.
+const FORMATS_DATE_HEURE: [&str; 3] = ["%d/%m/%Y %H:%M", "%d/%m/%Y %H:%M:%S", "%Y-%m-%d %H:%M"];
+const FORMATS_DATE: [&str; 4] = ["%d/%m/%Y", "%Y-%m-%d", "%d-%m-%Y", "%d.%m.%Y"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TypeEntree {
+    HorsClientele,
+    Clientele,
+}
+
+impl TypeEntree {
+    pub fn libelle(&self) -> &'static str {
+        match self {
+            TypeEntree::HorsClientele => "Hors clientèle",
+            TypeEntree::Clientele => "Clientèle",
+        }
+    }
+}
+
+/// Ligne analysée, modifiable avant le calcul.
+///
+/// Pour une entrée hors clientèle, `duree` est calculée à partir de `debut` et `fin`.
+/// Pour une intervention, `duree` est le temps de travail et `deplacement` le trajet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EntreeSaisie {
+    pub actif: bool,
+    pub type_entree: TypeEntree,
+    pub code: String,
+    pub client: String,
+    pub description: String,
+    pub debut: String,
+    pub fin: String,
+    pub date: Option<NaiveDate>,
+    pub duree: f64,
+    pub deplacement: f64,
+    /// Horaires du trajet aller d'une intervention, vides si inconnus
+    pub debut_trajet: String,
+    pub fin_trajet: String,
+    /// Numéro de la ligne d'origine dans la zone de saisie (à partir de 1)
+    pub ligne: usize,
+    /// Entrée créée depuis le formulaire, conservée lors d'une nouvelle analyse
+    pub saisie_manuelle: bool,
+}
+
+/// Date et heure d'une chaîne "DD/MM/YYYY HH:MM" (ou d'un des formats alternatifs).
+pub fn date_heure_de(texte: &str) -> Option<NaiveDateTime> {
+    let texte = texte.trim();
+    FORMATS_DATE_HEURE
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(texte, format).ok())
+}
+
+/// Date du jour d'une chaîne "DD/MM/YYYY HH:MM" (ou d'un des formats alternatifs).
+pub fn date_de(texte: &str) -> Option<NaiveDate> {
+    let texte = texte.trim();
+    date_heure_de(texte)
+        .map(|dt| dt.date())
+        .or_else(|| FORMATS_DATE.iter().find_map(|format| NaiveDate::parse_from_str(texte, format).ok()))
+}
+
+/// Horaire de trajet d'une ligne d'intervention ; "-" signifie qu'il n'est pas renseigné.
+pub fn horaire_trajet(colonne: &str) -> String {
+    match colonne.trim() {
+        "-" => String::new(),
+        horaire => horaire.to_string(),
+    }
+}
+
+/// Durée en heures décimales entre deux dates "DD/MM/YYYY HH:MM".
+pub fn duree_entre(debut: &str, fin: &str) -> f64 {
+    if debut.is_empty() || fin.is_empty() {
+        return 0.0;
+    }
+
+    let format = "%d/%m/%Y %H:%M";
+
+    match (
+        NaiveDateTime::parse_from_str(debut, format),
+        NaiveDateTime::parse_from_str(fin, format)
+    ) {
+        (Ok(debut_dt), Ok(fin_dt)) => {
+            let duree = fin_dt.signed_duration_since(debut_dt);
+            duree.num_seconds() as f64 / 3600.0
+        }
+        _ => 0.0,
+    }
+}
+
+impl EntreeSaisie {
+    pub fn total(&self) -> f64 {
+        self.duree + self.deplacement
+    }
+
+    /// Plage horaire occupée par l'entrée, si début et fin sont lisibles.
+    pub fn intervalle(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        match (date_heure_de(&self.debut), date_heure_de(&self.fin)) {
+            (Some(debut), Some(fin)) if fin > debut => Some((debut, fin)),
+            _ => None,
+        }
+    }
+
+    pub fn chevauche(&self, autre: &EntreeSaisie) -> bool {
+        match (self.intervalle(), autre.intervalle()) {
+            (Some((debut, fin)), Some((autre_debut, autre_fin))) => debut < autre_fin && autre_debut < fin,
+            _ => false,
+        }
+    }
+
+    /// Met à jour la date (et la durée d'une entrée hors clientèle) après modification des horaires.
+    pub fn recalculer(&mut self) {
+        self.date = date_de(&self.debut);
+        if self.type_entree == TypeEntree::HorsClientele {
+            self.duree = duree_entre(self.debut.trim(), self.fin.trim());
+        }
+    }
+
+    pub fn changer_type(&mut self, type_entree: TypeEntree) {
+        if self.type_entree == type_entree {
+            return;
+        }
+        self.type_entree = type_entree;
+        if type_entree == TypeEntree::HorsClientele {
+            self.deplacement = 0.0;
+        }
+        self.recalculer();
+    }
+
+    pub fn vers_detail(&self, regles: &ReglesCategories) -> EntreeHorsClientele {
+        EntreeHorsClientele {
+            code: self.code.clone(),
+            description: self.description.clone(),
+            categorie: regles.categoriser(&self.code, &self.description),
+            debut: Some(self.debut.clone()),
+            fin: Some(self.fin.clone()),
+            duree: self.duree,
+        }
+    }
+}
+
+impl GestionTempsApp {
+    /// Vrai si le texte des zones a changé depuis la dernière analyse.
+    pub fn entrees_obsoletes(&self) -> bool {
+        match self.source_entrees {
+            Some((ref hors_clientele, ref clientele)) => {
+                *hors_clientele != self.donnees_hors_clientele || *clientele != self.donnees_clientele
+            }
+            None => true,
+        }
+    }
+
+    /// Analyse le texte des deux zones en un tableau d'entrées modifiables.
+    pub fn analyser_saisie(&mut self) {
+        self.diagnostics.clear();
+
+        let donnees_hors_clientele = self.donnees_hors_clientele.clone();
+        let donnees_clientele = self.donnees_clientele.clone();
+
+        let mut entrees = self.lire_entrees_hors_clientele(&donnees_hors_clientele);
+        entrees.extend(self.lire_entrees_clientele(&donnees_clientele));
+        entrees.extend(self.entrees.drain(..).filter(|e| e.saisie_manuelle));
+
+        self.entrees = entrees;
+        self.source_entrees = Some((donnees_hors_clientele, donnees_clientele));
+    }
+
+    pub(crate) fn afficher_entrees(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("🔍 Analyser").clicked() {
+                self.analyser_saisie();
+            }
+            if !self.entrees.is_empty() && self.entrees_obsoletes() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 165, 0),
+                    "Le texte a changé : le prochain calcul réanalysera les zones (les modifications du tableau seront perdues)",
+                );
+            }
+        });
+
+        if self.entrees.is_empty() {
+            return;
+        }
+
+        let actives = self.entrees.iter().filter(|e| e.actif).count();
+        let mut a_supprimer = None;
+        ui.collapsing(format!("✏️ Lignes analysées ({} / {} retenues)", actives, self.entrees.len()), |ui| {
+            egui::ScrollArea::both().max_height(300.0).show(ui, |ui| {
+                egui::Grid::new("entrees_analysees").striped(true).show(ui, |ui| {
+                    for titre in ["", "Code", "Description", "Client", "Début", "Fin", "Durée", "Déplacement", "Date", "Type", ""] {
+                        ui.strong(titre);
+                    }
+                    ui.end_row();
+
+                    for (index, entree) in self.entrees.iter_mut().enumerate() {
+                        ui.checkbox(&mut entree.actif, "");
+                        ui.add(egui::TextEdit::singleline(&mut entree.code).desired_width(100.0));
+                        ui.add(egui::TextEdit::singleline(&mut entree.description).desired_width(160.0));
+                        ui.add(egui::TextEdit::singleline(&mut entree.client).desired_width(110.0));
+
+                        let debut = ui.add(egui::TextEdit::singleline(&mut entree.debut).desired_width(120.0));
+                        let fin = ui.add(egui::TextEdit::singleline(&mut entree.fin).desired_width(120.0));
+                        if debut.changed() || fin.changed() {
+                            entree.recalculer();
+                        }
+
+                        match entree.type_entree {
+                            TypeEntree::HorsClientele => {
+                                ui.label(format!("{:.2}", entree.duree));
+                                ui.label("—");
+                            }
+                            TypeEntree::Clientele => {
+                                ui.add(egui::DragValue::new(&mut entree.duree).speed(0.05).clamp_range(0.0..=24.0));
+                                ui.add(egui::DragValue::new(&mut entree.deplacement).speed(0.05).clamp_range(0.0..=24.0));
+                            }
+                        }
+
+                        match entree.date {
+                            Some(date) => ui.label(date.format("%d/%m/%Y").to_string()),
+                            None => ui.colored_label(egui::Color32::from_rgb(220, 80, 60), "?"),
+                        };
+
+                        let mut type_entree = entree.type_entree;
+                        egui::ComboBox::from_id_source(("type_entree", index))
+                            .selected_text(type_entree.libelle())
+                            .show_ui(ui, |ui| {
+                                for choix in [TypeEntree::HorsClientele, TypeEntree::Clientele] {
+                                    ui.selectable_value(&mut type_entree, choix, choix.libelle());
+                                }
+                            });
+                        entree.changer_type(type_entree);
+
+                        if entree.saisie_manuelle {
+                            if ui.small_button("🗑").on_hover_text("Supprimer la saisie manuelle").clicked() {
+                                a_supprimer = Some(index);
+                            }
+                        } else {
+                            ui.label("");
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+        if let Some(index) = a_supprimer {
+            self.entrees.remove(index);
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use crate::{EntreeSaisie, GestionTempsApp, ReglesArrondi, ReglesCategories, TypeEntree};
+
+/// Fichier de tarifs lu et écrit depuis le panneau des tarifs.
+pub const FICHIER_TARIFS: &str = "tarifs.txt";
+pub const FICHIER_FACTURATION_CSV: &str = "facturation.csv";
+pub const FICHIER_FACTURATION_PDF: &str = "facturation.pdf";
+
+/// Taux horaires hors taxes, en euros.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tarif {
+    pub travail: f64,
+    pub deplacement: f64,
+}
+
+/// Tarif d'un client, pour un type d'intervention ou pour tous si `type_intervention` est vide.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegleTarif {
+    pub client: String,
+    pub type_intervention: String,
+    pub tarif: Tarif,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TarifsClients {
+    pub regles: Vec<RegleTarif>,
+}
+
+fn meme_nom(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+fn taux(texte: &str) -> Option<f64> {
+    texte.trim().replace(',', ".").parse().ok()
+}
+
+impl TarifsClients {
+    /// Tarif du type d'intervention s'il est défini, sinon le tarif général du client.
+    pub fn tarif(&self, client: &str, type_intervention: &str) -> Option<Tarif> {
+        let du_client = || self.regles.iter().filter(|r| meme_nom(&r.client, client));
+
+        du_client()
+            .find(|r| !r.type_intervention.is_empty() && meme_nom(&r.type_intervention, type_intervention))
+            .or_else(|| du_client().find(|r| r.type_intervention.is_empty()))
+            .map(|r| r.tarif)
+    }
+
+    /// Lit des tarifs au format "Client;type d'intervention;taux travail;taux trajet".
+    /// Les lignes vides et celles commençant par '#' sont ignorées.
+    pub fn depuis_texte(texte: &str) -> Result<Self, String> {
+        let mut regles = Vec::new();
+
+        for (numero, ligne) in texte.lines().enumerate() {
+            let ligne = ligne.trim();
+            if ligne.is_empty() || ligne.starts_with('#') {
+                continue;
+            }
+
+            let parties: Vec<&str> = ligne.split(';').map(str::trim).collect();
+            if parties.len() != 4 || parties[0].is_empty() {
+                return Err(format!("Ligne {}: format attendu 'Client;type d'intervention;taux travail;taux trajet'", numero + 1));
+            }
+
+            let (Some(travail), Some(deplacement)) = (taux(parties[2]), taux(parties[3])) else {
+                return Err(format!("Ligne {}: taux illisibles '{}' et '{}'", numero + 1, parties[2], parties[3]));
+            };
+
+            regles.push(RegleTarif {
+                client: parties[0].to_string(),
+                type_intervention: parties[1].to_string(),
+                tarif: Tarif { travail, deplacement },
+            });
+        }
+
+        Ok(TarifsClients { regles })
+    }
+
+    pub fn vers_texte(&self) -> String {
+        let mut texte = String::from("# Client;type d'intervention (vide pour tous);taux travail €/h;taux trajet €/h\n");
+        for regle in &self.regles {
+            texte.push_str(&format!(
+                "{};{};{};{}\n",
+                regle.client, regle.type_intervention, regle.tarif.travail, regle.tarif.deplacement
+            ));
+        }
+        texte
+    }
+
+    pub fn charger(chemin: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let texte = fs::read_to_string(chemin)?;
+        Ok(Self::depuis_texte(&texte)?)
+    }
+
+    pub fn enregistrer(&self, chemin: &Path) -> io::Result<()> {
+        fs::write(chemin, self.vers_texte())
+    }
+}
+
+/// Intervention facturée ; les heures de travail sont arrondies selon les règles d'arrondi.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LigneFacture {
+    pub wo: String,
+    pub date: Option<NaiveDate>,
+    pub type_intervention: String,
+    pub travail: f64,
+    pub deplacement: f64,
+    /// Tarif appliqué, absent si le client n'a pas de tarif pour ce type d'intervention
+    pub tarif: Option<Tarif>,
+}
+
+impl LigneFacture {
+    pub fn montant_travail(&self) -> f64 {
+        self.tarif.map_or(0.0, |t| self.travail * t.travail)
+    }
+
+    pub fn montant_deplacement(&self) -> f64 {
+        self.tarif.map_or(0.0, |t| self.deplacement * t.deplacement)
+    }
+
+    pub fn montant(&self) -> f64 {
+        self.montant_travail() + self.montant_deplacement()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FactureClient {
+    pub client: String,
+    pub lignes: Vec<LigneFacture>,
+}
+
+impl FactureClient {
+    pub fn travail(&self) -> f64 {
+        self.lignes.iter().map(|l| l.travail).sum()
+    }
+
+    pub fn deplacement(&self) -> f64 {
+        self.lignes.iter().map(|l| l.deplacement).sum()
+    }
+
+    pub fn montant(&self) -> f64 {
+        self.lignes.iter().map(LigneFacture::montant).sum()
+    }
+
+    /// Vrai si au moins une intervention n'a pas pu être valorisée.
+    pub fn tarif_manquant(&self) -> bool {
+        self.lignes.iter().any(|l| l.tarif.is_none())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RapportFacturation {
+    /// Une facture par client, dans l'ordre alphabétique
+    pub clients: Vec<FactureClient>,
+}
+
+fn format_date(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format("%d/%m/%Y").to_string()).unwrap_or_default()
+}
+
+/// Nombre au format français (virgule décimale) pour les tableurs.
+fn nombre_csv(valeur: f64) -> String {
+    format!("{:.2}", valeur).replace('.', ",")
+}
+
+fn champ_csv(texte: &str) -> String {
+    if texte.contains([';', '"', '\n']) {
+        format!("\"{}\"", texte.replace('"', "\"\""))
+    } else {
+        texte.to_string()
+    }
+}
+
+impl RapportFacturation {
+    pub fn montant_total(&self) -> f64 {
+        self.clients.iter().map(FactureClient::montant).sum()
+    }
+
+    /// Rapport au format CSV séparé par des points-virgules, avec sous-totaux et total général.
+    pub fn vers_csv(&self) -> String {
+        let mut csv = String::from("Client;WO;Date;Type d'intervention;Travail (h);Trajet (h);Taux travail;Taux trajet;Montant\n");
+
+        for facture in &self.clients {
+            let client = champ_csv(&facture.client);
+            for ligne in &facture.lignes {
+                let (taux_travail, taux_trajet) = match ligne.tarif {
+                    Some(tarif) => (nombre_csv(tarif.travail), nombre_csv(tarif.deplacement)),
+                    None => (String::new(), String::new()),
+                };
+                csv.push_str(&format!(
+                    "{};{};{};{};{};{};{};{};{}\n",
+                    client,
+                    champ_csv(&ligne.wo),
+                    format_date(ligne.date),
+                    champ_csv(&ligne.type_intervention),
+                    nombre_csv(ligne.travail),
+                    nombre_csv(ligne.deplacement),
+                    taux_travail,
+                    taux_trajet,
+                    nombre_csv(ligne.montant())
+                ));
+            }
+            csv.push_str(&format!(
+                "{};Sous-total;;;{};{};;;{}\n",
+                client,
+                nombre_csv(facture.travail()),
+                nombre_csv(facture.deplacement()),
+                nombre_csv(facture.montant())
+            ));
+        }
+
+        csv.push_str(&format!("Total;;;;;;;;{}\n", nombre_csv(self.montant_total())));
+        csv
+    }
+
+    pub fn enregistrer_csv(&self, chemin: &Path) -> io::Result<()> {
+        fs::write(chemin, self.vers_csv())
+    }
+
+    pub fn enregistrer_pdf(&self, chemin: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let (document, page, calque) = PdfDocument::new("Rapport de facturation", Mm(210.0), Mm(297.0), "Calque 1");
+        let police = document.add_builtin_font(BuiltinFont::Helvetica)?;
+        let police_grasse = document.add_builtin_font(BuiltinFont::HelveticaBold)?;
+
+        let mut page_pdf = PagePdf {
+            calque: document.get_page(page).get_layer(calque),
+            y: HAUT_DE_PAGE,
+        };
+        page_pdf.ecrire(&police_grasse, 14.0, &[(MARGE, "Rapport de facturation")]);
+        page_pdf.y -= 4.0;
+
+        for facture in &self.clients {
+            if page_pdf.y < BAS_DE_PAGE + 20.0 {
+                page_pdf.nouvelle_page(&document);
+            }
+            page_pdf.y -= 2.0;
+            page_pdf.ecrire(&police_grasse, 11.0, &[(MARGE, &facture.client)]);
+            let entetes = ["Date", "WO", "Type", "Travail", "Trajet", "Montant"];
+            page_pdf.ecrire(&police_grasse, 9.0, &colonnes(&entetes));
+
+            for ligne in &facture.lignes {
+                if page_pdf.y < BAS_DE_PAGE {
+                    page_pdf.nouvelle_page(&document);
+                }
+                let type_intervention: String = ligne.type_intervention.chars().take(32).collect();
+                let montant = match ligne.tarif {
+                    Some(_) => format!("{:.2} €", ligne.montant()),
+                    None => "sans tarif".to_string(),
+                };
+                let valeurs = [
+                    format_date(ligne.date),
+                    ligne.wo.clone(),
+                    type_intervention,
+                    format!("{:.2} h", ligne.travail),
+                    format!("{:.2} h", ligne.deplacement),
+                    montant,
+                ];
+                page_pdf.ecrire(&police, 9.0, &colonnes(&valeurs));
+            }
+
+            let sous_total = [
+                String::new(),
+                "Sous-total".to_string(),
+                String::new(),
+                format!("{:.2} h", facture.travail()),
+                format!("{:.2} h", facture.deplacement()),
+                format!("{:.2} €", facture.montant()),
+            ];
+            page_pdf.ecrire(&police_grasse, 9.0, &colonnes(&sous_total));
+        }
+
+        page_pdf.y -= 4.0;
+        if page_pdf.y < BAS_DE_PAGE {
+            page_pdf.nouvelle_page(&document);
+        }
+        page_pdf.ecrire(&police_grasse, 12.0, &[(MARGE, &format!("Total général : {:.2} €", self.montant_total()))]);
+
+        document.save(&mut BufWriter::new(File::create(chemin)?))?;
+        Ok(())
+    }
+}
+
+const MARGE: f32 = 15.0;
+const HAUT_DE_PAGE: f32 = 280.0;
+const BAS_DE_PAGE: f32 = 20.0;
+/// Position horizontale des colonnes Date, WO, Type, Travail, Trajet, Montant (mm)
+const COLONNES: [f32; 6] = [MARGE, 40.0, 70.0, 130.0, 152.0, 174.0];
+
+fn colonnes<T: AsRef<str>>(valeurs: &[T]) -> Vec<(f32, &str)> {
+    COLONNES.iter().copied().zip(valeurs.iter().map(AsRef::as_ref)).collect()
+}
+
+/// Page en cours d'écriture, remplie de haut en bas.
+struct PagePdf {
+    calque: PdfLayerReference,
+    y: f32,
+}
+
+impl PagePdf {
+    fn ecrire(&mut self, police: &IndirectFontRef, taille: f32, textes: &[(f32, &str)]) {
+        for (x, texte) in textes {
+            self.calque.use_text(*texte, taille, Mm(*x), Mm(self.y), police);
+        }
+        self.y -= taille * 0.5 + 1.5;
+    }
+
+    fn nouvelle_page(&mut self, document: &printpdf::PdfDocumentReference) {
+        let (page, calque) = document.add_page(Mm(210.0), Mm(297.0), "Calque 1");
+        self.calque = document.get_page(page).get_layer(calque);
+        self.y = HAUT_DE_PAGE;
+    }
+}
+
+/// Rapport de facturation des interventions, par client puis par date.
+pub fn rapport_facturation(
+    entrees: &[&EntreeSaisie],
+    tarifs: &TarifsClients,
+    arrondi: &ReglesArrondi,
+    categories: &ReglesCategories,
+) -> RapportFacturation {
+    let mut par_client: BTreeMap<String, Vec<LigneFacture>> = BTreeMap::new();
+
+    for entree in entrees.iter().filter(|e| e.type_entree == TypeEntree::Clientele) {
+        let travail = arrondi.arrondir(entree, categories).map_or(entree.duree, |(_, duree)| duree);
+        par_client.entry(entree.client.clone()).or_default().push(LigneFacture {
+            wo: entree.code.clone(),
+            date: entree.date,
+            type_intervention: entree.description.clone(),
+            travail,
+            deplacement: entree.deplacement,
+            tarif: tarifs.tarif(&entree.client, &entree.description),
+        });
+    }
+
+    let clients = par_client
+        .into_iter()
+        .map(|(client, mut lignes)| {
+            lignes.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.wo.cmp(&b.wo)));
+            FactureClient { client, lignes }
+        })
+        .collect();
+
+    RapportFacturation { clients }
+}
+
+impl GestionTempsApp {
+    pub fn rapport_facturation(&self) -> RapportFacturation {
+        rapport_facturation(&self.entrees_retenues(), &self.tarifs, &self.regles_arrondi, &self.regles_categories)
+    }
+
+    pub fn appliquer_tarifs(&mut self) {
+        match TarifsClients::depuis_texte(&self.texte_tarifs) {
+            Ok(tarifs) => {
+                self.tarifs = tarifs;
+                self.status_message = "Tarifs appliqués".to_string();
+            }
+            Err(e) => self.status_message = format!("Tarifs invalides: {}", e),
+        }
+    }
+
+    pub fn exporter_facturation_csv(&mut self) {
+        self.status_message = match self.rapport_facturation().enregistrer_csv(Path::new(FICHIER_FACTURATION_CSV)) {
+            Ok(()) => format!("Rapport de facturation exporté dans {}", FICHIER_FACTURATION_CSV),
+            Err(e) => format!("Impossible d'écrire {}: {}", FICHIER_FACTURATION_CSV, e),
+        };
+    }
+
+    pub fn exporter_facturation_pdf(&mut self) {
+        self.status_message = match self.rapport_facturation().enregistrer_pdf(Path::new(FICHIER_FACTURATION_PDF)) {
+            Ok(()) => format!("Rapport de facturation exporté dans {}", FICHIER_FACTURATION_PDF),
+            Err(e) => format!("Impossible d'écrire {}: {}", FICHIER_FACTURATION_PDF, e),
+        };
+    }
+
+    pub(crate) fn afficher_tarifs(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("💶 Tarifs clients", |ui| {
+            if self.texte_tarifs.is_empty() {
+                self.texte_tarifs = self.tarifs.vers_texte();
+            }
+
+            ui.small("Une ligne par client : Client;type d'intervention (vide pour tous);taux travail €/h;taux trajet €/h");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.texte_tarifs)
+                    .id_source("tarifs_clients")
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(4),
+            );
+
+            ui.horizontal(|ui| {
+                if ui.button("✔ Appliquer").clicked() {
+                    self.appliquer_tarifs();
+                }
+                if ui.button("📂 Charger").clicked() {
+                    match TarifsClients::charger(Path::new(FICHIER_TARIFS)) {
+                        Ok(tarifs) => {
+                            self.texte_tarifs = tarifs.vers_texte();
+                            self.tarifs = tarifs;
+                            self.status_message = format!("Tarifs chargés depuis {}", FICHIER_TARIFS);
+                        }
+                        Err(e) => self.status_message = format!("Impossible de charger {}: {}", FICHIER_TARIFS, e),
+                    }
+                }
+                if ui.button("💾 Enregistrer").clicked() {
+                    self.status_message = match self.tarifs.enregistrer(Path::new(FICHIER_TARIFS)) {
+                        Ok(()) => format!("Tarifs enregistrés dans {}", FICHIER_TARIFS),
+                        Err(e) => format!("Impossible d'écrire {}: {}", FICHIER_TARIFS, e),
+                    };
+                }
+            });
+        });
+    }
+
+    pub(crate) fn afficher_facturation(&mut self, ui: &mut egui::Ui) {
+        let rapport = self.rapport_facturation();
+        if rapport.clients.is_empty() {
+            return;
+        }
+
+        ui.collapsing(format!("💶 Facturation : {:.2} €", rapport.montant_total()), |ui| {
+            for facture in &rapport.clients {
+                ui.collapsing(format!("{} : {:.2} €", facture.client, facture.montant()), |ui| {
+                    egui::Grid::new(("facture", &facture.client)).striped(true).show(ui, |ui| {
+                        for titre in ["Date", "WO", "Type", "Travail", "Trajet", "Montant"] {
+                            ui.strong(titre);
+                        }
+                        ui.end_row();
+
+                        for ligne in &facture.lignes {
+                            ui.label(format_date(ligne.date));
+                            ui.label(&ligne.wo);
+                            ui.label(&ligne.type_intervention);
+                            ui.label(format!("{:.2}h", ligne.travail));
+                            ui.label(format!("{:.2}h", ligne.deplacement));
+                            match ligne.tarif {
+                                Some(_) => ui.label(format!("{:.2} €", ligne.montant())),
+                                None => ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "sans tarif"),
+                            };
+                            ui.end_row();
+                        }
+
+                        ui.strong("Sous-total");
+                        ui.label("");
+                        ui.label("");
+                        ui.strong(format!("{:.2}h", facture.travail()));
+                        ui.strong(format!("{:.2}h", facture.deplacement()));
+                        ui.strong(format!("{:.2} €", facture.montant()));
+                        ui.end_row();
+                    });
+                });
+            }
+
+            let sans_tarif: Vec<&str> = rapport.clients.iter().filter(|f| f.tarif_manquant()).map(|f| f.client.as_str()).collect();
+            if !sans_tarif.is_empty() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 165, 0),
+                    format!("Tarif non renseigné : {}", sans_tarif.join(", ")),
+                );
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("📄 Exporter en CSV").clicked() {
+                    self.exporter_facturation_csv();
+                }
+                if ui.button("🖨 Exporter en PDF").clicked() {
+                    self.exporter_facturation_pdf();
+                }
+            });
+        });
+    }
+}
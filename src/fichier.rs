@@ -0,0 +1,237 @@
+use eframe::egui;
+use encoding_rs::{UTF_16BE, UTF_16LE, WINDOWS_1252};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::classeur::{est_classeur, lire_premiere_feuille};
+use crate::detection::zone_pour;
+use crate::GestionTempsApp;
+
+/// Extensions acceptées par "Ouvrir fichier" et le glisser-déposer.
+pub const EXTENSIONS_ACCEPTEES: [&str; 5] = ["txt", "csv", "tsv", "xlsx", "ods"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneSaisie {
+    HorsClientele,
+    Clientele,
+}
+
+/// Texte lu depuis un fichier, avec l'encodage détecté.
+#[derive(Clone, Debug)]
+pub struct ContenuFichier {
+    pub texte: String,
+    pub encodage: &'static str,
+}
+
+/// Décode un export texte : UTF-8 (avec ou sans BOM), UTF-16 avec BOM,
+/// sinon Windows-1252 comme le produisent la plupart des outils métiers.
+pub fn decoder_texte(octets: &[u8]) -> ContenuFichier {
+    if let Some(reste) = octets.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return ContenuFichier {
+            texte: String::from_utf8_lossy(reste).into_owned(),
+            encodage: "UTF-8",
+        };
+    }
+    if octets.starts_with(&[0xFF, 0xFE]) {
+        let (texte, _, _) = UTF_16LE.decode(octets);
+        return ContenuFichier { texte: texte.into_owned(), encodage: "UTF-16LE" };
+    }
+    if octets.starts_with(&[0xFE, 0xFF]) {
+        let (texte, _, _) = UTF_16BE.decode(octets);
+        return ContenuFichier { texte: texte.into_owned(), encodage: "UTF-16BE" };
+    }
+
+    match std::str::from_utf8(octets) {
+        Ok(texte) => ContenuFichier { texte: texte.to_string(), encodage: "UTF-8" },
+        Err(_) => {
+            let (texte, _, _) = WINDOWS_1252.decode(octets);
+            ContenuFichier { texte: texte.into_owned(), encodage: "Windows-1252" }
+        }
+    }
+}
+
+/// Lit un export texte en détectant son encodage.
+pub fn lire_fichier_texte(chemin: &Path) -> std::io::Result<ContenuFichier> {
+    fs::read(chemin).map(|octets| decoder_texte(&octets))
+}
+
+/// Lit un fichier d'export et le convertit au format tabulé attendu par les parsers.
+pub fn lire_fichier_export(chemin: &Path) -> Result<ContenuFichier, Box<dyn Error>> {
+    let extension = extension_minuscule(chemin);
+
+    match extension.as_str() {
+        "txt" | "tsv" => Ok(lire_fichier_texte(chemin)?),
+        "csv" => {
+            let contenu = lire_fichier_texte(chemin)?;
+            Ok(ContenuFichier {
+                texte: csv_vers_tsv(&contenu.texte),
+                encodage: contenu.encodage,
+            })
+        }
+        "xlsx" | "ods" => Ok(ContenuFichier {
+            texte: lire_premiere_feuille(chemin)?,
+            encodage: "classeur",
+        }),
+        _ => Err(format!("Format de fichier non pris en charge: {}", chemin.display()).into()),
+    }
+}
+
+fn extension_minuscule(chemin: &Path) -> String {
+    chemin
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Convertit un CSV (séparateur `;` ou `,`) en texte tabulé.
+/// Les champs entre guillemets peuvent contenir le séparateur et des guillemets doublés.
+pub fn csv_vers_tsv(texte: &str) -> String {
+    let premiere_ligne = texte.lines().next().unwrap_or("");
+    let separateur = if premiere_ligne.matches(';').count() >= premiere_ligne.matches(',').count() {
+        ';'
+    } else {
+        ','
+    };
+
+    texte
+        .lines()
+        .map(|ligne| decouper_ligne_csv(ligne, separateur).join("\t"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn decouper_ligne_csv(ligne: &str, separateur: char) -> Vec<String> {
+    let mut champs = Vec::new();
+    let mut champ = String::new();
+    let mut entre_guillemets = false;
+    let mut caracteres = ligne.chars().peekable();
+
+    while let Some(c) = caracteres.next() {
+        match c {
+            '"' if entre_guillemets && caracteres.peek() == Some(&'"') => {
+                champ.push('"');
+                caracteres.next();
+            }
+            '"' => entre_guillemets = !entre_guillemets,
+            c if c == separateur && !entre_guillemets => champs.push(std::mem::take(&mut champ)),
+            c => champ.push(c),
+        }
+    }
+    champs.push(champ);
+    champs
+}
+
+impl GestionTempsApp {
+    pub fn zone_mut(&mut self, zone: ZoneSaisie) -> &mut String {
+        match zone {
+            ZoneSaisie::HorsClientele => &mut self.donnees_hors_clientele,
+            ZoneSaisie::Clientele => &mut self.donnees_clientele,
+        }
+    }
+
+    /// Remplace le contenu d'une zone de saisie par celui du fichier.
+    /// Les classeurs passent d'abord par le choix de la feuille et de l'en-tête.
+    pub fn charger_fichier(&mut self, zone: ZoneSaisie, chemin: &Path) {
+        if est_classeur(chemin) {
+            self.ouvrir_import_classeur(zone, chemin);
+            return;
+        }
+
+        match lire_fichier_export(chemin) {
+            Ok(contenu) => {
+                *self.zone_mut(zone) = contenu.texte;
+                self.status_message = format!(
+                    "Fichier chargé: {} ({})",
+                    chemin.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+                    contenu.encodage
+                );
+            }
+            Err(e) => {
+                self.status_message = format!("Erreur lors de la lecture de {}: {}", chemin.display(), e);
+            }
+        }
+    }
+
+    pub(crate) fn ouvrir_fichier(&mut self, zone: ZoneSaisie) {
+        let choix = rfd::FileDialog::new()
+            .add_filter("Exports", &EXTENSIONS_ACCEPTEES)
+            .pick_file();
+
+        if let Some(chemin) = choix {
+            self.charger_fichier(zone, &chemin);
+        }
+    }
+
+    /// Dépose les fichiers glissés sur la zone survolée (ou la première zone vide).
+    pub(crate) fn gerer_fichiers_deposes(&mut self, ctx: &egui::Context, zones: &[(ZoneSaisie, egui::Rect)]) {
+        let (deposes, position) = ctx.input(|i| (i.raw.dropped_files.clone(), i.pointer.hover_pos()));
+        if deposes.is_empty() {
+            return;
+        }
+
+        if self.saisie_unifiee {
+            for fichier in deposes {
+                let nom = fichier
+                    .path
+                    .as_ref()
+                    .and_then(|chemin| chemin.file_name())
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or(fichier.name);
+                let contenu = match (fichier.path, fichier.bytes) {
+                    (Some(chemin), _) => lire_fichier_export(&chemin).map_err(|e| e.to_string()),
+                    (None, Some(octets)) => Ok(decoder_texte(&octets)),
+                    (None, None) => continue,
+                };
+                match contenu {
+                    Ok(contenu) => {
+                        if !self.donnees_melangees.is_empty() && !self.donnees_melangees.ends_with('\n') {
+                            self.donnees_melangees.push('\n');
+                        }
+                        self.donnees_melangees.push_str(&contenu.texte);
+                        self.status_message = format!("Fichier ajouté: {} ({})", nom, contenu.encodage);
+                    }
+                    Err(e) => self.status_message = format!("Erreur lors de la lecture de {}: {}", nom, e),
+                }
+            }
+            return;
+        }
+
+        let zone_survolee = position.and_then(|pos| {
+            zones.iter().find(|(_, rect)| rect.contains(pos)).map(|(zone, _)| *zone)
+        });
+        let zone_par_defaut = if self.donnees_hors_clientele.trim().is_empty() {
+            ZoneSaisie::HorsClientele
+        } else {
+            ZoneSaisie::Clientele
+        };
+        let zone = zone_survolee.unwrap_or(zone_par_defaut);
+
+        for fichier in deposes {
+            match (fichier.path, fichier.bytes) {
+                // Hors d'une zone, le contenu du fichier décide de sa destination
+                (Some(chemin), _) if zone_survolee.is_none() && !est_classeur(&chemin) => {
+                    let zone = lire_fichier_export(&chemin)
+                        .ok()
+                        .and_then(|contenu| zone_pour(&contenu.texte))
+                        .unwrap_or(zone_par_defaut);
+                    self.charger_fichier(zone, &chemin);
+                }
+                (Some(chemin), _) => self.charger_fichier(zone, &chemin),
+                (None, Some(_)) if est_classeur(Path::new(&fichier.name)) => {
+                    self.status_message = format!("Utilisez \"Ouvrir fichier\" pour importer le classeur {}", fichier.name);
+                }
+                (None, Some(octets)) => {
+                    let contenu = decoder_texte(&octets);
+                    *self.zone_mut(zone) = if fichier.name.to_lowercase().ends_with(".csv") {
+                        csv_vers_tsv(&contenu.texte)
+                    } else {
+                        contenu.texte
+                    };
+                    self.status_message = format!("Fichier chargé: {} ({})", fichier.name, contenu.encodage);
+                }
+                (None, None) => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,659 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::trajet::deplacement_compte;
+use crate::{FiltreDates, GestionTempsApp, Periode, RapportJson, TypeEntree};
+
+/// Base SQLite des calculs, ouverte au démarrage dans le dossier courant.
+pub const FICHIER_HISTORIQUE: &str = "historique.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS calculs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        calcule_le TEXT NOT NULL,
+        employe TEXT NOT NULL,
+        matricule TEXT NOT NULL,
+        du TEXT,
+        au TEXT,
+        hors_clientele REAL NOT NULL,
+        travail_clientele REAL NOT NULL,
+        deplacement REAL NOT NULL,
+        absences REAL NOT NULL,
+        donnees_hors_clientele TEXT NOT NULL,
+        donnees_clientele TEXT NOT NULL,
+        rapport TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS entrees (
+        calcul_id INTEGER NOT NULL REFERENCES calculs(id),
+        type_entree TEXT NOT NULL,
+        date TEXT,
+        client TEXT NOT NULL,
+        categorie TEXT NOT NULL,
+        duree REAL NOT NULL,
+        deplacement REAL NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS entrees_par_calcul ON entrees(calcul_id);
+";
+
+/// Entrée retenue d'un calcul, avec sa durée après arrondi.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntreeHistorique {
+    pub type_entree: TypeEntree,
+    pub date: Option<NaiveDate>,
+    pub client: String,
+    pub categorie: String,
+    pub duree: f64,
+    pub deplacement: f64,
+}
+
+/// Ligne de la liste des calculs enregistrés.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResumeCalcul {
+    pub id: i64,
+    /// Date et heure du calcul, "DD/MM/YYYY HH:MM"
+    pub calcule_le: String,
+    /// Nom de l'employé, vide en saisie libre
+    pub employe: String,
+    pub matricule: String,
+    /// Premier et dernier jour couverts, absents si aucune entrée n'est datée
+    pub du: Option<NaiveDate>,
+    pub au: Option<NaiveDate>,
+    pub hors_clientele: f64,
+    pub travail_clientele: f64,
+    pub deplacement: f64,
+    pub absences: f64,
+}
+
+impl ResumeCalcul {
+    pub fn total(&self) -> f64 {
+        self.hors_clientele + self.travail_clientele + self.deplacement
+    }
+
+    pub fn libelle_periode(&self) -> String {
+        match (self.du, self.au) {
+            (Some(du), Some(au)) => format!("{} → {}", du.format("%d/%m/%Y"), au.format("%d/%m/%Y")),
+            _ => "sans date".to_string(),
+        }
+    }
+
+    pub fn libelle(&self) -> String {
+        let employe = if self.employe.is_empty() { "Saisie libre" } else { &self.employe };
+        format!("#{} {} ({})", self.id, employe, self.libelle_periode())
+    }
+}
+
+/// Calcul enregistré, avec de quoi le rouvrir tel qu'il a été fait.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalculHistorique {
+    pub resume: ResumeCalcul,
+    pub donnees_hors_clientele: String,
+    pub donnees_clientele: String,
+    pub rapport: RapportJson,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Regroupement {
+    #[default]
+    Client,
+    Categorie,
+}
+
+impl Regroupement {
+    pub fn libelle(&self) -> &'static str {
+        match self {
+            Regroupement::Client => "Client",
+            Regroupement::Categorie => "Catégorie",
+        }
+    }
+}
+
+/// Total d'un client ou d'une catégorie sur un mois.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TotalMensuel {
+    /// Mois au format "YYYY-MM"
+    pub mois: String,
+    pub cle: String,
+    pub heures: f64,
+    pub deplacement: f64,
+}
+
+/// Ligne de la comparaison de deux calculs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LigneComparaison {
+    pub libelle: String,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl LigneComparaison {
+    pub fn ecart(&self) -> f64 {
+        self.b - self.a
+    }
+}
+
+fn texte_date(date: Option<NaiveDate>) -> Option<String> {
+    date.map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+fn lire_date(texte: Option<String>) -> Option<NaiveDate> {
+    texte.and_then(|t| NaiveDate::parse_from_str(&t, "%Y-%m-%d").ok())
+}
+
+fn code_type(type_entree: &TypeEntree) -> &'static str {
+    match type_entree {
+        TypeEntree::HorsClientele => "hors-clientele",
+        TypeEntree::Clientele => "clientele",
+    }
+}
+
+fn resume_de(ligne: &rusqlite::Row) -> rusqlite::Result<ResumeCalcul> {
+    Ok(ResumeCalcul {
+        id: ligne.get(0)?,
+        calcule_le: ligne.get(1)?,
+        employe: ligne.get(2)?,
+        matricule: ligne.get(3)?,
+        du: lire_date(ligne.get(4)?),
+        au: lire_date(ligne.get(5)?),
+        hors_clientele: ligne.get(6)?,
+        travail_clientele: ligne.get(7)?,
+        deplacement: ligne.get(8)?,
+        absences: ligne.get(9)?,
+    })
+}
+
+const COLONNES_RESUME: &str =
+    "id, calcule_le, employe, matricule, du, au, hors_clientele, travail_clientele, deplacement, absences";
+
+/// Historique de tous les calculs, conservé dans une base SQLite.
+#[derive(Debug)]
+pub struct Historique {
+    connexion: Connection,
+}
+
+impl Historique {
+    /// Ouvre la base, en la créant si elle n'existe pas.
+    pub fn ouvrir(chemin: &Path) -> rusqlite::Result<Self> {
+        Self::initialiser(Connection::open(chemin)?)
+    }
+
+    /// Base temporaire, perdue à la fermeture.
+    pub fn en_memoire() -> rusqlite::Result<Self> {
+        Self::initialiser(Connection::open_in_memory()?)
+    }
+
+    fn initialiser(connexion: Connection) -> rusqlite::Result<Self> {
+        connexion.execute_batch(SCHEMA)?;
+        Ok(Historique { connexion })
+    }
+
+    /// Enregistre un calcul et ses entrées retenues ; renvoie son numéro.
+    /// Sans filtre de dates, ou pour une borne ouverte, la période est celle des entrées datées.
+    pub fn enregistrer(
+        &mut self,
+        rapport: &RapportJson,
+        donnees_hors_clientele: &str,
+        donnees_clientele: &str,
+        entrees: &[EntreeHistorique],
+    ) -> Result<i64, Box<dyn std::error::Error>> {
+        let dates = entrees.iter().filter_map(|e| e.date);
+        let periode = rapport.metadonnees.periode.as_ref();
+        let du = periode.and_then(|p| p.du).or_else(|| dates.clone().min());
+        let au = periode.and_then(|p| p.au).or_else(|| dates.max());
+        let resultat = rapport.resultat.as_ref();
+        let (hors_clientele, travail, deplacement) =
+            resultat.map_or((0.0, 0.0, 0.0), |r| (r.hors_clientele, r.travail_clientele, r.deplacement));
+        let absences = resultat.map_or(0.0, |r| r.absences.iter().fold(0.0, |t, a| t + a.heures));
+
+        let transaction = self.connexion.transaction()?;
+        transaction.execute(
+            "INSERT INTO calculs (calcule_le, employe, matricule, du, au, hors_clientele, travail_clientele,
+                deplacement, absences, donnees_hors_clientele, donnees_clientele, rapport)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                rapport.metadonnees.genere_le.format("%d/%m/%Y %H:%M").to_string(),
+                rapport.metadonnees.employe.clone().unwrap_or_default(),
+                rapport.metadonnees.matricule.clone().unwrap_or_default(),
+                texte_date(du),
+                texte_date(au),
+                hors_clientele,
+                travail,
+                deplacement,
+                absences,
+                donnees_hors_clientele,
+                donnees_clientele,
+                rapport.vers_json()?,
+            ],
+        )?;
+        let id = transaction.last_insert_rowid();
+
+        {
+            let mut insertion = transaction.prepare(
+                "INSERT INTO entrees (calcul_id, type_entree, date, client, categorie, duree, deplacement)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for entree in entrees {
+                insertion.execute(params![
+                    id,
+                    code_type(&entree.type_entree),
+                    texte_date(entree.date),
+                    entree.client,
+                    entree.categorie,
+                    entree.duree,
+                    entree.deplacement,
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(id)
+    }
+
+    /// Calculs enregistrés, du plus récent au plus ancien.
+    pub fn lister(&self) -> rusqlite::Result<Vec<ResumeCalcul>> {
+        let mut requete = self
+            .connexion
+            .prepare(&format!("SELECT {} FROM calculs ORDER BY id DESC", COLONNES_RESUME))?;
+        let calculs = requete.query_map([], resume_de)?.collect();
+        calculs
+    }
+
+    pub fn charger(&self, id: i64) -> Result<Option<CalculHistorique>, Box<dyn std::error::Error>> {
+        let ligne = self
+            .connexion
+            .query_row(
+                &format!(
+                    "SELECT {}, donnees_hors_clientele, donnees_clientele, rapport FROM calculs WHERE id = ?1",
+                    COLONNES_RESUME
+                ),
+                [id],
+                |ligne| Ok((resume_de(ligne)?, ligne.get(10)?, ligne.get(11)?, ligne.get::<_, String>(12)?)),
+            )
+            .optional()?;
+
+        match ligne {
+            Some((resume, donnees_hors_clientele, donnees_clientele, rapport)) => Ok(Some(CalculHistorique {
+                resume,
+                donnees_hors_clientele,
+                donnees_clientele,
+                rapport: RapportJson::depuis_json(&rapport)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    pub fn supprimer(&mut self, id: i64) -> rusqlite::Result<()> {
+        let transaction = self.connexion.transaction()?;
+        transaction.execute("DELETE FROM entrees WHERE calcul_id = ?1", [id])?;
+        transaction.execute("DELETE FROM calculs WHERE id = ?1", [id])?;
+        transaction.commit()
+    }
+
+    /// Heures par client (travail) ou par catégorie (hors clientèle) d'un calcul.
+    fn totaux_du_calcul(&self, id: i64, regroupement: Regroupement) -> rusqlite::Result<BTreeMap<String, f64>> {
+        let (colonne, type_entree) = match regroupement {
+            Regroupement::Client => ("client", TypeEntree::Clientele),
+            Regroupement::Categorie => ("categorie", TypeEntree::HorsClientele),
+        };
+        let mut requete = self.connexion.prepare(&format!(
+            "SELECT {0}, SUM(duree) FROM entrees WHERE calcul_id = ?1 AND type_entree = ?2 GROUP BY {0}",
+            colonne
+        ))?;
+        let totaux = requete
+            .query_map(params![id, code_type(&type_entree)], |ligne| Ok((ligne.get(0)?, ligne.get(1)?)))?
+            .collect();
+        totaux
+    }
+
+    /// Compare deux calculs : totaux, puis catégories et clients présents dans l'un ou l'autre.
+    pub fn comparer(&self, a: i64, b: i64) -> Result<Vec<LigneComparaison>, Box<dyn std::error::Error>> {
+        let resume = |id| -> Result<ResumeCalcul, Box<dyn std::error::Error>> {
+            Ok(self
+                .connexion
+                .query_row(&format!("SELECT {} FROM calculs WHERE id = ?1", COLONNES_RESUME), [id], resume_de)
+                .optional()?
+                .ok_or_else(|| format!("calcul #{} introuvable", id))?)
+        };
+        let (resume_a, resume_b) = (resume(a)?, resume(b)?);
+
+        let mut lignes: Vec<LigneComparaison> = [
+            ("Hors clientèle", resume_a.hors_clientele, resume_b.hors_clientele),
+            ("Travail clientèle", resume_a.travail_clientele, resume_b.travail_clientele),
+            ("Déplacement", resume_a.deplacement, resume_b.deplacement),
+            ("Total", resume_a.total(), resume_b.total()),
+            ("Absences", resume_a.absences, resume_b.absences),
+        ]
+        .into_iter()
+        .map(|(libelle, a, b)| LigneComparaison { libelle: libelle.to_string(), a, b })
+        .collect();
+
+        for regroupement in [Regroupement::Categorie, Regroupement::Client] {
+            let totaux_a = self.totaux_du_calcul(a, regroupement)?;
+            let totaux_b = self.totaux_du_calcul(b, regroupement)?;
+            let mut cles: Vec<&String> = totaux_a.keys().chain(totaux_b.keys()).collect();
+            cles.sort();
+            cles.dedup();
+            for cle in cles {
+                lignes.push(LigneComparaison {
+                    libelle: format!("{} {}", regroupement.libelle(), cle),
+                    a: totaux_a.get(cle).copied().unwrap_or(0.0),
+                    b: totaux_b.get(cle).copied().unwrap_or(0.0),
+                });
+            }
+        }
+        Ok(lignes)
+    }
+
+    /// Heures par mois et par client (travail et déplacement) ou par catégorie (hors clientèle),
+    /// tous calculs confondus. Quand plusieurs calculs d'un même employé couvrent un jour,
+    /// seul le plus récent est compté ; les entrées sans date sont ignorées.
+    pub fn totaux_mensuels(&self, regroupement: Regroupement) -> rusqlite::Result<Vec<TotalMensuel>> {
+        let (colonne, type_entree) = match regroupement {
+            Regroupement::Client => ("client", TypeEntree::Clientele),
+            Regroupement::Categorie => ("categorie", TypeEntree::HorsClientele),
+        };
+        let mut requete = self.connexion.prepare(&format!(
+            "SELECT substr(e.date, 1, 7) AS mois, e.{0}, SUM(e.duree), SUM(e.deplacement)
+             FROM entrees e JOIN calculs c ON c.id = e.calcul_id
+             WHERE e.date IS NOT NULL AND e.type_entree = ?1
+               AND NOT EXISTS (
+                   SELECT 1 FROM calculs r
+                   WHERE r.employe = c.employe AND r.id > c.id AND r.du <= e.date AND e.date <= r.au
+               )
+             GROUP BY mois, e.{0}
+             ORDER BY mois, e.{0}",
+            colonne
+        ))?;
+        let totaux = requete
+            .query_map([code_type(&type_entree)], |ligne| {
+                Ok(TotalMensuel {
+                    mois: ligne.get(0)?,
+                    cle: ligne.get(1)?,
+                    heures: ligne.get(2)?,
+                    deplacement: ligne.get(3)?,
+                })
+            })?
+            .collect();
+        totaux
+    }
+}
+
+/// État du panneau d'historique : liste, comparaison et totaux mensuels.
+#[derive(Clone, Debug, Default)]
+pub struct VueHistorique {
+    pub calculs: Vec<ResumeCalcul>,
+    pub calcul_a: Option<i64>,
+    pub calcul_b: Option<i64>,
+    pub comparaison: Vec<LigneComparaison>,
+    pub regroupement: Regroupement,
+    pub totaux: Vec<TotalMensuel>,
+}
+
+impl GestionTempsApp {
+    /// Active l'enregistrement des calculs dans la base `chemin`.
+    pub fn ouvrir_historique(&mut self, chemin: &Path) {
+        match Historique::ouvrir(chemin) {
+            Ok(historique) => {
+                self.historique = Some(historique);
+                self.actualiser_historique();
+            }
+            Err(e) => self.signaler(format!("Historique non ouvert ({}): {}", chemin.display(), e)),
+        }
+    }
+
+    /// Entrées retenues du dernier calcul, telles qu'elles sont enregistrées dans l'historique.
+    pub fn entrees_historique(&self) -> Vec<EntreeHistorique> {
+        let trajets = self.repartition_trajets_active();
+        self.entrees_retenues()
+            .into_iter()
+            .filter(|e| {
+                e.type_entree == TypeEntree::Clientele
+                    || self.regles_absences.type_absence(&e.code, &e.description).is_none()
+            })
+            .map(|e| EntreeHistorique {
+                type_entree: e.type_entree,
+                date: e.date,
+                client: e.client.clone(),
+                categorie: match e.type_entree {
+                    TypeEntree::HorsClientele => self.regles_categories.categoriser(&e.code, &e.description),
+                    TypeEntree::Clientele => String::new(),
+                },
+                duree: self.regles_arrondi.arrondir(e, &self.regles_categories).map_or(e.duree, |(_, d)| d),
+                deplacement: deplacement_compte(e, trajets.as_ref()),
+            })
+            .collect()
+    }
+
+    /// Enregistre le dernier calcul dans `historique` ; `None` tant que rien n'a été calculé.
+    pub fn enregistrer_calcul(&self, historique: &mut Historique) -> Result<Option<i64>, Box<dyn std::error::Error>> {
+        if self.resultat.is_none() {
+            return Ok(None);
+        }
+        let rapport = self.rapport_json();
+        historique
+            .enregistrer(&rapport, &self.donnees_hors_clientele, &self.donnees_clientele, &self.entrees_historique())
+            .map(Some)
+    }
+
+    /// Enregistre le dernier calcul si un historique est ouvert.
+    pub fn enregistrer_dans_historique(&mut self) -> Option<i64> {
+        let mut historique = self.historique.take()?;
+        let enregistrement = self.enregistrer_calcul(&mut historique);
+        self.historique = Some(historique);
+
+        match enregistrement {
+            Ok(id) => {
+                if id.is_some() {
+                    self.actualiser_historique();
+                }
+                id
+            }
+            Err(e) => {
+                self.status_message = format!("Calcul non enregistré dans l'historique: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Relit la liste des calculs, la comparaison sélectionnée et les totaux mensuels.
+    pub fn actualiser_historique(&mut self) {
+        let Some(ref historique) = self.historique else {
+            return;
+        };
+        let vue = &mut self.vue_historique;
+        let lectures = historique.lister().map(|calculs| vue.calculs = calculs).and_then(|()| {
+            historique.totaux_mensuels(vue.regroupement).map(|totaux| vue.totaux = totaux)
+        });
+        let comparaison = match (vue.calcul_a, vue.calcul_b) {
+            (Some(a), Some(b)) => historique.comparer(a, b).map(|lignes| vue.comparaison = lignes),
+            _ => {
+                vue.comparaison.clear();
+                Ok(())
+            }
+        };
+
+        if let Err(e) = lectures {
+            self.status_message = format!("Lecture de l'historique impossible: {}", e);
+        } else if let Err(e) = comparaison {
+            self.status_message = format!("Comparaison impossible: {}", e);
+        }
+    }
+
+    /// Recharge un calcul enregistré : saisies, entrées modifiées, période et résultats.
+    pub fn rouvrir_calcul(&mut self, id: i64) {
+        let Some(ref historique) = self.historique else {
+            return;
+        };
+        let calcul = match historique.charger(id) {
+            Ok(Some(calcul)) => calcul,
+            Ok(None) => {
+                self.status_message = format!("Calcul #{} introuvable dans l'historique", id);
+                return;
+            }
+            Err(e) => {
+                self.status_message = format!("Impossible de rouvrir le calcul #{}: {}", id, e);
+                return;
+            }
+        };
+
+        // Le calcul est rouvert dans le dossier de son employé, pour que le prochain calcul
+        // n'écrive pas ses données dans celui d'un autre
+        let resume = &calcul.resume;
+        let employe = self.employes.iter().position(|dossier| {
+            let profil = &dossier.profil;
+            !resume.employe.is_empty()
+                && if resume.matricule.is_empty() { profil.nom == resume.employe } else { profil.matricule == resume.matricule }
+        });
+        let rouvert_pour = match employe {
+            Some(index) => {
+                self.selectionner_employe(index);
+                format!("pour {}", resume.employe)
+            }
+            None => {
+                self.deselectionner_employe();
+                if resume.employe.is_empty() {
+                    "en saisie libre".to_string()
+                } else {
+                    format!("en saisie libre ({} n'est pas dans l'espace de travail)", resume.employe)
+                }
+            }
+        };
+
+        let rapport = calcul.rapport;
+        self.donnees_hors_clientele = calcul.donnees_hors_clientele;
+        self.donnees_clientele = calcul.donnees_clientele;
+        self.donnees_melangees = [self.donnees_hors_clientele.as_str(), self.donnees_clientele.as_str()]
+            .iter()
+            .filter(|zone| !zone.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.source_entrees = Some((self.donnees_hors_clientele.clone(), self.donnees_clientele.clone()));
+        self.entrees = rapport.entrees;
+        self.diagnostics = rapport.diagnostics;
+        self.filtre_dates = match rapport.metadonnees.periode {
+            Some(periode) => FiltreDates {
+                periode: Periode::Personnalisee,
+                du: periode.du.map(|d| d.format("%d/%m/%Y").to_string()).unwrap_or_default(),
+                au: periode.au.map(|d| d.format("%d/%m/%Y").to_string()).unwrap_or_default(),
+            },
+            None => FiltreDates::default(),
+        };
+        self.saisie_filtre_du = None;
+        self.saisie_filtre_au = None;
+        self.entrees_hors_periode = rapport.metadonnees.entrees_hors_periode;
+        self.resultat = rapport.resultat;
+        self.temps_par_jour = rapport
+            .temps_par_jour
+            .iter()
+            .filter(|j| j.heures != 0.0)
+            .map(|j| (j.date, j.heures))
+            .collect();
+        self.show_result = self.resultat.is_some();
+        self.status_message = format!("Calcul {} rouvert {}", calcul.resume.libelle(), rouvert_pour);
+    }
+
+    pub(crate) fn afficher_historique(&mut self, ui: &mut egui::Ui) {
+        if self.historique.is_none() {
+            return;
+        }
+
+        let mut rouvrir = None;
+        let mut supprimer = None;
+        let mut actualiser = false;
+
+        ui.collapsing(format!("🕘 Historique ({} calcul(s))", self.vue_historique.calculs.len()), |ui| {
+            let vue = &mut self.vue_historique;
+            egui::ScrollArea::vertical().id_source("historique_calculs").max_height(200.0).show(ui, |ui| {
+                egui::Grid::new("historique_calculs_grille").striped(true).show(ui, |ui| {
+                    for titre in ["Calculé le", "Employé", "Période", "Total", "A", "B", ""] {
+                        ui.strong(titre);
+                    }
+                    ui.end_row();
+
+                    for calcul in &vue.calculs {
+                        ui.label(&calcul.calcule_le);
+                        ui.label(if calcul.employe.is_empty() { "—" } else { &calcul.employe });
+                        ui.label(calcul.libelle_periode());
+                        ui.label(format!("{:.2}h", calcul.total()));
+                        actualiser |= ui.radio_value(&mut vue.calcul_a, Some(calcul.id), "").changed();
+                        actualiser |= ui.radio_value(&mut vue.calcul_b, Some(calcul.id), "").changed();
+                        ui.horizontal(|ui| {
+                            if ui.small_button("📂 Rouvrir").clicked() {
+                                rouvrir = Some(calcul.id);
+                            }
+                            if ui.small_button("🗑").clicked() {
+                                supprimer = Some(calcul.id);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+
+            if !vue.comparaison.is_empty() {
+                ui.add_space(5.0);
+                ui.strong("Comparaison A / B");
+                egui::Grid::new("historique_comparaison").striped(true).show(ui, |ui| {
+                    for titre in ["", "A", "B", "Écart"] {
+                        ui.strong(titre);
+                    }
+                    ui.end_row();
+                    for ligne in &vue.comparaison {
+                        ui.label(&ligne.libelle);
+                        ui.label(format!("{:.2}", ligne.a));
+                        ui.label(format!("{:.2}", ligne.b));
+                        ui.label(format!("{:+.2}", ligne.ecart()));
+                        ui.end_row();
+                    }
+                });
+            } else {
+                ui.small("Cochez un calcul en A et un autre en B pour les comparer");
+            }
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.strong("Totaux par mois et par");
+                for regroupement in [Regroupement::Client, Regroupement::Categorie] {
+                    actualiser |= ui.radio_value(&mut vue.regroupement, regroupement, regroupement.libelle()).changed();
+                }
+            });
+            egui::Grid::new("historique_totaux").striped(true).show(ui, |ui| {
+                for titre in ["Mois", vue.regroupement.libelle(), "Heures", "Déplacement"] {
+                    ui.strong(titre);
+                }
+                ui.end_row();
+                for total in &vue.totaux {
+                    ui.label(&total.mois);
+                    ui.label(&total.cle);
+                    ui.label(format!("{:.2}", total.heures));
+                    ui.label(format!("{:.2}", total.deplacement));
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(id) = supprimer {
+            if let Some(ref mut historique) = self.historique {
+                if let Err(e) = historique.supprimer(id) {
+                    self.status_message = format!("Impossible de supprimer le calcul #{}: {}", id, e);
+                }
+            }
+            let vue = &mut self.vue_historique;
+            if vue.calcul_a == Some(id) {
+                vue.calcul_a = None;
+            }
+            if vue.calcul_b == Some(id) {
+                vue.calcul_b = None;
+            }
+            actualiser = true;
+        }
+        if actualiser {
+            self.actualiser_historique();
+        }
+        if let Some(id) = rouvrir {
+            self.rouvrir_calcul(id);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use eframe::egui;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::HashMap;
+
+use crate::{Calendrier, GestionTempsApp, TypeJour};
+
+pub const JOURS_SEMAINE: [&str; 7] = ["Lundi", "Mardi", "Mercredi", "Jeudi", "Vendredi", "Samedi", "Dimanche"];
+
+/// Heures prévues pour chaque jour de la semaine, du lundi au dimanche.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HoraireHebdo {
+    pub heures: [f64; 7],
+}
+
+impl Default for HoraireHebdo {
+    fn default() -> Self {
+        HoraireHebdo::depuis_contrat(35.0)
+    }
+}
+
+impl HoraireHebdo {
+    /// Répartit les heures hebdomadaires du contrat sur cinq jours.
+    pub fn depuis_contrat(heures_hebdo: f64) -> Self {
+        let jour = heures_hebdo / 5.0;
+        HoraireHebdo { heures: [jour, jour, jour, jour, jour, 0.0, 0.0] }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.heures.iter().sum()
+    }
+
+    pub fn heures_du_jour(&self, date: NaiveDate) -> f64 {
+        self.heures[date.weekday().num_days_from_monday() as usize]
+    }
+}
+
+/// Comparaison d'une journée avec l'horaire prévu.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LigneEcart {
+    pub date: NaiveDate,
+    pub type_jour: TypeJour,
+    pub prevu: f64,
+    pub realise: f64,
+    /// Réalisé moins prévu
+    pub ecart: f64,
+    /// Cumul des écarts depuis le début de la période
+    pub solde: f64,
+}
+
+/// Heures prévues un jour donné : rien les jours fériés et de fermeture.
+pub fn heures_prevues(horaire: &HoraireHebdo, type_jour: &TypeJour, date: NaiveDate) -> f64 {
+    match type_jour {
+        TypeJour::Ferie(_) | TypeJour::Fermeture(_) => 0.0,
+        TypeJour::Ouvre | TypeJour::WeekEnd => horaire.heures_du_jour(date),
+    }
+}
+
+/// Écarts jour par jour entre `debut` et `fin` inclus. Rien n'est prévu les jours fériés
+/// et de fermeture ; un jour sans saisie compte pour zéro heure réalisée.
+/// `temps_par_jour` est le temps réalisé, absences comprises.
+pub fn comparer_horaire(
+    horaire: &HoraireHebdo,
+    calendrier: &Calendrier,
+    temps_par_jour: &HashMap<NaiveDate, f64>,
+    debut: NaiveDate,
+    fin: NaiveDate,
+) -> Vec<LigneEcart> {
+    let mut lignes = Vec::new();
+    let mut solde = 0.0;
+    let mut date = debut;
+
+    while date <= fin {
+        let type_jour = calendrier.type_jour(date);
+        let prevu = heures_prevues(horaire, &type_jour, date);
+        let realise = temps_par_jour.get(&date).copied().unwrap_or(0.0);
+        let ecart = realise - prevu;
+        solde += ecart;
+
+        lignes.push(LigneEcart { date, type_jour, prevu, realise, ecart, solde });
+        date += Duration::days(1);
+    }
+
+    lignes
+}
+
+fn couleur_ecart(ecart: f64) -> egui::Color32 {
+    if ecart < -0.005 {
+        egui::Color32::from_rgb(220, 80, 60)
+    } else if ecart > 0.005 {
+        egui::Color32::from_rgb(60, 160, 90)
+    } else {
+        egui::Color32::GRAY
+    }
+}
+
+impl GestionTempsApp {
+    /// Horaire de l'employé actif, ou celui de la saisie libre sans employé.
+    pub fn horaire_actif(&self) -> &HoraireHebdo {
+        match self.employe_actif.and_then(|i| self.employes.get(i)) {
+            Some(dossier) => &dossier.profil.horaire,
+            None => &self.horaire,
+        }
+    }
+
+    fn horaire_actif_mut(&mut self) -> &mut HoraireHebdo {
+        match self.employe_actif.and_then(|i| self.employes.get_mut(i)) {
+            Some(dossier) => &mut dossier.profil.horaire,
+            None => &mut self.horaire,
+        }
+    }
+
+    /// Temps réalisé d'une journée : le temps travaillé plus les heures d'absence,
+    /// qui couvrent l'horaire prévu ce jour-là.
+    pub fn temps_realise(&self, date: NaiveDate) -> f64 {
+        let absences = self.resultat.as_ref().map_or(0.0, |r| r.absences_du_jour(date).map(|a| a.heures).sum());
+        self.temps_par_jour.get(&date).copied().unwrap_or(0.0) + absences
+    }
+
+    /// Temps réalisé de chaque jour saisi ou d'absence.
+    pub fn temps_realise_par_jour(&self) -> HashMap<NaiveDate, f64> {
+        let dates_absence = self.resultat.iter().flat_map(|r| r.absences.iter().filter_map(|a| a.date));
+        self.temps_par_jour
+            .keys()
+            .copied()
+            .chain(dates_absence)
+            .map(|date| (date, self.temps_realise(date)))
+            .collect()
+    }
+
+    /// Écarts sur la période filtrée, ou à défaut du premier au dernier jour saisi.
+    pub fn ecarts_horaire(&self) -> Vec<LigneEcart> {
+        let realise = self.temps_realise_par_jour();
+        let premier = realise.keys().min().copied();
+        let dernier = realise.keys().max().copied();
+
+        let (debut, fin) = match (self.filtre_dates.bornes_du_jour(), premier, dernier) {
+            (Some((debut, fin)), Some(premier), Some(dernier)) => (
+                if debut == NaiveDate::MIN { premier } else { debut },
+                if fin == NaiveDate::MAX { dernier } else { fin },
+            ),
+            (Some((debut, fin)), _, _) if debut != NaiveDate::MIN && fin != NaiveDate::MAX => (debut, fin),
+            (None, Some(premier), Some(dernier)) => (premier, dernier),
+            _ => return Vec::new(),
+        };
+
+        comparer_horaire(self.horaire_actif(), &self.calendrier, &realise, debut, fin)
+    }
+
+    pub(crate) fn afficher_horaire(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("🗓️ Horaire hebdomadaire", |ui| {
+            if let Some(dossier) = self.employe_actif.and_then(|i| self.employes.get(i)) {
+                ui.label(format!("Horaire de {}", dossier.profil.nom));
+            }
+
+            let horaire = self.horaire_actif_mut();
+            egui::Grid::new("horaire_hebdo").show(ui, |ui| {
+                for (jour, heures) in JOURS_SEMAINE.iter().zip(horaire.heures.iter_mut()) {
+                    ui.vertical(|ui| {
+                        ui.label(*jour);
+                        ui.add(egui::DragValue::new(heures).speed(0.25).clamp_range(0.0..=24.0).suffix(" h"));
+                    });
+                }
+                ui.end_row();
+            });
+            ui.label(format!("Total: {:.2} h/semaine", horaire.total()));
+        });
+    }
+
+    pub(crate) fn afficher_ecarts_horaire(&self, ui: &mut egui::Ui) {
+        let lignes = self.ecarts_horaire();
+        let Some(derniere) = lignes.last() else {
+            return;
+        };
+
+        ui.collapsing(format!("⚖️ Prévu / réalisé (solde: {:+.2}h)", derniere.solde), |ui| {
+            egui::Grid::new("ecarts_horaire").striped(true).show(ui, |ui| {
+                for titre in ["Date", "", "Prévu", "Réalisé", "Écart", "Solde"] {
+                    ui.strong(titre);
+                }
+                ui.end_row();
+
+                for ligne in &lignes {
+                    ui.label(format!(
+                        "{} {}",
+                        &JOURS_SEMAINE[ligne.date.weekday().num_days_from_monday() as usize][..3],
+                        ligne.date.format("%d/%m/%Y")
+                    ));
+                    ui.label(ligne.type_jour.libelle());
+                    ui.label(format!("{:.2}", ligne.prevu));
+                    ui.label(format!("{:.2}", ligne.realise));
+                    ui.colored_label(couleur_ecart(ligne.ecart), format!("{:+.2}", ligne.ecart));
+                    ui.colored_label(couleur_ecart(ligne.solde), format!("{:+.2}", ligne.solde));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -1,9 +1,64 @@
 use eframe::egui;
 use eframe::App;
+use image::ImageEncoder;
 use plotters::prelude::*;
-use std::collections::HashMap;
-use chrono::{NaiveDateTime,NaiveDate};
-use std::collections::BTreeMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDate;
+use entree::{date_de, duree_entre, horaire_trajet};
+use periode::dans_periode;
+
+pub mod absence;
+#[cfg(feature = "api")]
+pub mod api;
+pub mod agenda;
+pub mod arrondi;
+pub mod calendrier;
+pub mod categorie;
+pub mod classeur;
+pub mod compteur;
+pub mod deplacement;
+pub mod detection;
+pub mod employe;
+pub mod entree;
+pub mod facturation;
+pub mod fichier;
+pub mod historique;
+pub mod horaire;
+pub mod lot;
+pub mod periode;
+pub mod rapport_json;
+pub mod regroupement;
+pub mod saisie_manuelle;
+pub mod saisie_unifiee;
+pub mod surveillance;
+pub mod trajet;
+pub mod vue_mois;
+
+pub use absence::{Absence, ReglesAbsences};
+pub use agenda::EvenementAgenda;
+pub use arrondi::{EcartArrondi, PolitiqueArrondi, ReglesArrondi};
+pub use calendrier::{Calendrier, TypeJour};
+pub use categorie::{RegleCategorie, ReglesCategories};
+pub use classeur::ImportClasseur;
+pub use compteur::{CompteurHeures, FormulaireMouvement, MouvementCompteur, TypeMouvement};
+pub use deplacement::{NoteDeFrais, ParametresIndemnites};
+pub use employe::{DossierEmploye, LigneEquipe, ProfilEmploye, ReglagesCalcul};
+pub use entree::{EntreeSaisie, TypeEntree};
+pub use facturation::{RapportFacturation, Tarif, TarifsClients};
+pub use fichier::ZoneSaisie;
+pub use historique::{Historique, VueHistorique};
+pub use horaire::HoraireHebdo;
+pub use lot::{RapportLot, ResultatDossier};
+pub use periode::{FiltreDates, Periode};
+pub use rapport_json::RapportJson;
+pub use regroupement::VueDetails;
+pub use saisie_manuelle::FormulaireSaisie;
+pub use saisie_unifiee::Repartition;
+pub use surveillance::{NotificationTotaux, Surveillance};
+pub use trajet::{RepartitionTrajet, ReglesTrajet};
+pub use vue_mois::{Charge, VueMois};
 
 #[derive(Default)]
 pub struct GestionTempsApp {
@@ -13,19 +68,86 @@ pub struct GestionTempsApp {
     pub show_result: bool,
     pub status_message: String,
     pub temps_par_jour: HashMap<NaiveDate, f64>,
+    pub diagnostics: Vec<String>,
+    pub employes: Vec<DossierEmploye>,
+    pub employe_actif: Option<usize>,
+    pub nouveau_profil: ProfilEmploye,
+    pub chemin_lot: String,
+    pub rapport_lot: Option<RapportLot>,
+    pub import_classeur: Option<ImportClasseur>,
+    pub routage_automatique: bool,
+    pub alerte_zones_inversees: bool,
+    pub saisie_unifiee: bool,
+    pub donnees_melangees: String,
+    /// Classement de la saisie unique et texte classé, recalculé quand le collage change
+    pub apercu_saisie_unifiee: Option<(String, Repartition)>,
+    pub entrees: Vec<EntreeSaisie>,
+    /// Texte des deux zones au moment de la dernière analyse
+    pub source_entrees: Option<(String, String)>,
+    pub formulaire_saisie: FormulaireSaisie,
+    pub regles_categories: ReglesCategories,
+    /// Texte en cours d'édition dans le panneau des règles de catégories
+    pub texte_regles_categories: String,
+    pub vue_details: VueDetails,
+    pub filtre_dates: FiltreDates,
+    /// Bornes personnalisées en cours de saisie, pas encore appliquées au filtre
+    pub saisie_filtre_du: Option<String>,
+    pub saisie_filtre_au: Option<String>,
+    /// Entrées retenues mais écartées par le filtre de dates lors du dernier calcul
+    pub entrees_hors_periode: usize,
+    pub calendrier: Calendrier,
+    /// Saisie en cours d'un jour de fermeture
+    pub date_fermeture: String,
+    pub motif_fermeture: String,
+    /// Horaire utilisé quand aucun employé n'est sélectionné
+    pub horaire: HoraireHebdo,
+    pub compteur: CompteurHeures,
+    /// Fichier où le compteur est enregistré à chaque mouvement ; aucun enregistrement si absent
+    pub fichier_compteur: Option<std::path::PathBuf>,
+    pub formulaire_mouvement: FormulaireMouvement,
+    pub regles_absences: ReglesAbsences,
+    pub texte_regles_absences: String,
+    pub indemnites: ParametresIndemnites,
+    pub regles_trajet: ReglesTrajet,
+    pub regles_arrondi: ReglesArrondi,
+    pub texte_regles_arrondi: String,
+    pub tarifs: TarifsClients,
+    pub texte_tarifs: String,
+    pub vue_mois: VueMois,
+    /// Base des calculs passés ; aucun calcul n'est conservé si elle n'est pas ouverte
+    pub historique: Option<Historique>,
+    pub vue_historique: VueHistorique,
+    /// Dossier dont les exports sont recalculés automatiquement
+    pub surveillance: Option<Surveillance>,
+    pub dossier_surveillance: String,
+    pub notification_surveillance: Option<NotificationTotaux>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ResultatCalcul {
+    /// Heures hors clientèle, absences exclues
     pub hors_clientele: f64,
+    /// Heures de travail chez les clients
     pub travail_clientele: f64,
+    /// Heures de trajet vers les clients ; avec les règles de trajet, seule la part comptée
     pub deplacement: f64,
+    /// Répartition du trajet quand les règles de trajet sont actives : la franchise et le trajet
+    /// compensé ne sont pas dans `deplacement`
+    #[serde(default)]
+    pub trajets: Option<RepartitionTrajet>,
     pub details_hors_clientele: Vec<EntreeHorsClientele>,
+    /// Lignes hors clientèle reconnues comme absences, exclues des totaux ci-dessus
+    pub absences: Vec<Absence>,
+    /// Temps réel et arrondi par cible des règles d'arrondi ; les totaux ci-dessus sont arrondis
+    pub arrondis: BTreeMap<String, EcartArrondi>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct EntreeHorsClientele {
+    /// Code ABS de la ligne d'origine
+    pub code: String,
     pub description: String,
+    pub categorie: String,
     pub debut: Option<String>,
     pub fin: Option<String>,
     pub duree: f64,
@@ -34,66 +156,133 @@ pub struct EntreeHorsClientele {
 impl App for GestionTempsApp {
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.verifier_surveillance();
+        if self.surveillance.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+
+        egui::SidePanel::left("panneau_employes")
+            .resizable(true)
+            .default_width(220.0)
+            .show(ctx, |ui| {
+                self.afficher_panneau_employes(ui);
+            });
+
+        let mut zones_saisie = Vec::new();
+
         egui::CentralPanel::default().show(ctx, |ui| {
 
             egui::ScrollArea::vertical()
                 .auto_shrink([false;2])
                 .show(ui, |ui| {
                         ui.heading("📊 Gestion du Temps de Travail");
+                        if let Some(dossier) = self.employe_actif.and_then(|i| self.employes.get(i)) {
+                            ui.label(format!("Employé: {} - Équipe: {}", dossier.profil.nom, dossier.profil.equipe));
+                        }
                         ui.separator();
 
-                        // Zone de saisie 1 - Données Hors Clientèle
-                        ui.group(|ui| {
-                            ui.label("🏢 Données Hors Clientèle:");
-                            ui.add(
-                                egui::TextEdit::multiline(&mut self.donnees_hors_clientele)
-                                    .id_source("donnees_hors_clientele")
-                                    .desired_width(f32::INFINITY)
-                                    .desired_rows(8)
-                                    .hint_text("Collez vos données ici...\nFormat: Code\tDescription\tDébut\tFin"),
-                            );
-                            ui.small("Format attendu: Code\\tDescription\\tDébut (DD/MM/YYYY HH:MM)\\tFin (DD/MM/YYYY HH:MM)");
-                        });
+                        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+                            ui.colored_label(egui::Color32::LIGHT_BLUE, "📥 Déposez le fichier sur la zone à remplir");
+                        }
 
-                        ui.add_space(10.0);
+                        ui.checkbox(&mut self.saisie_unifiee, "Zone de saisie unique (lignes mélangées)");
+                        ui.add_space(5.0);
 
-                        // Zone de saisie 2 - Données En Clientèle
-                        ui.group(|ui| {
-                            ui.label("👥 Données En Clientèle:");
-                            ui.add(
-                                egui::TextEdit::multiline(&mut self.donnees_clientele)
-                                    .id_source("donnees_clientele")
-                                    .desired_width(f32::INFINITY)
-                                    .desired_rows(8)
-                                    .hint_text("Collez vos données ici..."),
-                            );
-                            ui.small("Format attendu: WO\\tClient\\t...\\tDuréeTravail\\tDuréeTrajet");
-                        });
+                        if self.saisie_unifiee {
+                            self.afficher_saisie_unifiee(ui);
+                        } else {
+                            // Zone de saisie 1 - Données Hors Clientèle
+                            let zone_hors_clientele = ui.group(|ui| {
+                                ui.label("🏢 Données Hors Clientèle:");
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut self.donnees_hors_clientele)
+                                        .id_source("donnees_hors_clientele")
+                                        .desired_width(f32::INFINITY)
+                                        .desired_rows(8)
+                                        .hint_text("Collez vos données ici...\nFormat: Code\tDescription\tDébut\tFin"),
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.small("Format attendu: Code\\tDescription\\tDébut (DD/MM/YYYY HH:MM)\\tFin (DD/MM/YYYY HH:MM)");
+                                    if ui.small_button("📂 Ouvrir fichier").clicked() {
+                                        self.ouvrir_fichier(ZoneSaisie::HorsClientele);
+                                    }
+                                    if ui.small_button("📅 Importer un agenda (.ics)").clicked() {
+                                        self.ouvrir_agenda();
+                                    }
+                                });
+                            });
+                            zones_saisie.push((ZoneSaisie::HorsClientele, zone_hors_clientele.response.rect));
+
+                            ui.add_space(10.0);
+
+                            // Zone de saisie 2 - Données En Clientèle
+                            let zone_clientele = ui.group(|ui| {
+                                ui.label("👥 Données En Clientèle:");
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut self.donnees_clientele)
+                                        .id_source("donnees_clientele")
+                                        .desired_width(f32::INFINITY)
+                                        .desired_rows(8)
+                                        .hint_text("Collez vos données ici..."),
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.small("Format attendu: WO\\tClient\\t...\\tDuréeTravail\\tDuréeTrajet");
+                                    if ui.small_button("📂 Ouvrir fichier").clicked() {
+                                        self.ouvrir_fichier(ZoneSaisie::Clientele);
+                                    }
+                                });
+                            });
+                            zones_saisie.push((ZoneSaisie::Clientele, zone_clientele.response.rect));
+                        }
+
+                        ui.add_space(10.0);
+                        self.afficher_entrees(ui);
+                        self.afficher_saisie_manuelle(ui);
+                        self.afficher_regles_categories(ui);
+                        self.afficher_regles_absences(ui);
+                        self.afficher_regles_trajet(ui);
+                        self.afficher_regles_arrondi(ui);
+                        self.afficher_tarifs(ui);
+                        self.afficher_calendrier(ui);
+                        self.afficher_horaire(ui);
+                        self.afficher_filtre_dates(ui);
 
                         ui.add_space(20.0);
 
                         // Boutons d'action
                         ui.horizontal(|ui| {
                             if ui.button("🔄 Calculer").clicked() {
-                                self.calculer_resultats();
+                                self.calculer_et_enregistrer();
                             }
                             
                             if ui.button("🗑️ Effacer").clicked() {
-                                self.donnees_hors_clientele.clear();
-                                self.donnees_clientele.clear();
-                                self.resultat = None;
-                                self.show_result = false;
-                                self.status_message.clear();
-                                self.temps_par_jour.clear();
+                                self.vider_saisie();
+                                self.sauvegarder_employe_actif();
                             }
+
+                            ui.checkbox(&mut self.routage_automatique, "Remettre automatiquement les données dans la bonne zone");
                         });
 
+                        self.afficher_alerte_zones(ui);
+
+                        ui.add_space(10.0);
+                        self.afficher_traitement_lot(ui);
+                        self.afficher_surveillance(ui);
+
                         // Message de statut
                         if !self.status_message.is_empty() {
                             ui.add_space(10.0);
                             ui.colored_label(egui::Color32::from_rgb(255, 165, 0), &self.status_message);
                         }
 
+                        if !self.diagnostics.is_empty() {
+                            ui.collapsing("⚠️ Avertissements de lecture", |ui| {
+                                for message in &self.diagnostics {
+                                    ui.small(message);
+                                }
+                            });
+                        }
+
                         // Affichage des résultats
                         if let Some(ref resultat) = self.resultat {
                             ui.separator();
@@ -103,84 +292,193 @@ impl App for GestionTempsApp {
                                 ui.label(format!("🏢 Hors clientèle: {:.2} heures", resultat.hors_clientele));
                                 ui.label(format!("👥 Travail clientèle: {:.2} heures", resultat.travail_clientele));
                                 ui.label(format!("🚗 Déplacement: {:.2} heures", resultat.deplacement));
+                                if let Some(ref trajets) = resultat.trajets {
+                                    ui.small(format!(
+                                        "Hors total : {:.2}h de trajet non comptées, {:.2}h compensées ({:.2}h de contrepartie)",
+                                        trajets.non_compte, trajets.compense, trajets.contrepartie
+                                    ));
+                                }
                                 
                                 let total = resultat.hors_clientele + resultat.travail_clientele + resultat.deplacement;
-                                ui.strong(&format!("⏱️ Total: {:.2} heures", total));
+                                ui.strong(format!("⏱️ Total: {:.2} heures", total));
                             });
 
                             // Détails des activités hors clientèle
                             if !resultat.details_hors_clientele.is_empty() {
                                 ui.add_space(10.0);
                                 ui.collapsing("📋 Détails Hors Clientèle", |ui| {
-                                    for detail in &resultat.details_hors_clientele {
-                                        ui.label(format!("• {}: {:.2}h", detail.description, detail.duree));
-                                    }
+                                    self.vue_details.afficher(ui, resultat);
                                 });
+                                self.afficher_categories(ui, resultat);
                             }
+                            self.afficher_absences(ui, resultat);
+                            self.afficher_repartition_trajets(ui);
+                            self.afficher_arrondis(ui, resultat);
 
                             ui.add_space(10.0);
-                            if ui.button("💾 Générer Graphique").clicked() {
-                                self.generer_graphique();
-                            }
+                            ui.horizontal(|ui| {
+                                if ui.button("💾 Générer Graphique").clicked() {
+                                    self.generer_graphique();
+                                }
+                                if ui.button("📊 Graphique par catégorie").clicked() {
+                                    self.generer_graphique_categories();
+                                }
+                                if ui.button("📅 Exporter vers l'agenda (.ics)").clicked() {
+                                    self.enregistrer_agenda();
+                                }
+                                if ui.button("🧾 Exporter en JSON").clicked() {
+                                    self.exporter_rapport_json();
+                                }
+                            });
                         }
 
                         // Ajoutez une section pour afficher les résultats par jour
-                        if !self.temps_par_jour.is_empty() {
-                            ui.separator();
-                            ui.heading("Temps de travail par jour :");
-
-                            let trie_par_date: BTreeMap<_, _> = self.temps_par_jour.iter().collect();
+                        if !self.temps_par_jour.is_empty() || self.resultat.as_ref().is_some_and(|r| !r.absences.is_empty()) {
+                            self.afficher_vue_mois(ui);
+                            self.afficher_temps_par_jour(ui);
+                        }
+                        self.afficher_ecarts_horaire(ui);
+                        self.afficher_compteur(ui);
+                        if self.resultat.is_some() {
+                            self.afficher_deplacements(ui);
+                            self.afficher_facturation(ui);
+                        }
 
-                            for (date, &total) in &trie_par_date {
-                                ui.label(format!(
-                                    "Date: {} - Temps total: {} ( {:.2}h )",
-                                    date,
-                                    self.format_duree_en_heures(total),
-                                    total
-                                ));
-                            }
+                        if !self.employes.is_empty() {
+                            ui.separator();
+                            self.afficher_vue_equipe(ui);
                         }
+                        self.afficher_historique(ui);
                 });
         });
+
+        self.gerer_fichiers_deposes(ctx, &zones_saisie);
+        self.afficher_notification_surveillance(ctx);
+        self.afficher_import_classeur(ctx);
     }
 }
 
 impl GestionTempsApp {
     pub fn calculer_resultats(&mut self) {
         self.status_message.clear();
+
+        // Les modifications du tableau sont conservées tant que le texte ne change pas
+        if self.entrees_obsoletes() {
+            self.analyser_saisie();
+        }
+        self.calculer_depuis_entrees();
+
+        self.sauvegarder_employe_actif();
+        self.status_message = if self.diagnostics.is_empty() {
+            "Calculs terminés avec succès !".to_string()
+        } else {
+            format!("Calculs terminés avec {} avertissement(s)", self.diagnostics.len())
+        };
+        if self.entrees_hors_periode > 0 {
+            self.status_message = format!(
+                "{} ({} entrée(s) hors période exclue(s))",
+                self.status_message, self.entrees_hors_periode
+            );
+        }
+    }
+
+    /// Calcul demandé par l'utilisateur : seul ce calcul est conservé dans l'historique,
+    /// pas les recalculs provoqués par un filtre, une modification du tableau ou la surveillance.
+    pub fn calculer_et_enregistrer(&mut self) {
+        if self.saisie_unifiee {
+            self.calculer_saisie_unifiee();
+        } else {
+            self.calculer_avec_verification();
+        }
+        self.enregistrer_dans_historique();
+    }
+
+    /// Calcule les totaux et le temps par jour à partir des entrées retenues.
+    pub fn calculer_depuis_entrees(&mut self) {
         self.temps_par_jour.clear(); // Reset des données par jour
-        
-        // Cloner les données pour éviter les conflits d'emprunt
-        let donnees_hors_clientele = self.donnees_hors_clientele.clone();
-        let donnees_clientele = self.donnees_clientele.clone();
-        
-        let hors_clientele_data = self.parser_donnees_hors_clientele(&donnees_hors_clientele);
-        let hors_clientele_total = hors_clientele_data.iter().map(|e| e.duree).sum::<f64>();
-        
-        let (travail, deplacement) = self.parser_donnees_clientele(&donnees_clientele);
-        
+
+        let mut hors_clientele_total = 0.0;
+        let mut travail = 0.0;
+        let mut deplacement = 0.0;
+        let mut details_hors_clientele = Vec::new();
+        let mut heures_absence = BTreeMap::new();
+        let mut arrondis = BTreeMap::new();
+        let bornes = self.filtre_dates.bornes_du_jour();
+        let trajets = self.repartition_trajets_active();
+        self.entrees_hors_periode = 0;
+
+        for entree in self.entrees.iter().filter(|e| e.actif) {
+            if !dans_periode(bornes, entree.date) {
+                self.entrees_hors_periode += 1;
+                continue;
+            }
+
+            if entree.type_entree == TypeEntree::HorsClientele {
+                if let Some(type_absence) = self.regles_absences.type_absence(&entree.code, &entree.description) {
+                    *heures_absence.entry((entree.date, type_absence)).or_insert(0.0) += entree.duree;
+                    continue;
+                }
+            }
+
+            let duree = arrondi::duree_arrondie(&self.regles_arrondi, &self.regles_categories, entree, &mut arrondis);
+            let trajet_compte = trajet::deplacement_compte(entree, trajets.as_ref());
+
+            match entree.type_entree {
+                TypeEntree::HorsClientele => {
+                    hors_clientele_total += duree;
+                    let mut detail = entree.vers_detail(&self.regles_categories);
+                    detail.duree = duree;
+                    details_hors_clientele.push(detail);
+                }
+                TypeEntree::Clientele => {
+                    travail += duree;
+                    deplacement += trajet_compte;
+                }
+            }
+
+            if let Some(date) = entree.date {
+                *self.temps_par_jour.entry(date).or_insert(0.0) += duree + trajet_compte;
+            }
+        }
+
         self.resultat = Some(ResultatCalcul {
             hors_clientele: hors_clientele_total,
             travail_clientele: travail,
             deplacement,
-            details_hors_clientele: hors_clientele_data,
+            trajets: trajets.as_ref().map(trajet::total_trajets),
+            details_hors_clientele,
+            absences: absence::regrouper_absences(heures_absence, self.horaire_actif()),
+            arrondis,
         });
-        
-        self.status_message = "Calculs terminés avec succès !".to_string();
     }
-    
+
     pub fn parser_donnees_hors_clientele(&mut self, donnees: &str) -> Vec<EntreeHorsClientele> {
+        let bornes = self.filtre_dates.bornes_du_jour();
+        let mut entrees = self.lire_entrees_hors_clientele(donnees);
+        entrees.retain(|e| dans_periode(bornes, e.date));
+
+        for entree in &entrees {
+            if let Some(date) = entree.date {
+                *self.temps_par_jour.entry(date).or_insert(0.0) += entree.duree;
+            }
+        }
+
+        entrees.iter().map(|e| e.vers_detail(&self.regles_categories)).collect()
+    }
+
+    pub fn lire_entrees_hors_clientele(&mut self, donnees: &str) -> Vec<EntreeSaisie> {
         let mut resultats = Vec::new();
 
         for (numero_ligne, ligne) in donnees.lines().enumerate() {
-            let ligne = ligne.trim();
-            if ligne.is_empty() {
+            // On conserve les tabulations finales : une fin vide reste une colonne
+            let ligne = ligne.trim_matches(|c: char| c.is_whitespace() && c != '\t');
+            if ligne.trim().is_empty() {
                 continue;
             }
 
             let parties: Vec<&str> = ligne.split('\t').collect();
             if parties.len() < 4 {
-                println!("Ligne {} ignorée (pas assez de colonnes): {}", numero_ligne + 1, ligne);
+                self.signaler(format!("Hors clientèle ligne {} ignorée (pas assez de colonnes): {}", numero_ligne + 1, ligne));
                 continue;
             }
 
@@ -191,32 +489,28 @@ impl GestionTempsApp {
 
             let debut = parties[2].trim();
             let fin = parties[3].trim();
-            let duree = self.calculer_duree(debut, fin);
 
             // Parsez la date de début pour obtenir la date
-            match NaiveDateTime::parse_from_str(debut, "%d/%m/%Y %H:%M") {
-                Ok(date_debut) => {
-                    let date = date_debut.date();
-                    *self.temps_par_jour.entry(date).or_insert(0.0) += duree;
-                }
-                Err(e) => {
-                    println!("Erreur parsing date hors clientèle ligne {}: '{}' - {}", numero_ligne + 1, debut, e);
-                    // Essayer d'autres formats possibles
-                    if let Ok(date_debut) = NaiveDateTime::parse_from_str(debut, "%d/%m/%Y %H:%M:%S") {
-                        let date = date_debut.date();
-                        *self.temps_par_jour.entry(date).or_insert(0.0) += duree;
-                    } else if let Ok(date_debut) = NaiveDateTime::parse_from_str(debut, "%Y-%m-%d %H:%M") {
-                        let date = date_debut.date();
-                        *self.temps_par_jour.entry(date).or_insert(0.0) += duree;
-                    }
-                }
+            let date = date_de(debut);
+            if date.is_none() && !debut.is_empty() {
+                self.signaler(format!("Hors clientèle ligne {}: date de début illisible '{}'", numero_ligne + 1, debut));
             }
 
-            resultats.push(EntreeHorsClientele {
+            resultats.push(EntreeSaisie {
+                actif: true,
+                type_entree: TypeEntree::HorsClientele,
+                code: parties[0].trim().to_string(),
+                client: String::new(),
                 description: description.to_string(),
-                debut: Some(debut.to_string()),
-                fin: Some(fin.to_string()),
-                duree,
+                debut: debut.to_string(),
+                fin: fin.to_string(),
+                date,
+                duree: self.calculer_duree(debut, fin),
+                deplacement: 0.0,
+                debut_trajet: String::new(),
+                fin_trajet: String::new(),
+                ligne: numero_ligne + 1,
+                saisie_manuelle: false,
             });
         }
 
@@ -228,6 +522,24 @@ impl GestionTempsApp {
         let mut total_travail = 0.0;
         let mut total_deplacement = 0.0;
 
+        let bornes = self.filtre_dates.bornes_du_jour();
+
+        for entree in self.lire_entrees_clientele(donnees).iter().filter(|e| e.actif && dans_periode(bornes, e.date)) {
+            total_travail += entree.duree;
+            total_deplacement += entree.deplacement;
+
+            if let Some(date) = entree.date {
+                *self.temps_par_jour.entry(date).or_insert(0.0) += entree.total();
+            }
+        }
+        (total_travail, total_deplacement)
+    }
+
+    /// Les interventions dont la date est illisible sont conservées mais désactivées,
+    /// pour pouvoir être corrigées dans le tableau des lignes analysées.
+    pub fn lire_entrees_clientele(&mut self, donnees: &str) -> Vec<EntreeSaisie> {
+        let mut resultats = Vec::new();
+
         for (numero_ligne, ligne) in donnees.lines().enumerate() {
             let ligne = ligne.trim();
             if ligne.is_empty() || ligne.starts_with("ABS") || ligne.starts_with("Description") {
@@ -235,81 +547,57 @@ impl GestionTempsApp {
             }
 
             let parties: Vec<&str> = ligne.split('\t').collect();
-            if parties.len() >= 6 {
-                // Parsez la date pour obtenir la date
-                let date_str = parties[8].trim();
-                println!("{}", date_str);
-                match NaiveDate::parse_from_str(date_str, "%d/%m/%Y %H:%M") {
-                    Ok(date) => {
-                        // Durée du travail (avant-dernière colonne)
-                        if let Ok(travail) = parties[parties.len() - 2].replace(',', ".").parse::<f64>() {
-                            total_travail += travail;
-                            *self.temps_par_jour.entry(date).or_insert(0.0) += travail;
-                        }
-
-                        // Durée du trajet (dernière colonne)
-                        if let Ok(deplacement) = parties[parties.len() - 1].replace(',', ".").parse::<f64>() {
-                            total_deplacement += deplacement;
-                            *self.temps_par_jour.entry(date).or_insert(0.0) += deplacement;
-                        }
-                    }
-                    Err(e) => {
-                        println!("Erreur parsing date clientèle ligne {}: '{}' - {}", numero_ligne + 1, date_str, e);
-                        
-                        // Essayer d'autres formats possibles
-                        let date_parsed = if let Ok(d) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                            Some(d)
-                        } else if let Ok(d) = NaiveDate::parse_from_str(date_str, "%d-%m-%Y") {
-                            Some(d)
-                        } else if let Ok(d) = NaiveDate::parse_from_str(date_str, "%d.%m.%Y") {
-                            Some(d)
-                        } else {
-                            println!("Impossible de parser la date: '{}'", date_str);
-                            None
-                        };
-                        
-                        if let Some(date) = date_parsed {
-                            // Durée du travail (avant-dernière colonne)
-                            if let Ok(travail) = parties[parties.len() - 2].replace(',', ".").parse::<f64>() {
-                                total_travail += travail;
-                                *self.temps_par_jour.entry(date).or_insert(0.0) += travail;
-                            }
+            if parties.len() < 9 {
+                self.signaler(format!("Clientèle ligne {} ignorée (pas assez de colonnes): {}", numero_ligne + 1, ligne));
+                continue;
+            }
 
-                            // Durée du trajet (dernière colonne)
-                            if let Ok(deplacement) = parties[parties.len() - 1].replace(',', ".").parse::<f64>() {
-                                total_deplacement += deplacement;
-                                *self.temps_par_jour.entry(date).or_insert(0.0) += deplacement;
-                            }
-                        }
-                    }
-                }
-            } else {
-                println!("Ligne {} ignorée (pas assez de colonnes): {}", numero_ligne + 1, ligne);
+            // Parsez la date pour obtenir la date
+            let date_str = parties[8].trim();
+            let date = date_de(date_str);
+            if date.is_none() {
+                self.signaler(format!("Clientèle ligne {}: impossible de parser la date '{}'", numero_ligne + 1, date_str));
             }
+
+            // Durée du travail (avant-dernière colonne) et du trajet (dernière colonne)
+            let mut duree_colonne = |index: usize, libelle: &str| {
+                let valeur = parties[index].trim();
+                valeur.replace(',', ".").parse::<f64>().unwrap_or_else(|_| {
+                    self.signaler(format!("Clientèle ligne {}: durée de {} illisible '{}'", numero_ligne + 1, libelle, valeur));
+                    0.0
+                })
+            };
+            let travail = duree_colonne(parties.len() - 2, "travail");
+            let deplacement = duree_colonne(parties.len() - 1, "trajet");
+
+            resultats.push(EntreeSaisie {
+                actif: date.is_some(),
+                type_entree: TypeEntree::Clientele,
+                code: parties[0].trim().to_string(),
+                client: parties[1].trim().to_string(),
+                description: parties[2].trim().to_string(),
+                debut: date_str.to_string(),
+                fin: parties.get(9).map(|fin| fin.trim().to_string()).unwrap_or_default(),
+                date,
+                duree: travail,
+                deplacement,
+                debut_trajet: horaire_trajet(parties[4]),
+                fin_trajet: horaire_trajet(parties[5]),
+                ligne: numero_ligne + 1,
+                saisie_manuelle: false,
+            });
         }
-        (total_travail, total_deplacement)
+
+        resultats
+    }
+
+    /// Conserve un avertissement de lecture, affiché par l'interface ou la ligne de commande.
+    pub fn signaler(&mut self, message: String) {
+        self.diagnostics.push(message);
     }
 
-    
     pub fn calculer_duree(&self, debut: &str, fin: &str) -> f64 {
-        if debut.is_empty() || fin.is_empty() {
-            return 0.0;
-        }
-        
-        let format = "%d/%m/%Y %H:%M";
-        
-        match (
-            NaiveDateTime::parse_from_str(debut, format),
-            NaiveDateTime::parse_from_str(fin, format)
-        ) {
-            (Ok(debut_dt), Ok(fin_dt)) => {
-                let duree = fin_dt.signed_duration_since(debut_dt);
-                duree.num_seconds() as f64 / 3600.0
-            }
-            _ => {
-                0.0
-            }
-        }
+        duree_entre(debut, fin)
     }
 
     pub fn format_duree_en_heures(&self,duree_heures: &f64) -> String {
@@ -318,7 +606,7 @@ impl GestionTempsApp {
         let _secondes = ((((duree_heures - heures as f64) * 60.0) - minutes as f64) * 60.0).round() as u32;
 
        // format!("{:02}h{:02}m{:02}s", heures, minutes, secondes)
-        format!("{:02}h{:02}m", heures, minutes)
+        format!("{:02}h{:02}", heures, minutes)
     }
     
     pub fn generer_graphique(&self) {
@@ -336,61 +624,85 @@ impl GestionTempsApp {
     
     fn creer_camembert(&self, resultat: &ResultatCalcul) -> Result<(), Box<dyn std::error::Error>> {
         let root = BitMapBackend::new("camembert_temps_travail.png", (800, 600)).into_drawing_area();
-        root.fill(&WHITE)?;
-        
-        let mut chart = ChartBuilder::on(&root)
-            .caption("Répartition du temps de travail", ("sans-serif", 30))
-            .margin(20)
-            .build_cartesian_2d(-1.2f32..1.2f32, -1.2f32..1.2f32)?;
-        
-        let total = resultat.hors_clientele + resultat.travail_clientele + resultat.deplacement;
-        if total == 0.0 {
-            return Ok(());
-        }
-        
-        let donnees = vec![
-            ("Hors clientèle", resultat.hors_clientele, &RED),
-            ("Travail clientèle", resultat.travail_clientele, &BLUE),
-            ("Déplacement", resultat.deplacement, &GREEN),
-        ];
-        
-        let mut angle_debut = 0.0f32;
-        
-        for (label, valeur, couleur) in donnees {
-            if valeur > 0.0 {
-                let angle_fin = angle_debut + (valeur / total * 360.0) as f32;
-                
-                // Dessiner la section du camembert
-                let points: Vec<(f32, f32)> = (0..=((angle_fin - angle_debut) as i32))
-                    .map(|i| {
-                        let angle = (angle_debut + i as f32) * std::f32::consts::PI / 180.0;
-                        (angle.cos(), angle.sin())
-                    })
-                    .collect();
-                
-                let mut path = vec![(0.0, 0.0)];
-                path.extend(points);
-                path.push((0.0, 0.0));
-                
-                chart.draw_series(std::iter::once(Polygon::new(path, couleur.filled())))?;
-                
-                // Ajouter le texte
-                let angle_milieu = (angle_debut + angle_fin) / 2.0 * std::f32::consts::PI / 180.0;
-                let x = angle_milieu.cos() * 0.7;
-                let y = angle_milieu.sin() * 0.7;
-                
-                chart.draw_series(std::iter::once(Text::new(
-                    format!("{}\n{:.1}h ({:.1}%)", label, valeur, valeur / total * 100.0),
-                    (x, y),
-                    ("sans-serif", 12),
-                )))?;
-                
-                angle_debut = angle_fin;
-            }
+        dessiner_camembert(&root, resultat)
+    }
+}
+
+/// Camembert de la répartition du temps, encodé en PNG en mémoire.
+pub fn camembert_png(resultat: &ResultatCalcul) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let (largeur, hauteur) = (800, 600);
+    let mut pixels = vec![0u8; (largeur * hauteur * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut pixels, (largeur, hauteur)).into_drawing_area();
+        dessiner_camembert(&root, resultat)?;
+    }
+
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png).write_image(&pixels, largeur, hauteur, image::ExtendedColorType::Rgb8)?;
+    Ok(png)
+}
+
+fn dessiner_camembert<DB: DrawingBackend>(
+    root: &DrawingArea<DB, plotters::coord::Shift>,
+    resultat: &ResultatCalcul,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+    
+    let mut chart = ChartBuilder::on(root)
+        .caption("Répartition du temps de travail", ("sans-serif", 30))
+        .margin(20)
+        .build_cartesian_2d(-1.2f32..1.2f32, -1.2f32..1.2f32)?;
+    
+    let total = resultat.hors_clientele + resultat.travail_clientele + resultat.deplacement;
+    if total == 0.0 {
+        return Ok(());
+    }
+    
+    let donnees = vec![
+        ("Hors clientèle", resultat.hors_clientele, &RED),
+        ("Travail clientèle", resultat.travail_clientele, &BLUE),
+        ("Déplacement", resultat.deplacement, &GREEN),
+    ];
+    
+    let mut angle_debut = 0.0f32;
+    
+    for (label, valeur, couleur) in donnees {
+        if valeur > 0.0 {
+            let angle_fin = angle_debut + (valeur / total * 360.0) as f32;
+            
+            // Dessiner la section du camembert
+            let points: Vec<(f32, f32)> = (0..=((angle_fin - angle_debut) as i32))
+                .map(|i| {
+                    let angle = (angle_debut + i as f32) * std::f32::consts::PI / 180.0;
+                    (angle.cos(), angle.sin())
+                })
+                .collect();
+            
+            let mut path = vec![(0.0, 0.0)];
+            path.extend(points);
+            path.push((0.0, 0.0));
+            
+            chart.draw_series(std::iter::once(Polygon::new(path, couleur.filled())))?;
+            
+            // Ajouter le texte
+            let angle_milieu = (angle_debut + angle_fin) / 2.0 * std::f32::consts::PI / 180.0;
+            let x = angle_milieu.cos() * 0.7;
+            let y = angle_milieu.sin() * 0.7;
+            
+            chart.draw_series(std::iter::once(Text::new(
+                format!("{}\n{:.1}h ({:.1}%)", label, valeur, valeur / total * 100.0),
+                (x, y),
+                ("sans-serif", 12),
+            )))?;
+            
+            angle_debut = angle_fin;
         }
-        
-        root.present()?;
-        
-        Ok(())
     }
+    
+    root.present()?;
+    
+    Ok(())
 }
\ No newline at end of file
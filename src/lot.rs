@@ -0,0 +1,336 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::detection::zones_inversees;
+use crate::fichier::lire_fichier_texte;
+use crate::{DossierEmploye, GestionTempsApp, Historique, ProfilEmploye, RapportJson, ReglagesCalcul, ResultatCalcul};
+
+/// Nom des fichiers attendus dans chaque sous-dossier (même disposition que `tests/data`).
+pub const FICHIER_HORS_CLIENTELE: &str = "hors_client.txt";
+pub const FICHIER_CLIENTELE: &str = "client.txt";
+pub const FICHIER_RAPPORT_CONSOLIDE: &str = "rapport_consolide.txt";
+/// Sous-dossier de sortie par défaut, ignoré lors du parcours.
+pub const DOSSIER_RAPPORTS: &str = "rapports";
+
+/// Résultat du calcul d'un sous-dossier (un employé ou une période).
+#[derive(Clone, Debug, Default)]
+pub struct ResultatDossier {
+    pub nom: String,
+    pub chemin: PathBuf,
+    pub donnees_hors_clientele: String,
+    pub donnees_clientele: String,
+    pub resultat: Option<ResultatCalcul>,
+    pub temps_par_jour: HashMap<NaiveDate, f64>,
+    pub problemes: Vec<String>,
+    pub rapport_json: Option<RapportJson>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RapportLot {
+    pub racine: PathBuf,
+    pub dossiers: Vec<ResultatDossier>,
+}
+
+fn chemin_absolu(chemin: &Path) -> PathBuf {
+    fs::canonicalize(chemin)
+        .or_else(|_| std::path::absolute(chemin))
+        .unwrap_or_else(|_| chemin.to_path_buf())
+}
+
+/// Vrai si `chemin` est le dossier de sortie, ou le contient.
+fn contient_la_sortie(chemin: &Path, sortie: &Path) -> bool {
+    chemin_absolu(sortie).starts_with(chemin_absolu(chemin))
+}
+
+/// Parcourt les sous-dossiers de `racine` et calcule chaque paire clientèle / hors clientèle
+/// avec les règles et le filtre de dates de `reglages` ; chaque calcul est enregistré dans `historique`.
+/// Le dossier `sortie` où seront écrits les rapports est ignoré, comme `rapports`.
+pub fn traiter_lot(
+    racine: &Path,
+    sortie: &Path,
+    reglages: &ReglagesCalcul,
+    mut historique: Option<&mut Historique>,
+) -> io::Result<RapportLot> {
+    let mut sous_dossiers: Vec<PathBuf> = fs::read_dir(racine)?
+        .filter_map(|entree| entree.ok().map(|e| e.path()))
+        .filter(|chemin| chemin.is_dir() && !chemin.ends_with(DOSSIER_RAPPORTS) && !contient_la_sortie(chemin, sortie))
+        .collect();
+    sous_dossiers.sort();
+
+    let dossiers = sous_dossiers
+        .iter()
+        .map(|chemin| traiter_sous_dossier(chemin, reglages, historique.as_deref_mut()))
+        .collect();
+
+    Ok(RapportLot {
+        racine: racine.to_path_buf(),
+        dossiers,
+    })
+}
+
+pub fn traiter_sous_dossier(
+    chemin: &Path,
+    reglages: &ReglagesCalcul,
+    historique: Option<&mut Historique>,
+) -> ResultatDossier {
+    let mut dossier = ResultatDossier {
+        nom: chemin
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        chemin: chemin.to_path_buf(),
+        ..Default::default()
+    };
+
+    dossier.donnees_hors_clientele = lire_fichier(&chemin.join(FICHIER_HORS_CLIENTELE), &mut dossier.problemes);
+    dossier.donnees_clientele = lire_fichier(&chemin.join(FICHIER_CLIENTELE), &mut dossier.problemes);
+
+    if zones_inversees(&dossier.donnees_hors_clientele, &dossier.donnees_clientele) {
+        std::mem::swap(&mut dossier.donnees_hors_clientele, &mut dossier.donnees_clientele);
+        dossier.problemes.push(format!(
+            "{} et {} semblent inversés, ils ont été échangés",
+            FICHIER_HORS_CLIENTELE, FICHIER_CLIENTELE
+        ));
+    }
+
+    // Le sous-dossier tient lieu d'employé dans le rapport et l'historique
+    let profil = ProfilEmploye {
+        nom: dossier.nom.clone(),
+        ..Default::default()
+    };
+    let mut app = GestionTempsApp {
+        employes: vec![DossierEmploye::new(profil)],
+        employe_actif: Some(0),
+        ..reglages.application(dossier.donnees_hors_clientele.clone(), dossier.donnees_clientele.clone())
+    };
+    app.calculer_resultats();
+
+    dossier.rapport_json = Some(app.rapport_json());
+    if let Some(historique) = historique {
+        if let Err(e) = app.enregistrer_calcul(historique) {
+            app.diagnostics.push(format!("Calcul non enregistré dans l'historique: {}", e));
+        }
+    }
+    dossier.problemes.extend(app.diagnostics);
+    dossier.resultat = app.resultat;
+    dossier.temps_par_jour = app.temps_par_jour;
+    dossier
+}
+
+fn lire_fichier(chemin: &Path, problemes: &mut Vec<String>) -> String {
+    match lire_fichier_texte(chemin) {
+        Ok(contenu) => contenu.texte,
+        Err(e) => {
+            problemes.push(format!("Lecture de {} impossible: {}", chemin.display(), e));
+            String::new()
+        }
+    }
+}
+
+fn totaux(resultat: &Option<ResultatCalcul>) -> (f64, f64, f64) {
+    match resultat {
+        Some(r) => (r.hors_clientele, r.travail_clientele, r.deplacement),
+        None => (0.0, 0.0, 0.0),
+    }
+}
+
+impl ResultatDossier {
+    pub fn total(&self) -> f64 {
+        let (hors_clientele, travail, deplacement) = totaux(&self.resultat);
+        hors_clientele + travail + deplacement
+    }
+
+    pub fn rapport(&self) -> String {
+        let (hors_clientele, travail, deplacement) = totaux(&self.resultat);
+        let mut texte = String::new();
+
+        let _ = writeln!(texte, "Rapport: {}", self.nom);
+        let _ = writeln!(texte, "Hors clientèle: {:.2} heures", hors_clientele);
+        let _ = writeln!(texte, "Travail clientèle: {:.2} heures", travail);
+        let _ = writeln!(texte, "Déplacement: {:.2} heures", deplacement);
+        let _ = writeln!(texte, "Total: {:.2} heures", self.total());
+
+        if !self.temps_par_jour.is_empty() {
+            let _ = writeln!(texte, "\nTemps de travail par jour :");
+            let trie_par_date: BTreeMap<_, _> = self.temps_par_jour.iter().collect();
+            for (date, total) in trie_par_date {
+                let _ = writeln!(texte, "{} : {:.2}h", date.format("%d/%m/%Y"), total);
+            }
+        }
+
+        if !self.problemes.is_empty() {
+            let _ = writeln!(texte, "\nProblèmes de lecture :");
+            for probleme in &self.problemes {
+                let _ = writeln!(texte, "- {}", probleme);
+            }
+        }
+
+        texte
+    }
+}
+
+impl RapportLot {
+    /// Totaux (hors clientèle, travail, déplacement) de l'ensemble des sous-dossiers.
+    pub fn totaux(&self) -> (f64, f64, f64) {
+        self.dossiers.iter().fold((0.0, 0.0, 0.0), |acc, dossier| {
+            let (h, t, d) = totaux(&dossier.resultat);
+            (acc.0 + h, acc.1 + t, acc.2 + d)
+        })
+    }
+
+    pub fn dossiers_en_erreur(&self) -> Vec<&ResultatDossier> {
+        self.dossiers.iter().filter(|d| !d.problemes.is_empty()).collect()
+    }
+
+    pub fn rapport_consolide(&self) -> String {
+        let mut texte = String::new();
+        let _ = writeln!(texte, "Rapport consolidé: {}", self.racine.display());
+        let _ = writeln!(texte, "Dossiers traités: {}\n", self.dossiers.len());
+
+        for dossier in &self.dossiers {
+            let (h, t, d) = totaux(&dossier.resultat);
+            let _ = writeln!(
+                texte,
+                "{} : hors clientèle {:.2}h - travail {:.2}h - déplacement {:.2}h - total {:.2}h",
+                dossier.nom, h, t, d, dossier.total()
+            );
+        }
+
+        let (h, t, d) = self.totaux();
+        let _ = writeln!(texte, "\nTotal hors clientèle: {:.2} heures", h);
+        let _ = writeln!(texte, "Total travail clientèle: {:.2} heures", t);
+        let _ = writeln!(texte, "Total déplacement: {:.2} heures", d);
+        let _ = writeln!(texte, "Total général: {:.2} heures", h + t + d);
+
+        let en_erreur = self.dossiers_en_erreur();
+        if en_erreur.is_empty() {
+            let _ = writeln!(texte, "\nAucun problème de lecture.");
+        } else {
+            let _ = writeln!(texte, "\nDossiers avec problèmes de lecture :");
+            for dossier in en_erreur {
+                let _ = writeln!(texte, "- {} ({} problème(s))", dossier.nom, dossier.problemes.len());
+            }
+        }
+
+        texte
+    }
+
+    /// Écrit un rapport texte et JSON par sous-dossier et le rapport consolidé dans `sortie`.
+    pub fn ecrire_rapports(&self, sortie: &Path) -> io::Result<()> {
+        fs::create_dir_all(sortie)?;
+
+        for dossier in &self.dossiers {
+            fs::write(sortie.join(format!("rapport_{}.txt", dossier.nom)), dossier.rapport())?;
+            if let Some(ref rapport_json) = dossier.rapport_json {
+                let json = rapport_json.vers_json().map_err(io::Error::other)?;
+                fs::write(sortie.join(format!("rapport_{}.json", dossier.nom)), json)?;
+            }
+        }
+        fs::write(sortie.join(FICHIER_RAPPORT_CONSOLIDE), self.rapport_consolide())
+    }
+}
+
+impl GestionTempsApp {
+    pub fn lancer_traitement_lot(&mut self) {
+        let racine = PathBuf::from(self.chemin_lot.trim());
+
+        let sortie = racine.join(DOSSIER_RAPPORTS);
+        let reglages = self.reglages_calcul();
+        let traitement = traiter_lot(&racine, &sortie, &reglages, self.historique.as_mut());
+        self.actualiser_historique();
+        match traitement {
+            Ok(rapport) => {
+                self.status_message = match rapport.ecrire_rapports(&sortie) {
+                    Ok(()) => format!(
+                        "{} dossier(s) traité(s), rapports écrits dans {}",
+                        rapport.dossiers.len(),
+                        sortie.display()
+                    ),
+                    Err(e) => format!("Erreur lors de l'écriture des rapports: {}", e),
+                };
+                self.rapport_lot = Some(rapport);
+            }
+            Err(e) => {
+                self.status_message = format!("Impossible de lire le dossier {}: {}", racine.display(), e);
+            }
+        }
+    }
+
+    /// Crée un employé par sous-dossier du dernier traitement par lot.
+    pub fn importer_lot_comme_employes(&mut self) {
+        let Some(rapport) = self.rapport_lot.take() else {
+            return;
+        };
+
+        self.sauvegarder_employe_actif();
+        for dossier in &rapport.dossiers {
+            self.employes.push(DossierEmploye {
+                profil: ProfilEmploye {
+                    nom: dossier.nom.clone(),
+                    ..Default::default()
+                },
+                donnees_hors_clientele: dossier.donnees_hors_clientele.clone(),
+                donnees_clientele: dossier.donnees_clientele.clone(),
+                resultat: dossier.resultat.clone(),
+                temps_par_jour: dossier.temps_par_jour.clone(),
+                ..Default::default()
+            });
+        }
+        self.rapport_lot = Some(rapport);
+    }
+
+    pub(crate) fn afficher_traitement_lot(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("📁 Traitement par lot", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Dossier:");
+                ui.text_edit_singleline(&mut self.chemin_lot);
+                if ui.button("▶ Traiter").clicked() && !self.chemin_lot.trim().is_empty() {
+                    self.lancer_traitement_lot();
+                }
+            });
+            ui.small(format!(
+                "Chaque sous-dossier doit contenir {} et {}",
+                FICHIER_HORS_CLIENTELE, FICHIER_CLIENTELE
+            ));
+
+            let mut importer = false;
+            if let Some(ref rapport) = self.rapport_lot {
+                egui::Grid::new("rapport_lot").striped(true).show(ui, |ui| {
+                    for titre in ["Dossier", "Hors clientèle", "Travail", "Déplacement", "Total", "Problèmes"] {
+                        ui.strong(titre);
+                    }
+                    ui.end_row();
+
+                    for dossier in &rapport.dossiers {
+                        let (h, t, d) = totaux(&dossier.resultat);
+                        ui.label(&dossier.nom);
+                        ui.label(format!("{:.2}", h));
+                        ui.label(format!("{:.2}", t));
+                        ui.label(format!("{:.2}", d));
+                        ui.strong(format!("{:.2}", dossier.total()));
+                        if dossier.problemes.is_empty() {
+                            ui.label("—");
+                        } else {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), dossier.problemes.len().to_string())
+                                .on_hover_text(dossier.problemes.join("\n"));
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                let (h, t, d) = rapport.totaux();
+                ui.strong(format!("⏱️ Total consolidé: {:.2} heures", h + t + d));
+                importer = ui.button("👤 Importer comme employés").clicked();
+            }
+
+            if importer {
+                self.importer_lot_comme_employes();
+            }
+        });
+    }
+}
@@ -1,8 +1,46 @@
 use eframe::egui;
 use gestion_temps::GestionTempsApp;
+use gestion_temps::compteur;
+use gestion_temps::historique;
+use gestion_temps::lot;
+use gestion_temps::rapport_json;
+use gestion_temps::{fichier, ReglagesCalcul, ZoneSaisie};
+use std::path::{Path, PathBuf};
 
 
 fn main() -> Result<(), eframe::Error> {
+    // Modes ligne de commande :
+    //   gestion_temps --lot <dossier> [<dossier de sortie>]
+    //   gestion_temps --json <hors clientèle> <clientèle> [<fichier de sortie>]
+    //   gestion_temps --schema-json [<fichier de sortie>]
+    //   gestion_temps --api [<adresse>]            (fonctionnalité "api")
+    // Et pour l'interface :
+    //   gestion_temps --surveiller <dossier>       (recalcul à chaque export déposé)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("--lot") => {
+            traiter_lot_cli(&args[1..]);
+            return Ok(());
+        }
+        Some("--json") => {
+            rapport_json_cli(&args[1..]);
+            return Ok(());
+        }
+        Some("--schema-json") => {
+            ecrire_sortie(args.get(1), &rapport_json::schema_json());
+            return Ok(());
+        }
+        #[cfg(feature = "api")]
+        Some("--api") => {
+            serveur_api_cli(&args[1..]);
+            return Ok(());
+        }
+        _ => {}
+    }
+    let dossier_surveille = match args.first().map(String::as_str) {
+        Some("--surveiller") => args.get(1).map(PathBuf::from),
+        _ => None,
+    };
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -20,10 +58,104 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "Gestion du Temps de Travail",
         options,
-        Box::new(|_cc| Box::new(GestionTempsApp::default())),
+        Box::new(|_cc| {
+            let mut app = GestionTempsApp::default();
+            app.charger_compteur(PathBuf::from(compteur::FICHIER_COMPTEUR));
+            app.ouvrir_historique(Path::new(historique::FICHIER_HISTORIQUE));
+            if let Some(dossier) = dossier_surveille {
+                app.dossier_surveillance = dossier.display().to_string();
+                app.demarrer_surveillance(&dossier);
+            }
+            Box::new(app)
+        }),
     )
 }
 
+fn traiter_lot_cli(args: &[String]) {
+    let Some(racine) = args.first().map(PathBuf::from) else {
+        eprintln!("Usage: gestion_temps --lot <dossier> [<dossier de sortie>]");
+        std::process::exit(2);
+    };
+    let sortie = args.get(1).map(PathBuf::from).unwrap_or_else(|| racine.join(lot::DOSSIER_RAPPORTS));
+
+    let mut historique = historique::Historique::ouvrir(Path::new(historique::FICHIER_HISTORIQUE))
+        .map_err(|e| eprintln!("Historique indisponible, calculs non enregistrés: {}", e))
+        .ok();
+    let rapport = match lot::traiter_lot(&racine, &sortie, &ReglagesCalcul::default(), historique.as_mut()) {
+        Ok(rapport) => rapport,
+        Err(e) => {
+            eprintln!("Impossible de lire le dossier {}: {}", racine.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = rapport.ecrire_rapports(&sortie) {
+        eprintln!("Erreur lors de l'écriture des rapports: {}", e);
+        std::process::exit(1);
+    }
+
+    println!("{}", rapport.rapport_consolide());
+    println!("Rapports écrits dans {}", sortie.display());
+}
+
+fn rapport_json_cli(args: &[String]) {
+    let (Some(hors_clientele), Some(clientele)) = (args.first(), args.get(1)) else {
+        eprintln!("Usage: gestion_temps --json <hors clientèle> <clientèle> [<fichier de sortie>]");
+        std::process::exit(2);
+    };
+
+    let mut app = GestionTempsApp::default();
+    for (zone, chemin) in [(ZoneSaisie::HorsClientele, hors_clientele), (ZoneSaisie::Clientele, clientele)] {
+        match fichier::lire_fichier_export(Path::new(chemin)) {
+            Ok(contenu) => *app.zone_mut(zone) = contenu.texte,
+            Err(e) => {
+                eprintln!("Impossible de lire {}: {}", chemin, e);
+                std::process::exit(1);
+            }
+        }
+    }
+    app.calculer_resultats();
+    for message in &app.diagnostics {
+        eprintln!("{}", message);
+    }
+
+    match app.rapport_json().vers_json() {
+        Ok(json) => ecrire_sortie(args.get(2), &json),
+        Err(e) => {
+            eprintln!("Erreur lors de la génération du rapport JSON: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "api")]
+fn serveur_api_cli(args: &[String]) {
+    let adresse = args.first().map_or(gestion_temps::api::ADRESSE_PAR_DEFAUT, String::as_str);
+    match gestion_temps::api::ServeurApi::demarrer(adresse) {
+        Ok(serveur) => {
+            println!("API à l'écoute sur http://{}", adresse);
+            serveur.servir();
+        }
+        Err(e) => {
+            eprintln!("Impossible d'écouter sur {}: {}", adresse, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Écrit le texte dans le fichier indiqué, ou sur la sortie standard.
+fn ecrire_sortie(chemin: Option<&String>, texte: &str) {
+    match chemin {
+        Some(chemin) => {
+            if let Err(e) = std::fs::write(chemin, texte) {
+                eprintln!("Impossible d'écrire {}: {}", chemin, e);
+                std::process::exit(1);
+            }
+        }
+        None => println!("{}", texte),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,185 @@
+use eframe::egui;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+use crate::entree::date_de;
+use crate::{EntreeSaisie, GestionTempsApp};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Periode {
+    #[default]
+    Toutes,
+    CetteSemaine,
+    SemaineDerniere,
+    CeMois,
+    MoisDernier,
+    Personnalisee,
+}
+
+impl Periode {
+    pub const TOUTES: [Periode; 6] = [
+        Periode::Toutes,
+        Periode::CetteSemaine,
+        Periode::SemaineDerniere,
+        Periode::CeMois,
+        Periode::MoisDernier,
+        Periode::Personnalisee,
+    ];
+
+    pub fn libelle(&self) -> &'static str {
+        match self {
+            Periode::Toutes => "Toutes les dates",
+            Periode::CetteSemaine => "Cette semaine",
+            Periode::SemaineDerniere => "Semaine dernière",
+            Periode::CeMois => "Ce mois-ci",
+            Periode::MoisDernier => "Mois dernier",
+            Periode::Personnalisee => "Personnalisée",
+        }
+    }
+}
+
+fn premier_du_mois(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+fn dernier_du_mois(date: NaiveDate) -> NaiveDate {
+    let premier = premier_du_mois(date);
+    let suivant = if premier.month() == 12 {
+        NaiveDate::from_ymd_opt(premier.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(premier.year(), premier.month() + 1, 1)
+    };
+    suivant.unwrap() - Duration::days(1)
+}
+
+/// Filtre du/au appliqué aux entrées avant le calcul. Les semaines commencent le lundi.
+#[derive(Clone, Debug, Default)]
+pub struct FiltreDates {
+    pub periode: Periode,
+    /// Bornes de la période personnalisée, "DD/MM/YYYY" ; vide pour une borne ouverte
+    pub du: String,
+    pub au: String,
+}
+
+impl FiltreDates {
+    /// Premier et dernier jour inclus, ou None si toutes les dates sont retenues.
+    pub fn bornes(&self, aujourdhui: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let lundi = aujourdhui - Duration::days(aujourdhui.weekday().num_days_from_monday() as i64);
+
+        match self.periode {
+            Periode::Toutes => None,
+            Periode::CetteSemaine => Some((lundi, lundi + Duration::days(6))),
+            Periode::SemaineDerniere => Some((lundi - Duration::days(7), lundi - Duration::days(1))),
+            Periode::CeMois => Some((premier_du_mois(aujourdhui), dernier_du_mois(aujourdhui))),
+            Periode::MoisDernier => {
+                let mois_dernier = premier_du_mois(aujourdhui) - Duration::days(1);
+                Some((premier_du_mois(mois_dernier), mois_dernier))
+            }
+            Periode::Personnalisee => Some((
+                date_de(&self.du).unwrap_or(NaiveDate::MIN),
+                date_de(&self.au).unwrap_or(NaiveDate::MAX),
+            )),
+        }
+    }
+
+    /// Bornes calculées par rapport à la date du jour.
+    pub fn bornes_du_jour(&self) -> Option<(NaiveDate, NaiveDate)> {
+        self.bornes(Local::now().date_naive())
+    }
+}
+
+/// Vrai si la date est dans la période ; une entrée sans date n'est retenue que sans filtre.
+pub fn dans_periode(bornes: Option<(NaiveDate, NaiveDate)>, date: Option<NaiveDate>) -> bool {
+    match (bornes, date) {
+        (None, _) => true,
+        (Some((debut, fin)), Some(date)) => debut <= date && date <= fin,
+        (Some(_), None) => false,
+    }
+}
+
+/// Vrai si le texte d'une borne personnalisée est vide (borne ouverte) ou une date DD/MM/YYYY.
+pub fn borne_valide(texte: &str) -> bool {
+    texte.trim().is_empty() || date_de(texte).is_some()
+}
+
+/// Champ d'une borne personnalisée. Le texte saisi n'est appliqué au filtre que s'il est valide :
+/// une date mal tapée est signalée en rouge et la borne précédente reste appliquée.
+fn champ_borne(ui: &mut egui::Ui, saisie: &mut Option<String>, borne: &mut String) -> bool {
+    let mut texte = saisie.clone().unwrap_or_else(|| borne.clone());
+    let invalide = !borne_valide(&texte);
+
+    let mut champ = egui::TextEdit::singleline(&mut texte).desired_width(90.0).hint_text("DD/MM/YYYY");
+    if invalide {
+        champ = champ.text_color(egui::Color32::RED);
+    }
+    let mut reponse = ui.add(champ);
+    if invalide {
+        let precedente = if borne.is_empty() { "aucune" } else { borne.as_str() };
+        reponse = reponse.on_hover_text(format!("Date invalide, borne appliquée: {}", precedente));
+    }
+
+    if reponse.changed() {
+        *saisie = Some(texte);
+    }
+    if reponse.lost_focus() {
+        if let Some(texte) = saisie.take_if(|texte| borne_valide(texte)) {
+            let modifie = texte.trim() != borne.as_str();
+            *borne = texte.trim().to_string();
+            return modifie;
+        }
+    }
+    false
+}
+
+fn format_borne(date: NaiveDate) -> String {
+    if date == NaiveDate::MIN || date == NaiveDate::MAX {
+        "…".to_string()
+    } else {
+        date.format("%d/%m/%Y").to_string()
+    }
+}
+
+impl GestionTempsApp {
+    /// Entrées cochées dont la date est dans la période du filtre.
+    pub fn entrees_retenues(&self) -> Vec<&EntreeSaisie> {
+        let bornes = self.filtre_dates.bornes_du_jour();
+        self.entrees.iter().filter(|e| e.actif && dans_periode(bornes, e.date)).collect()
+    }
+
+    pub(crate) fn afficher_filtre_dates(&mut self, ui: &mut egui::Ui) {
+        let mut modifie = false;
+
+        ui.horizontal(|ui| {
+            ui.label("📅 Période:");
+            egui::ComboBox::from_id_source("filtre_periode")
+                .selected_text(self.filtre_dates.periode.libelle())
+                .show_ui(ui, |ui| {
+                    for periode in Periode::TOUTES {
+                        modifie |= ui
+                            .selectable_value(&mut self.filtre_dates.periode, periode, periode.libelle())
+                            .changed();
+                    }
+                });
+
+            if self.filtre_dates.periode == Periode::Personnalisee {
+                ui.label("du");
+                modifie |= champ_borne(ui, &mut self.saisie_filtre_du, &mut self.filtre_dates.du);
+                ui.label("au");
+                modifie |= champ_borne(ui, &mut self.saisie_filtre_au, &mut self.filtre_dates.au);
+            }
+
+            if let Some((debut, fin)) = self.filtre_dates.bornes_du_jour() {
+                ui.label(format!("{} → {}", format_borne(debut), format_borne(fin)));
+                if self.entrees_hors_periode > 0 {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 165, 0),
+                        format!("{} entrée(s) exclue(s)", self.entrees_hors_periode),
+                    );
+                }
+            }
+        });
+
+        if modifie && self.resultat.is_some() {
+            self.calculer_resultats();
+        }
+    }
+}
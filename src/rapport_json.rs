@@ -0,0 +1,161 @@
+use chrono::{DateTime, Local, NaiveDate};
+use schemars::{schema_for, JsonSchema};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::horaire::heures_prevues;
+use crate::{EntreeSaisie, GestionTempsApp, ResultatCalcul, TypeJour};
+
+/// Version du schéma du rapport JSON. Elle change à chaque modification incompatible
+/// (champ supprimé, renommé ou dont le type change) ; un champ ajouté ne la change pas.
+pub const VERSION_SCHEMA: u32 = 1;
+pub const FICHIER_RAPPORT_JSON: &str = "rapport.json";
+/// Schéma JSON livré avec le dépôt, régénéré par `gestion_temps --schema-json`.
+pub const FICHIER_SCHEMA_JSON: &str = "schema/rapport.schema.json";
+
+/// Rapport complet d'un calcul, destiné aux outils externes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RapportJson {
+    /// Version du schéma de ce document
+    pub version_schema: u32,
+    pub metadonnees: MetadonneesRapport,
+    /// Résultat du calcul, absent si aucun calcul n'a été fait
+    pub resultat: Option<ResultatCalcul>,
+    /// Hors clientèle, travail clientèle et déplacement, en heures
+    pub total: f64,
+    /// Temps par jour, dans l'ordre des dates
+    pub temps_par_jour: Vec<JourRapport>,
+    /// Entrées analysées, y compris celles décochées ou hors période
+    pub entrees: Vec<EntreeSaisie>,
+    /// Avertissements de lecture des données
+    pub diagnostics: Vec<String>,
+}
+
+/// Origine et périmètre des données du rapport.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MetadonneesRapport {
+    /// Date et heure de génération, avec le décalage horaire local
+    pub genere_le: DateTime<Local>,
+    pub version_application: String,
+    /// Nom de l'employé sélectionné, absent en saisie libre
+    pub employe: Option<String>,
+    pub matricule: Option<String>,
+    /// Bornes incluses du filtre de dates, absentes sans filtre ; une borne ouverte est nulle
+    pub periode: Option<PeriodeRapport>,
+    /// Lignes non vides des zones de saisie
+    pub lignes_hors_clientele: usize,
+    pub lignes_clientele: usize,
+    /// Entrées retenues dans le calcul (cochées et dans la période)
+    pub entrees_retenues: usize,
+    /// Entrées cochées écartées par le filtre de dates
+    pub entrees_hors_periode: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PeriodeRapport {
+    pub du: Option<NaiveDate>,
+    pub au: Option<NaiveDate>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JourRapport {
+    pub date: NaiveDate,
+    pub type_jour: TypeJour,
+    /// Temps travaillé (travail, hors clientèle et déplacement), en heures
+    pub heures: f64,
+    /// Heures d'absence
+    pub absences: f64,
+    /// Heures prévues par l'horaire
+    pub prevu: f64,
+}
+
+fn lignes_non_vides(texte: &str) -> usize {
+    texte.lines().filter(|l| !l.trim().is_empty()).count()
+}
+
+fn non_vide(texte: &str) -> Option<String> {
+    (!texte.trim().is_empty()).then(|| texte.trim().to_string())
+}
+
+impl RapportJson {
+    pub fn vers_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn depuis_json(texte: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(texte)
+    }
+
+    pub fn enregistrer(&self, chemin: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(chemin, self.vers_json()?)?;
+        Ok(())
+    }
+}
+
+/// Schéma JSON du rapport, généré à partir des types.
+pub fn schema_json() -> String {
+    serde_json::to_string_pretty(&schema_for!(RapportJson)).expect("schéma sérialisable")
+}
+
+impl GestionTempsApp {
+    /// Rapport JSON du dernier calcul.
+    pub fn rapport_json(&self) -> RapportJson {
+        let profil = self.employe_actif.and_then(|i| self.employes.get(i)).map(|d| &d.profil);
+        let periode = self.filtre_dates.bornes_du_jour().map(|(du, au)| PeriodeRapport {
+            du: (du != NaiveDate::MIN).then_some(du),
+            au: (au != NaiveDate::MAX).then_some(au),
+        });
+
+        let mut dates: Vec<NaiveDate> = self.temps_par_jour.keys().copied().collect();
+        if let Some(ref resultat) = self.resultat {
+            dates.extend(resultat.absences.iter().filter_map(|a| a.date));
+        }
+        dates.sort();
+        dates.dedup();
+
+        let temps_par_jour = dates
+            .into_iter()
+            .map(|date| {
+                let type_jour = self.calendrier.type_jour(date);
+                JourRapport {
+                    date,
+                    heures: self.temps_par_jour.get(&date).copied().unwrap_or(0.0),
+                    absences: self.resultat.as_ref().map_or(0.0, |r| r.absences_du_jour(date).fold(0.0, |t, a| t + a.heures)),
+                    prevu: heures_prevues(self.horaire_actif(), &type_jour, date),
+                    type_jour,
+                }
+            })
+            .collect();
+
+        RapportJson {
+            version_schema: VERSION_SCHEMA,
+            metadonnees: MetadonneesRapport {
+                genere_le: Local::now(),
+                version_application: env!("CARGO_PKG_VERSION").to_string(),
+                employe: profil.and_then(|p| non_vide(&p.nom)),
+                matricule: profil.and_then(|p| non_vide(&p.matricule)),
+                periode,
+                lignes_hors_clientele: lignes_non_vides(&self.donnees_hors_clientele),
+                lignes_clientele: lignes_non_vides(&self.donnees_clientele),
+                entrees_retenues: self.entrees_retenues().len(),
+                entrees_hors_periode: self.entrees_hors_periode,
+            },
+            total: self
+                .resultat
+                .as_ref()
+                .map_or(0.0, |r| r.hors_clientele + r.travail_clientele + r.deplacement),
+            resultat: self.resultat.clone(),
+            temps_par_jour,
+            entrees: self.entrees.clone(),
+            diagnostics: self.diagnostics.clone(),
+        }
+    }
+
+    pub fn exporter_rapport_json(&mut self) {
+        self.status_message = match self.rapport_json().enregistrer(Path::new(FICHIER_RAPPORT_JSON)) {
+            Ok(()) => format!("Rapport JSON écrit dans {}", FICHIER_RAPPORT_JSON),
+            Err(e) => format!("Impossible d'écrire {}: {}", FICHIER_RAPPORT_JSON, e),
+        };
+    }
+}
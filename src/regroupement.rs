@@ -0,0 +1,184 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use std::cmp::Ordering;
+
+use crate::entree::date_de;
+use crate::{EntreeHorsClientele, ResultatCalcul};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CleRegroupement {
+    Description,
+    Categorie,
+}
+
+impl CleRegroupement {
+    pub fn libelle(&self) -> &'static str {
+        match self {
+            CleRegroupement::Description => "Description",
+            CleRegroupement::Categorie => "Catégorie",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColonneTri {
+    Libelle,
+    Duree,
+    Nombre,
+    Premiere,
+    Derniere,
+}
+
+/// Activités hors clientèle de même description (ou de même catégorie).
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupeDetails {
+    pub libelle: String,
+    pub duree: f64,
+    pub nombre: usize,
+    pub premiere: Option<NaiveDate>,
+    pub derniere: Option<NaiveDate>,
+}
+
+/// Présentation de la section "Détails Hors Clientèle".
+#[derive(Clone, Debug)]
+pub struct VueDetails {
+    pub groupee: bool,
+    pub cle: CleRegroupement,
+    pub tri: ColonneTri,
+    pub croissant: bool,
+}
+
+impl Default for VueDetails {
+    fn default() -> Self {
+        VueDetails {
+            groupee: true,
+            cle: CleRegroupement::Description,
+            tri: ColonneTri::Duree,
+            croissant: false,
+        }
+    }
+}
+
+/// Regroupe les détails par description (sans tenir compte de la casse) ou par catégorie,
+/// dans l'ordre de première apparition.
+pub fn regrouper_details(details: &[EntreeHorsClientele], cle: CleRegroupement) -> Vec<GroupeDetails> {
+    let mut groupes: Vec<GroupeDetails> = Vec::new();
+
+    for detail in details {
+        let libelle = match cle {
+            CleRegroupement::Description => detail.description.trim(),
+            CleRegroupement::Categorie => detail.categorie.trim(),
+        };
+        let date = detail.debut.as_deref().and_then(date_de);
+
+        let index = match groupes.iter().position(|g| g.libelle.to_lowercase() == libelle.to_lowercase()) {
+            Some(index) => index,
+            None => {
+                groupes.push(GroupeDetails {
+                    libelle: libelle.to_string(),
+                    duree: 0.0,
+                    nombre: 0,
+                    premiere: None,
+                    derniere: None,
+                });
+                groupes.len() - 1
+            }
+        };
+
+        let groupe = &mut groupes[index];
+        groupe.duree += detail.duree;
+        groupe.nombre += 1;
+        if let Some(date) = date {
+            groupe.premiere = Some(groupe.premiere.map_or(date, |d| d.min(date)));
+            groupe.derniere = Some(groupe.derniere.map_or(date, |d| d.max(date)));
+        }
+    }
+
+    groupes
+}
+
+pub fn trier_groupes(groupes: &mut [GroupeDetails], colonne: ColonneTri, croissant: bool) {
+    groupes.sort_by(|a, b| {
+        let ordre = match colonne {
+            ColonneTri::Libelle => a.libelle.to_lowercase().cmp(&b.libelle.to_lowercase()),
+            ColonneTri::Duree => a.duree.partial_cmp(&b.duree).unwrap_or(Ordering::Equal),
+            ColonneTri::Nombre => a.nombre.cmp(&b.nombre),
+            ColonneTri::Premiere => a.premiere.cmp(&b.premiere),
+            ColonneTri::Derniere => a.derniere.cmp(&b.derniere),
+        };
+        if croissant { ordre } else { ordre.reverse() }
+    });
+}
+
+fn date_courte(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format("%d/%m/%Y").to_string()).unwrap_or_else(|| "—".to_string())
+}
+
+impl VueDetails {
+    /// Un clic sur la colonne déjà triée inverse le sens du tri.
+    pub fn trier_par(&mut self, colonne: ColonneTri) {
+        if self.tri == colonne {
+            self.croissant = !self.croissant;
+        } else {
+            self.tri = colonne;
+            self.croissant = colonne == ColonneTri::Libelle;
+        }
+    }
+
+    pub fn groupes(&self, resultat: &ResultatCalcul) -> Vec<GroupeDetails> {
+        let mut groupes = regrouper_details(&resultat.details_hors_clientele, self.cle);
+        trier_groupes(&mut groupes, self.tri, self.croissant);
+        groupes
+    }
+
+    fn entete(&mut self, ui: &mut egui::Ui, titre: &str, colonne: ColonneTri) {
+        let fleche = match (self.tri == colonne, self.croissant) {
+            (true, true) => " ⏶",
+            (true, false) => " ⏷",
+            (false, _) => "",
+        };
+        if ui.selectable_label(self.tri == colonne, format!("{}{}", titre, fleche)).clicked() {
+            self.trier_par(colonne);
+        }
+    }
+
+    pub(crate) fn afficher(&mut self, ui: &mut egui::Ui, resultat: &ResultatCalcul) {
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.groupee, true, "Regroupé");
+            ui.radio_value(&mut self.groupee, false, "Détail");
+            if self.groupee {
+                ui.separator();
+                ui.label("par");
+                for cle in [CleRegroupement::Description, CleRegroupement::Categorie] {
+                    ui.radio_value(&mut self.cle, cle, cle.libelle());
+                }
+            }
+        });
+
+        if !self.groupee {
+            for detail in &resultat.details_hors_clientele {
+                ui.label(format!("• {} [{}]: {:.2}h", detail.description, detail.categorie, detail.duree));
+            }
+            return;
+        }
+
+        let groupes = self.groupes(resultat);
+        egui::Grid::new("details_regroupes").striped(true).show(ui, |ui| {
+            self.entete(ui, self.cle.libelle(), ColonneTri::Libelle);
+            self.entete(ui, "Durée", ColonneTri::Duree);
+            self.entete(ui, "Lignes", ColonneTri::Nombre);
+            self.entete(ui, "Première date", ColonneTri::Premiere);
+            self.entete(ui, "Dernière date", ColonneTri::Derniere);
+            ui.end_row();
+
+            for groupe in &groupes {
+                ui.label(&groupe.libelle);
+                ui.label(format!("{:.2}h", groupe.duree));
+                ui.label(groupe.nombre.to_string());
+                ui.label(date_courte(groupe.premiere));
+                ui.label(date_courte(groupe.derniere));
+                ui.end_row();
+            }
+        });
+    }
+}
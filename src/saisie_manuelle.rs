@@ -0,0 +1,197 @@
+use eframe::egui;
+use chrono::Duration;
+
+use crate::entree::{date_de, date_heure_de, duree_entre};
+use crate::{EntreeSaisie, GestionTempsApp, TypeEntree};
+
+/// Champs du formulaire de saisie manuelle.
+#[derive(Clone, Debug)]
+pub struct FormulaireSaisie {
+    pub type_entree: TypeEntree,
+    pub description: String,
+    pub client: String,
+    pub code: String,
+    /// "DD/MM/YYYY HH:MM"
+    pub debut: String,
+    /// "DD/MM/YYYY HH:MM", hors clientèle uniquement
+    pub fin: String,
+    pub travail: f64,
+    pub deplacement: f64,
+    pub erreur: Option<String>,
+}
+
+impl Default for FormulaireSaisie {
+    fn default() -> Self {
+        FormulaireSaisie {
+            type_entree: TypeEntree::HorsClientele,
+            description: String::new(),
+            client: String::new(),
+            code: String::new(),
+            debut: String::new(),
+            fin: String::new(),
+            travail: 0.0,
+            deplacement: 0.0,
+            erreur: None,
+        }
+    }
+}
+
+impl FormulaireSaisie {
+    /// Valide les champs et construit l'entrée correspondante.
+    pub fn vers_entree(&self) -> Result<EntreeSaisie, String> {
+        let debut = date_heure_de(&self.debut)
+            .ok_or_else(|| format!("Début invalide '{}' (format attendu: DD/MM/YYYY HH:MM)", self.debut))?;
+
+        let entree = match self.type_entree {
+            TypeEntree::HorsClientele => {
+                if self.description.trim().is_empty() {
+                    return Err("La description est obligatoire".to_string());
+                }
+                let fin = date_heure_de(&self.fin)
+                    .ok_or_else(|| format!("Fin invalide '{}' (format attendu: DD/MM/YYYY HH:MM)", self.fin))?;
+                if fin <= debut {
+                    return Err("La fin doit être postérieure au début".to_string());
+                }
+
+                let debut = debut.format("%d/%m/%Y %H:%M").to_string();
+                let fin = fin.format("%d/%m/%Y %H:%M").to_string();
+                EntreeSaisie {
+                    actif: true,
+                    type_entree: TypeEntree::HorsClientele,
+                    code: self.code.trim().to_string(),
+                    client: String::new(),
+                    description: self.description.trim().to_string(),
+                    date: date_de(&debut),
+                    duree: duree_entre(&debut, &fin),
+                    deplacement: 0.0,
+                    debut_trajet: String::new(),
+                    fin_trajet: String::new(),
+                    debut,
+                    fin,
+                    ligne: 0,
+                    saisie_manuelle: true,
+                }
+            }
+            TypeEntree::Clientele => {
+                if self.client.trim().is_empty() {
+                    return Err("Le client est obligatoire".to_string());
+                }
+                if self.travail <= 0.0 {
+                    return Err("La durée de travail doit être positive".to_string());
+                }
+                if self.deplacement < 0.0 {
+                    return Err("La durée de trajet ne peut pas être négative".to_string());
+                }
+
+                // L'intervention occupe la plage début -> début + durée de travail
+                let fin = debut + Duration::seconds((self.travail * 3600.0).round() as i64);
+                EntreeSaisie {
+                    actif: true,
+                    type_entree: TypeEntree::Clientele,
+                    code: self.code.trim().to_string(),
+                    client: self.client.trim().to_string(),
+                    description: self.description.trim().to_string(),
+                    debut: debut.format("%d/%m/%Y %H:%M").to_string(),
+                    fin: fin.format("%d/%m/%Y %H:%M").to_string(),
+                    date: Some(debut.date()),
+                    duree: self.travail,
+                    deplacement: self.deplacement,
+                    debut_trajet: String::new(),
+                    fin_trajet: String::new(),
+                    ligne: 0,
+                    saisie_manuelle: true,
+                }
+            }
+        };
+
+        Ok(entree)
+    }
+}
+
+impl GestionTempsApp {
+    /// Ajoute une entrée saisie à la main, refusée si elle chevauche une entrée retenue.
+    pub fn ajouter_entree_manuelle(&mut self, entree: EntreeSaisie) -> Result<(), String> {
+        // Analyser d'abord le texte collé pour vérifier les chevauchements avec ses lignes
+        if self.entrees_obsoletes() {
+            self.analyser_saisie();
+        }
+
+        if let Some(conflit) = self.entrees.iter().find(|e| e.actif && e.chevauche(&entree)) {
+            return Err(format!(
+                "Chevauchement avec '{}' ({} - {})",
+                if conflit.description.is_empty() { &conflit.client } else { &conflit.description },
+                conflit.debut,
+                conflit.fin
+            ));
+        }
+
+        self.entrees.push(entree);
+        Ok(())
+    }
+
+    pub(crate) fn afficher_saisie_manuelle(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("✍️ Saisie manuelle", |ui| {
+            let formulaire = &mut self.formulaire_saisie;
+
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut formulaire.type_entree, TypeEntree::HorsClientele, "Hors clientèle");
+                ui.radio_value(&mut formulaire.type_entree, TypeEntree::Clientele, "Intervention clientèle");
+            });
+
+            egui::Grid::new("formulaire_saisie").num_columns(2).show(ui, |ui| {
+                match formulaire.type_entree {
+                    TypeEntree::HorsClientele => {
+                        ui.label("Description:");
+                        ui.text_edit_singleline(&mut formulaire.description);
+                        ui.end_row();
+
+                        ui.label("Début:");
+                        ui.add(egui::TextEdit::singleline(&mut formulaire.debut).hint_text("DD/MM/YYYY HH:MM"));
+                        ui.end_row();
+
+                        ui.label("Fin:");
+                        ui.add(egui::TextEdit::singleline(&mut formulaire.fin).hint_text("DD/MM/YYYY HH:MM"));
+                        ui.end_row();
+                    }
+                    TypeEntree::Clientele => {
+                        ui.label("Client:");
+                        ui.text_edit_singleline(&mut formulaire.client);
+                        ui.end_row();
+
+                        ui.label("WO:");
+                        ui.text_edit_singleline(&mut formulaire.code);
+                        ui.end_row();
+
+                        ui.label("Début intervention:");
+                        ui.add(egui::TextEdit::singleline(&mut formulaire.debut).hint_text("DD/MM/YYYY HH:MM"));
+                        ui.end_row();
+
+                        ui.label("Durée travail:");
+                        ui.add(egui::DragValue::new(&mut formulaire.travail).speed(0.05).clamp_range(0.0..=24.0).suffix(" h"));
+                        ui.end_row();
+
+                        ui.label("Durée trajet:");
+                        ui.add(egui::DragValue::new(&mut formulaire.deplacement).speed(0.05).clamp_range(0.0..=24.0).suffix(" h"));
+                        ui.end_row();
+                    }
+                }
+            });
+
+            if ui.button("➕ Ajouter").clicked() {
+                let ajout = self.formulaire_saisie.vers_entree().and_then(|entree| self.ajouter_entree_manuelle(entree));
+                match ajout {
+                    Ok(()) => {
+                        let type_entree = self.formulaire_saisie.type_entree;
+                        self.formulaire_saisie = FormulaireSaisie { type_entree, ..Default::default() };
+                        self.status_message = "Entrée ajoutée, cliquez sur Calculer pour mettre à jour les résultats".to_string();
+                    }
+                    Err(e) => self.formulaire_saisie.erreur = Some(e),
+                }
+            }
+
+            if let Some(ref erreur) = self.formulaire_saisie.erreur {
+                ui.colored_label(egui::Color32::from_rgb(220, 80, 60), erreur);
+            }
+        });
+    }
+}
@@ -0,0 +1,138 @@
+use eframe::egui;
+
+use crate::detection::{classer_ligne, TypeLigne};
+use crate::GestionTempsApp;
+
+/// Nombre maximum de lignes affichées dans l'aperçu du classement.
+const LIGNES_APERCU: usize = 200;
+
+/// Lignes d'un collage mixte réparties entre les deux parsers.
+#[derive(Clone, Debug, Default)]
+pub struct Repartition {
+    pub hors_clientele: String,
+    pub clientele: String,
+    /// Numéro de ligne (à partir de 1), type détecté et texte de chaque ligne non vide
+    pub lignes: Vec<(usize, TypeLigne, String)>,
+}
+
+impl Repartition {
+    pub fn nombre(&self, type_ligne: TypeLigne) -> usize {
+        self.lignes.iter().filter(|(_, t, _)| *t == type_ligne).count()
+    }
+
+    pub fn lignes_non_reconnues(&self) -> impl Iterator<Item = &(usize, TypeLigne, String)> {
+        self.lignes.iter().filter(|(_, t, _)| *t == TypeLigne::Invalide)
+    }
+}
+
+/// Classe chaque ligne d'un collage combiné et la range dans la bonne zone.
+pub fn repartir_lignes(donnees: &str) -> Repartition {
+    let mut repartition = Repartition::default();
+    let mut hors_clientele = Vec::new();
+    let mut clientele = Vec::new();
+
+    for (numero_ligne, ligne) in donnees.lines().enumerate() {
+        if ligne.trim().is_empty() {
+            continue;
+        }
+
+        let type_ligne = classer_ligne(ligne);
+        match type_ligne {
+            TypeLigne::HorsClientele => hors_clientele.push(ligne),
+            TypeLigne::Clientele => clientele.push(ligne),
+            TypeLigne::EnTete | TypeLigne::Invalide => {}
+        }
+        repartition.lignes.push((numero_ligne + 1, type_ligne, ligne.to_string()));
+    }
+
+    repartition.hors_clientele = hors_clientele.join("\n");
+    repartition.clientele = clientele.join("\n");
+    repartition
+}
+
+pub fn libelle_type(type_ligne: TypeLigne) -> &'static str {
+    match type_ligne {
+        TypeLigne::HorsClientele => "Hors clientèle",
+        TypeLigne::Clientele => "Clientèle",
+        TypeLigne::EnTete => "En-tête",
+        TypeLigne::Invalide => "Non reconnue",
+    }
+}
+
+pub fn couleur_type(type_ligne: TypeLigne) -> egui::Color32 {
+    match type_ligne {
+        TypeLigne::HorsClientele => egui::Color32::from_rgb(70, 130, 180),
+        TypeLigne::Clientele => egui::Color32::from_rgb(60, 160, 90),
+        TypeLigne::EnTete => egui::Color32::GRAY,
+        TypeLigne::Invalide => egui::Color32::from_rgb(220, 80, 60),
+    }
+}
+
+impl GestionTempsApp {
+    /// Remplit les deux zones à partir du collage combiné.
+    pub fn repartir_saisie_unifiee(&mut self) -> Repartition {
+        let repartition = repartir_lignes(&self.donnees_melangees);
+        self.donnees_hors_clientele = repartition.hors_clientele.clone();
+        self.donnees_clientele = repartition.clientele.clone();
+        repartition
+    }
+
+    pub fn calculer_saisie_unifiee(&mut self) {
+        let repartition = self.repartir_saisie_unifiee();
+
+        if self.entrees_obsoletes() {
+            self.analyser_saisie();
+            for (numero, _, ligne) in repartition.lignes_non_reconnues() {
+                self.signaler(format!("Ligne {} non reconnue: {}", numero, ligne));
+            }
+        }
+        self.calculer_resultats();
+    }
+
+    pub(crate) fn afficher_saisie_unifiee(&mut self, ui: &mut egui::Ui) -> egui::Rect {
+        let zone = ui.group(|ui| {
+            ui.label("📋 Données combinées (hors clientèle et clientèle):");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.donnees_melangees)
+                    .id_source("donnees_melangees")
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(12)
+                    .hint_text("Collez ici les lignes ABS et les lignes d'intervention, dans n'importe quel ordre..."),
+            );
+            ui.small("Chaque ligne est classée automatiquement avant le calcul");
+        });
+
+        if !self.donnees_melangees.trim().is_empty() {
+            // Le classement n'est refait que si le collage a changé depuis l'image précédente
+            if !matches!(self.apercu_saisie_unifiee, Some((ref texte, _)) if *texte == self.donnees_melangees) {
+                self.apercu_saisie_unifiee = None;
+            }
+            let (_, repartition) = self
+                .apercu_saisie_unifiee
+                .get_or_insert_with(|| (self.donnees_melangees.clone(), repartir_lignes(&self.donnees_melangees)));
+            ui.collapsing(
+                format!(
+                    "🔎 Aperçu du classement ({} hors clientèle, {} clientèle, {} non reconnue(s))",
+                    repartition.nombre(TypeLigne::HorsClientele),
+                    repartition.nombre(TypeLigne::Clientele),
+                    repartition.nombre(TypeLigne::Invalide)
+                ),
+                |ui| {
+                    egui::ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                        egui::Grid::new("apercu_classement").striped(true).show(ui, |ui| {
+                            for (numero, type_ligne, ligne) in repartition.lignes.iter().take(LIGNES_APERCU) {
+                                ui.label(numero.to_string());
+                                ui.colored_label(couleur_type(*type_ligne), libelle_type(*type_ligne));
+                                let apercu: Vec<&str> = ligne.split('\t').take(4).collect();
+                                ui.colored_label(couleur_type(*type_ligne), apercu.join(" | "));
+                                ui.end_row();
+                            }
+                        });
+                    });
+                },
+            );
+        }
+
+        zone.response.rect
+    }
+}
@@ -0,0 +1,333 @@
+use eframe::egui;
+use chrono::{DateTime, Local};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+use crate::fichier::{lire_fichier_export, EXTENSIONS_ACCEPTEES};
+use crate::saisie_unifiee::repartir_lignes;
+use crate::GestionTempsApp;
+
+/// Écart de total en dessous duquel aucune notification n'est affichée.
+const TOLERANCE_TOTAUX: f64 = 0.005;
+
+/// Délai sans événement ni changement de taille avant de relire un dossier modifié,
+/// pour ne pas calculer un export en cours d'écriture.
+pub const DELAI_STABILITE: Duration = Duration::from_secs(1);
+
+/// Exports lus dans un dossier, lignes réparties entre les deux zones.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContenuDossier {
+    pub hors_clientele: String,
+    pub clientele: String,
+    /// Noms des fichiers lus, dans l'ordre alphabétique
+    pub fichiers: Vec<String>,
+    pub problemes: Vec<String>,
+}
+
+fn est_export(chemin: &Path) -> bool {
+    chemin.is_file()
+        && chemin
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| EXTENSIONS_ACCEPTEES.contains(&e.as_str()))
+}
+
+/// Lit tous les exports du dossier (sans les sous-dossiers). Chaque ligne est rangée
+/// dans la zone détectée, un fichier pouvant mélanger les deux types de lignes.
+pub fn lire_dossier(dossier: &Path) -> std::io::Result<ContenuDossier> {
+    let mut chemins: Vec<PathBuf> = std::fs::read_dir(dossier)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|chemin| est_export(chemin))
+        .collect();
+    chemins.sort();
+
+    let mut contenu = ContenuDossier::default();
+    let mut hors_clientele = Vec::new();
+    let mut clientele = Vec::new();
+    for chemin in chemins {
+        let nom = chemin.file_name().unwrap_or_default().to_string_lossy().to_string();
+        match lire_fichier_export(&chemin) {
+            Ok(fichier) => {
+                let repartition = repartir_lignes(&fichier.texte);
+                hors_clientele.extend(repartition.hors_clientele.lines().map(str::to_string));
+                clientele.extend(repartition.clientele.lines().map(str::to_string));
+                contenu.fichiers.push(nom);
+            }
+            Err(e) => contenu.problemes.push(format!("{}: {}", nom, e)),
+        }
+    }
+    contenu.hors_clientele = hors_clientele.join("\n");
+    contenu.clientele = clientele.join("\n");
+    Ok(contenu)
+}
+
+/// Nom et taille de chaque export du dossier.
+fn tailles_exports(dossier: &Path) -> Vec<(String, u64)> {
+    let mut tailles: Vec<(String, u64)> = std::fs::read_dir(dossier)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|chemin| est_export(chemin))
+        .map(|chemin| {
+            let taille = chemin.metadata().map(|m| m.len()).unwrap_or(0);
+            (chemin.file_name().unwrap_or_default().to_string_lossy().to_string(), taille)
+        })
+        .collect();
+    tailles.sort();
+    tailles
+}
+
+/// Dossier surveillé et événements du système de fichiers en attente.
+pub struct Surveillance {
+    pub dossier: PathBuf,
+    pub fichiers: Vec<String>,
+    /// Exports modifiés pas encore recalculés, en attente de la fin des écritures
+    pub en_attente: Vec<String>,
+    dernier_evenement: Option<Instant>,
+    /// Tailles des exports au dernier événement
+    tailles: Vec<(String, u64)>,
+    evenements: Receiver<notify::Result<Event>>,
+    // Conservé pour que la surveillance reste active
+    _observateur: RecommendedWatcher,
+}
+
+impl Surveillance {
+    pub fn demarrer(dossier: &Path) -> notify::Result<Self> {
+        let (emetteur, evenements) = channel();
+        let mut observateur = notify::recommended_watcher(move |evenement| {
+            let _ = emetteur.send(evenement);
+        })?;
+        observateur.watch(dossier, RecursiveMode::NonRecursive)?;
+
+        Ok(Surveillance {
+            dossier: dossier.to_path_buf(),
+            fichiers: Vec::new(),
+            en_attente: Vec::new(),
+            dernier_evenement: None,
+            tailles: Vec::new(),
+            evenements,
+            _observateur: observateur,
+        })
+    }
+
+    /// Noms des exports créés, modifiés ou supprimés depuis le dernier appel.
+    pub fn fichiers_modifies(&self) -> Vec<String> {
+        let mut noms = Vec::new();
+        for evenement in self.evenements.try_iter().flatten() {
+            if !matches!(evenement.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                continue;
+            }
+            for chemin in evenement.paths {
+                let extension = chemin.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+                let nom = chemin.file_name().unwrap_or_default().to_string_lossy().to_string();
+                if EXTENSIONS_ACCEPTEES.contains(&extension.as_str()) && !noms.contains(&nom) {
+                    noms.push(nom);
+                }
+            }
+        }
+        noms
+    }
+
+    /// Exports modifiés, rendus une fois le dossier stable : aucun événement depuis
+    /// `DELAI_STABILITE` et des tailles inchangées depuis le dernier événement.
+    pub fn fichiers_stables(&mut self) -> Vec<String> {
+        let modifies = self.fichiers_modifies();
+        if !modifies.is_empty() {
+            for nom in modifies {
+                if !self.en_attente.contains(&nom) {
+                    self.en_attente.push(nom);
+                }
+            }
+            self.dernier_evenement = Some(Instant::now());
+            self.tailles = tailles_exports(&self.dossier);
+            return Vec::new();
+        }
+
+        if self.dernier_evenement.is_none_or(|dernier| dernier.elapsed() < DELAI_STABILITE) {
+            return Vec::new();
+        }
+        let tailles = tailles_exports(&self.dossier);
+        if tailles != self.tailles {
+            // Écriture sans événement : on attend un nouveau délai
+            self.tailles = tailles;
+            self.dernier_evenement = Some(Instant::now());
+            return Vec::new();
+        }
+
+        self.dernier_evenement = None;
+        std::mem::take(&mut self.en_attente)
+    }
+}
+
+/// Changement de totaux après un nouveau calcul automatique.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotificationTotaux {
+    pub le: DateTime<Local>,
+    /// Total avant le recalcul, absent s'il n'y avait pas encore de résultat
+    pub ancien_total: Option<f64>,
+    pub nouveau_total: f64,
+    /// Fichiers à l'origine du recalcul
+    pub fichiers: Vec<String>,
+}
+
+impl NotificationTotaux {
+    pub fn message(&self) -> String {
+        let total = match self.ancien_total {
+            Some(ancien) => format!(
+                "{:.2}h → {:.2}h ({:+.2}h)",
+                ancien,
+                self.nouveau_total,
+                self.nouveau_total - ancien
+            ),
+            None => format!("{:.2}h", self.nouveau_total),
+        };
+        format!("{} Total: {} après modification de {}", self.le.format("%H:%M:%S"), total, self.fichiers.join(", "))
+    }
+}
+
+impl GestionTempsApp {
+    fn totaux_actuels(&self) -> Option<[f64; 3]> {
+        self.resultat.as_ref().map(|r| [r.hors_clientele, r.travail_clientele, r.deplacement])
+    }
+
+    /// Surveille `dossier` : ses exports remplacent les deux zones et sont recalculés à chaque modification.
+    /// La surveillance se fait en saisie libre : l'employé sélectionné est d'abord rangé dans son
+    /// dossier, pour que les recalculs n'écrasent pas ses données.
+    pub fn demarrer_surveillance(&mut self, dossier: &Path) {
+        match Surveillance::demarrer(dossier) {
+            Ok(surveillance) => {
+                self.surveillance = Some(surveillance);
+                self.deselectionner_employe();
+                self.recalculer_dossier_surveille(Vec::new());
+            }
+            Err(e) => self.status_message = format!("Impossible de surveiller {}: {}", dossier.display(), e),
+        }
+    }
+
+    pub fn arreter_surveillance(&mut self) {
+        if let Some(surveillance) = self.surveillance.take() {
+            self.status_message = format!("Surveillance de {} arrêtée", surveillance.dossier.display());
+        }
+    }
+
+    /// Traite les événements en attente ; vrai si les résultats ont été recalculés.
+    /// La surveillance s'arrête si un employé est sélectionné entre-temps.
+    pub fn verifier_surveillance(&mut self) -> bool {
+        if self.surveillance.is_some() && self.employe_actif.is_some() {
+            self.arreter_surveillance();
+            self.status_message.push_str(" : un employé a été sélectionné");
+            return false;
+        }
+        let Some(ref mut surveillance) = self.surveillance else {
+            return false;
+        };
+        let fichiers = surveillance.fichiers_stables();
+        !fichiers.is_empty() && self.recalculer_dossier_surveille(fichiers)
+    }
+
+    /// Relit le dossier surveillé et recalcule si son contenu a changé.
+    fn recalculer_dossier_surveille(&mut self, fichiers_modifies: Vec<String>) -> bool {
+        let Some(ref mut surveillance) = self.surveillance else {
+            return false;
+        };
+        let contenu = match lire_dossier(&surveillance.dossier) {
+            Ok(contenu) => contenu,
+            Err(e) => {
+                self.status_message = format!("Lecture de {} impossible: {}", surveillance.dossier.display(), e);
+                return false;
+            }
+        };
+        surveillance.fichiers = contenu.fichiers;
+
+        let resultat_a_jour = self.resultat.is_some()
+            && contenu.hors_clientele == self.donnees_hors_clientele
+            && contenu.clientele == self.donnees_clientele;
+        if resultat_a_jour {
+            return false;
+        }
+
+        let avant = self.totaux_actuels();
+        self.donnees_hors_clientele = contenu.hors_clientele;
+        self.donnees_clientele = contenu.clientele;
+        self.calculer_resultats();
+        for probleme in contenu.problemes {
+            self.signaler(format!("Fichier surveillé non lu ({})", probleme));
+        }
+        self.show_result = true;
+
+        let apres = self.totaux_actuels().unwrap_or_default();
+        let change = avant.is_none_or(|avant| avant.iter().zip(apres).any(|(a, b)| (a - b).abs() > TOLERANCE_TOTAUX));
+        if change && !fichiers_modifies.is_empty() {
+            self.notification_surveillance = Some(NotificationTotaux {
+                le: Local::now(),
+                ancien_total: avant.map(|t| t.iter().sum()),
+                nouveau_total: apres.iter().sum(),
+                fichiers: fichiers_modifies,
+            });
+        }
+        true
+    }
+
+    pub(crate) fn afficher_surveillance(&mut self, ui: &mut egui::Ui) {
+        let titre = match self.surveillance {
+            Some(ref surveillance) => format!("👁 Surveillance de {}", surveillance.dossier.display()),
+            None => "👁 Surveillance d'un dossier".to_string(),
+        };
+
+        ui.collapsing(titre, |ui| {
+            let mut demarrer = false;
+            let mut arreter = false;
+
+            ui.horizontal(|ui| {
+                ui.label("Dossier:");
+                ui.add_enabled(self.surveillance.is_none(), egui::TextEdit::singleline(&mut self.dossier_surveillance));
+                if self.surveillance.is_none() {
+                    if ui.button("📂").clicked() {
+                        if let Some(dossier) = rfd::FileDialog::new().pick_folder() {
+                            self.dossier_surveillance = dossier.display().to_string();
+                        }
+                    }
+                    demarrer = ui.button("▶ Surveiller").clicked() && !self.dossier_surveillance.trim().is_empty();
+                } else {
+                    arreter = ui.button("⏹ Arrêter").clicked();
+                }
+            });
+            ui.small("Les exports du dossier remplacent les deux zones et sont recalculés une fois chaque ajout ou modification terminé");
+            ui.small("La surveillance se fait en saisie libre : sélectionner un employé l'arrête");
+
+            if let Some(ref surveillance) = self.surveillance {
+                ui.label(format!("{} fichier(s) lu(s)", surveillance.fichiers.len()))
+                    .on_hover_text(surveillance.fichiers.join("\n"));
+            }
+
+            if demarrer {
+                let dossier = PathBuf::from(self.dossier_surveillance.trim());
+                self.demarrer_surveillance(&dossier);
+            }
+            if arreter {
+                self.arreter_surveillance();
+            }
+        });
+    }
+
+    pub(crate) fn afficher_notification_surveillance(&mut self, ctx: &egui::Context) {
+        let Some(ref notification) = self.notification_surveillance else {
+            return;
+        };
+
+        let mut fermer = false;
+        egui::Window::new("🔔 Totaux mis à jour")
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(notification.message());
+                fermer = ui.button("OK").clicked();
+            });
+        if fermer {
+            self.notification_surveillance = None;
+        }
+    }
+}
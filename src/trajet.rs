@@ -0,0 +1,231 @@
+use eframe::egui;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::entree::date_heure_de;
+use crate::{EntreeSaisie, GestionTempsApp, TypeEntree};
+
+/// Traitement du temps de trajet en paie, par journée :
+/// - la franchise correspond au trajet domicile-travail habituel et n'est pas comptée ;
+/// - au-delà, le trajet effectué pendant l'horaire de travail est compté comme travail ;
+/// - le trajet effectué en dehors de l'horaire donne lieu à une contrepartie au taux indiqué.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReglesTrajet {
+    pub actif: bool,
+    /// Heures de trajet quotidien non comptées (ex: 0.5 pour 30 minutes)
+    pub franchise_quotidienne: f64,
+    /// Contrepartie accordée pour une heure de trajet hors horaire (ex: 0.5 pour 50 %)
+    pub taux_compensation: f64,
+    pub debut_journee: NaiveTime,
+    pub fin_journee: NaiveTime,
+}
+
+impl Default for ReglesTrajet {
+    fn default() -> Self {
+        ReglesTrajet {
+            actif: false,
+            franchise_quotidienne: 0.5,
+            taux_compensation: 0.5,
+            debut_journee: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            fin_journee: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        }
+    }
+}
+
+/// Déplacement réparti selon les règles de trajet, en heures.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RepartitionTrajet {
+    /// Compté comme temps de travail
+    pub compte: f64,
+    /// Franchise du trajet habituel
+    pub non_compte: f64,
+    /// Trajet hors horaire, non compté comme travail mais compensé
+    pub compense: f64,
+    /// Contrepartie due pour le trajet compensé
+    pub contrepartie: f64,
+}
+
+impl RepartitionTrajet {
+    pub fn total(&self) -> f64 {
+        self.compte + self.non_compte + self.compense
+    }
+
+    fn ajouter(&mut self, autre: &RepartitionTrajet) {
+        self.compte += autre.compte;
+        self.non_compte += autre.non_compte;
+        self.compense += autre.compense;
+        self.contrepartie += autre.contrepartie;
+    }
+}
+
+/// Minutes de la plage `debut` - `fin` situées en dehors de l'horaire de travail.
+fn minutes_hors_horaire(debut: NaiveDateTime, fin: NaiveDateTime, regles: &ReglesTrajet) -> i64 {
+    let mut hors_horaire = 0;
+    let mut jour = debut.date();
+
+    while jour <= fin.date() {
+        let ouverture = jour.and_time(regles.debut_journee);
+        let fermeture = jour.and_time(regles.fin_journee);
+        let debut_jour = debut.max(jour.and_hms_opt(0, 0, 0).unwrap());
+        let fin_jour = fin.min(jour.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap());
+
+        if fin_jour > debut_jour {
+            let total = (fin_jour - debut_jour).num_minutes();
+            let pendant = (fin_jour.min(fermeture) - debut_jour.max(ouverture)).num_minutes().max(0);
+            hors_horaire += total - pendant;
+        }
+        jour = jour.succ_opt().unwrap();
+    }
+
+    hors_horaire
+}
+
+/// Part du trajet déclaré effectuée hors horaire. Seul le trajet aller a des horaires dans
+/// l'export : ses minutes hors horaire sont comptées, et le reste du déplacement déclaré
+/// (le retour) est supposé effectué pendant l'horaire.
+fn trajet_hors_horaire(entree: &EntreeSaisie, regles: &ReglesTrajet) -> f64 {
+    match (date_heure_de(&entree.debut_trajet), date_heure_de(&entree.fin_trajet)) {
+        (Some(debut), Some(fin)) if fin > debut => {
+            (minutes_hors_horaire(debut, fin, regles) as f64 / 60.0).min(entree.deplacement)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Répartit le déplacement des interventions, jour par jour.
+pub fn repartir_trajets(entrees: &[&EntreeSaisie], regles: &ReglesTrajet) -> BTreeMap<NaiveDate, RepartitionTrajet> {
+    // Trajet total et part hors horaire par jour
+    let mut par_jour: BTreeMap<NaiveDate, (f64, f64)> = BTreeMap::new();
+    for entree in entrees.iter().filter(|e| e.type_entree == TypeEntree::Clientele && e.deplacement > 0.0) {
+        if let Some(date) = entree.date {
+            let jour = par_jour.entry(date).or_insert((0.0, 0.0));
+            jour.0 += entree.deplacement;
+            jour.1 += trajet_hors_horaire(entree, regles);
+        }
+    }
+
+    par_jour
+        .into_iter()
+        .map(|(date, (total, hors_horaire))| {
+            // La franchise s'impute d'abord sur le trajet hors horaire (domicile-travail)
+            let non_compte = total.min(regles.franchise_quotidienne.max(0.0));
+            let compense = (hors_horaire - non_compte).max(0.0);
+            let compte = total - non_compte - compense;
+
+            let repartition = RepartitionTrajet {
+                compte,
+                non_compte,
+                compense,
+                contrepartie: compense * regles.taux_compensation,
+            };
+            (date, repartition)
+        })
+        .collect()
+}
+
+/// Part comptée comme travail du déplacement d'une intervention : la part comptée de sa journée,
+/// au prorata de son trajet. Sans répartition (règles inactives) ou sans date, tout est compté.
+pub fn deplacement_compte(entree: &EntreeSaisie, par_jour: Option<&BTreeMap<NaiveDate, RepartitionTrajet>>) -> f64 {
+    let jour = entree.date.zip(par_jour).and_then(|(date, par_jour)| par_jour.get(&date));
+    match jour {
+        Some(jour) if jour.total() > 0.0 => entree.deplacement * jour.compte / jour.total(),
+        _ => entree.deplacement,
+    }
+}
+
+pub fn total_trajets(par_jour: &BTreeMap<NaiveDate, RepartitionTrajet>) -> RepartitionTrajet {
+    let mut total = RepartitionTrajet::default();
+    for repartition in par_jour.values() {
+        total.ajouter(repartition);
+    }
+    total
+}
+
+impl GestionTempsApp {
+    pub fn repartition_trajets(&self) -> BTreeMap<NaiveDate, RepartitionTrajet> {
+        repartir_trajets(&self.entrees_retenues(), &self.regles_trajet)
+    }
+
+    pub(crate) fn afficher_regles_trajet(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("🛣️ Règles de temps de trajet", |ui| {
+            ui.checkbox(&mut self.regles_trajet.actif, "Appliquer les règles de trajet");
+
+            egui::Grid::new("regles_trajet").num_columns(2).show(ui, |ui| {
+                ui.label("Franchise quotidienne (non comptée):");
+                ui.add(egui::DragValue::new(&mut self.regles_trajet.franchise_quotidienne).speed(0.05).clamp_range(0.0..=4.0).suffix(" h"));
+                ui.end_row();
+
+                ui.label("Contrepartie hors horaire:");
+                let mut pourcentage = self.regles_trajet.taux_compensation * 100.0;
+                if ui.add(egui::DragValue::new(&mut pourcentage).speed(1.0).clamp_range(0.0..=200.0).suffix(" %")).changed() {
+                    self.regles_trajet.taux_compensation = pourcentage / 100.0;
+                }
+                ui.end_row();
+
+                for (libelle, heure) in [
+                    ("Début de journée:", &mut self.regles_trajet.debut_journee),
+                    ("Fin de journée:", &mut self.regles_trajet.fin_journee),
+                ] {
+                    ui.label(libelle);
+                    ui.horizontal(|ui| {
+                        let (mut heures, mut minutes) = (heure.hour(), heure.minute());
+                        let modifie = ui.add(egui::DragValue::new(&mut heures).clamp_range(0..=23).suffix(" h")).changed()
+                            | ui.add(egui::DragValue::new(&mut minutes).clamp_range(0..=59).suffix(" min")).changed();
+                        if modifie {
+                            *heure = NaiveTime::from_hms_opt(heures, minutes, 0).unwrap_or(*heure);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// Répartition par jour si les règles de trajet sont actives.
+    pub(crate) fn repartition_trajets_active(&self) -> Option<BTreeMap<NaiveDate, RepartitionTrajet>> {
+        self.regles_trajet.actif.then(|| self.repartition_trajets())
+    }
+
+    pub(crate) fn afficher_repartition_trajets(&self, ui: &mut egui::Ui) {
+        let Some(par_jour) = self.repartition_trajets_active() else {
+            return;
+        };
+        if par_jour.is_empty() {
+            return;
+        }
+        let total = total_trajets(&par_jour);
+
+        ui.collapsing(
+            format!(
+                "🛣️ Trajets en paie : {:.2}h comptées dans le total, {:.2}h non comptées, {:.2}h compensées ({:.2}h de contrepartie)",
+                total.compte, total.non_compte, total.compense, total.contrepartie
+            ),
+            |ui| {
+                egui::Grid::new("repartition_trajets").striped(true).show(ui, |ui| {
+                    for titre in ["Date", "Trajet", "Compté", "Non compté", "Compensé", "Contrepartie"] {
+                        ui.strong(titre);
+                    }
+                    ui.end_row();
+
+                    let ligne = |ui: &mut egui::Ui, repartition: &RepartitionTrajet| {
+                        ui.label(format!("{:.2}", repartition.total()));
+                        ui.label(format!("{:.2}", repartition.compte));
+                        ui.label(format!("{:.2}", repartition.non_compte));
+                        ui.label(format!("{:.2}", repartition.compense));
+                        ui.label(format!("{:.2}", repartition.contrepartie));
+                        ui.end_row();
+                    };
+
+                    for (date, repartition) in &par_jour {
+                        ui.label(date.format("%d/%m/%Y").to_string());
+                        ligne(ui, repartition);
+                    }
+                    ui.strong("Total");
+                    ligne(ui, &total);
+                });
+            },
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use eframe::egui;
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::entree::date_heure_de;
+use crate::horaire::{heures_prevues, JOURS_SEMAINE};
+use crate::{EntreeSaisie, GestionTempsApp, TypeEntree};
+
+/// Écart toléré, en heures, entre le temps d'une journée et l'horaire prévu.
+pub const TOLERANCE_CHARGE: f64 = 0.25;
+
+/// Charge d'une journée par rapport à l'horaire prévu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charge {
+    /// Rien de prévu ni de saisi
+    Aucune,
+    Sous,
+    Normale,
+    Sur,
+}
+
+impl Charge {
+    pub fn libelle(&self) -> &'static str {
+        match self {
+            Charge::Aucune => "",
+            Charge::Sous => "Sous l'horaire",
+            Charge::Normale => "Conforme à l'horaire",
+            Charge::Sur => "Au-delà de l'horaire",
+        }
+    }
+
+    fn couleur(&self) -> Option<egui::Color32> {
+        match self {
+            Charge::Aucune => None,
+            Charge::Sous => Some(egui::Color32::from_rgb(200, 150, 40)),
+            Charge::Normale => Some(egui::Color32::from_rgb(60, 140, 80)),
+            Charge::Sur => Some(egui::Color32::from_rgb(190, 60, 50)),
+        }
+    }
+}
+
+pub fn charge_du_jour(realise: f64, prevu: f64) -> Charge {
+    if realise <= 0.0 && prevu <= 0.0 {
+        Charge::Aucune
+    } else if realise < prevu - TOLERANCE_CHARGE {
+        Charge::Sous
+    } else if realise > prevu + TOLERANCE_CHARGE {
+        Charge::Sur
+    } else {
+        Charge::Normale
+    }
+}
+
+fn premier_du_mois(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+fn mois_suivant(mois: NaiveDate) -> NaiveDate {
+    premier_du_mois(premier_du_mois(mois) + Duration::days(31))
+}
+
+fn mois_precedent(mois: NaiveDate) -> NaiveDate {
+    premier_du_mois(premier_du_mois(mois) - Duration::days(1))
+}
+
+/// Semaines du mois, du lundi au dimanche ; les jours hors du mois sont vides.
+pub fn semaines_du_mois(mois: NaiveDate) -> Vec<[Option<NaiveDate>; 7]> {
+    let premier = premier_du_mois(mois);
+    let mut semaines = Vec::new();
+    let mut semaine = [None; 7];
+    let mut date = premier;
+
+    while date.month() == premier.month() {
+        let jour = date.weekday().num_days_from_monday() as usize;
+        semaine[jour] = Some(date);
+        if jour == 6 {
+            semaines.push(semaine);
+            semaine = [None; 7];
+        }
+        date += Duration::days(1);
+    }
+    if semaine.iter().any(Option::is_some) {
+        semaines.push(semaine);
+    }
+
+    semaines
+}
+
+/// Heure "HH:MM" d'un horodatage "DD/MM/YYYY HH:MM", ou le texte tel quel.
+fn heure(horodatage: &str) -> &str {
+    horodatage.trim().split_once(' ').map_or(horodatage, |(_, heure)| heure)
+}
+
+/// Mois affiché dans la grille et jour dont les entrées sont détaillées.
+#[derive(Clone, Debug, Default)]
+pub struct VueMois {
+    /// Premier jour du mois affiché ; à défaut, le mois du dernier jour saisi
+    pub mois: Option<NaiveDate>,
+    pub jour_selectionne: Option<NaiveDate>,
+}
+
+impl GestionTempsApp {
+    /// Mois affiché dans la grille, par défaut celui du dernier jour travaillé.
+    pub fn mois_affiche(&self) -> Option<NaiveDate> {
+        self.vue_mois
+            .mois
+            .or_else(|| self.temps_par_jour.keys().max().copied())
+            .map(premier_du_mois)
+    }
+
+    /// Temps saisi (travail et absences) et heures prévues pour une journée.
+    pub fn realise_et_prevu(&self, date: NaiveDate) -> (f64, f64) {
+        let realise = self.temps_realise(date);
+        let prevu = heures_prevues(self.horaire_actif(), &self.calendrier.type_jour(date), date);
+        (realise, prevu)
+    }
+
+    /// Charge de la journée ; un jour sans aucune saisie n'est pas évalué.
+    pub fn charge(&self, date: NaiveDate) -> Charge {
+        match self.realise_et_prevu(date) {
+            (realise, _) if realise <= 0.0 => Charge::Aucune,
+            (realise, prevu) => charge_du_jour(realise, prevu),
+        }
+    }
+
+    /// Entrées retenues d'une journée, dans l'ordre de début.
+    pub fn entrees_du_jour(&self, date: NaiveDate) -> Vec<&EntreeSaisie> {
+        let mut entrees: Vec<&EntreeSaisie> = self.entrees_retenues().into_iter().filter(|e| e.date == Some(date)).collect();
+        entrees.sort_by_key(|e| date_heure_de(&e.debut));
+        entrees
+    }
+
+    fn categorie_entree(&self, entree: &EntreeSaisie) -> String {
+        match entree.type_entree {
+            TypeEntree::HorsClientele => self
+                .regles_absences
+                .type_absence(&entree.code, &entree.description)
+                .unwrap_or_else(|| self.regles_categories.categoriser(&entree.code, &entree.description)),
+            TypeEntree::Clientele => format!("Client {}", entree.client),
+        }
+    }
+
+    pub(crate) fn afficher_vue_mois(&mut self, ui: &mut egui::Ui) {
+        let Some(mois) = self.mois_affiche() else {
+            return;
+        };
+
+        ui.collapsing("🗓 Calendrier du mois", |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("◀").clicked() {
+                    self.vue_mois.mois = Some(mois_precedent(mois));
+                }
+                ui.strong(mois.format("%m/%Y").to_string());
+                if ui.button("▶").clicked() {
+                    self.vue_mois.mois = Some(mois_suivant(mois));
+                }
+                for charge in [Charge::Sous, Charge::Normale, Charge::Sur] {
+                    ui.colored_label(charge.couleur().unwrap(), format!("■ {}", charge.libelle()));
+                }
+            });
+
+            egui::Grid::new("grille_mois").spacing([4.0, 4.0]).show(ui, |ui| {
+                for jour in JOURS_SEMAINE {
+                    ui.strong(&jour[..3]);
+                }
+                ui.end_row();
+
+                for semaine in semaines_du_mois(mois) {
+                    for date in semaine {
+                        let Some(date) = date else {
+                            ui.label("");
+                            continue;
+                        };
+
+                        let (realise, prevu) = self.realise_et_prevu(date);
+                        let charge = self.charge(date);
+                        let texte = if realise > 0.0 {
+                            format!("{}\n{:.2}h", date.day(), realise)
+                        } else {
+                            format!("{}\n", date.day())
+                        };
+
+                        let mut bouton = egui::Button::new(texte)
+                            .min_size(egui::vec2(64.0, 40.0))
+                            .selected(self.vue_mois.jour_selectionne == Some(date));
+                        if let Some(couleur) = charge.couleur() {
+                            bouton = bouton.fill(couleur);
+                        }
+
+                        let mut infobulle = format!("Prévu : {:.2}h", prevu);
+                        let type_jour = self.calendrier.type_jour(date);
+                        if !type_jour.est_ouvre() {
+                            infobulle = format!("{}\n{}", type_jour.libelle(), infobulle);
+                        }
+                        if ui.add(bouton).on_hover_text(infobulle).clicked() {
+                            self.vue_mois.jour_selectionne = if self.vue_mois.jour_selectionne == Some(date) { None } else { Some(date) };
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if let Some(date) = self.vue_mois.jour_selectionne {
+                self.afficher_entrees_du_jour(ui, date);
+            }
+        });
+    }
+
+    fn afficher_entrees_du_jour(&self, ui: &mut egui::Ui, date: NaiveDate) {
+        ui.add_space(5.0);
+        let (realise, prevu) = self.realise_et_prevu(date);
+        ui.strong(format!("{} : {:.2}h pour {:.2}h prévues", date.format("%d/%m/%Y"), realise, prevu));
+
+        let entrees = self.entrees_du_jour(date);
+        if entrees.is_empty() {
+            ui.label("Aucune entrée ce jour-là");
+            return;
+        }
+
+        egui::Grid::new("entrees_du_jour").striped(true).show(ui, |ui| {
+            for titre in ["Début", "Fin", "Catégorie", "Description", "Durée", "Trajet"] {
+                ui.strong(titre);
+            }
+            ui.end_row();
+
+            for entree in entrees {
+                ui.label(heure(&entree.debut));
+                ui.label(heure(&entree.fin));
+                ui.label(self.categorie_entree(entree));
+                ui.label(&entree.description);
+                ui.label(format!("{:.2}h", entree.duree));
+                ui.label(if entree.deplacement > 0.0 { format!("{:.2}h", entree.deplacement) } else { String::new() });
+                ui.end_row();
+            }
+        });
+    }
+}
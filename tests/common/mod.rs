@@ -0,0 +1,17 @@
+/// Ligne d'intervention de l'export clientèle (16 colonnes tabulées).
+/// `trajet` et `intervention` sont les horaires de début et de fin, "-" s'ils ne sont pas renseignés.
+pub fn ligne_clientele(
+    wo: &str,
+    client: &str,
+    type_intervention: &str,
+    trajet: (&str, &str),
+    intervention: (&str, &str),
+    travail: &str,
+    deplacement: &str,
+) -> String {
+    [
+        wo, client, type_intervention, "Clôturé", trajet.0, trajet.1, "-", "-", intervention.0, intervention.1,
+        "-", "-", "-", "-", travail, deplacement,
+    ]
+    .join("\t")
+}
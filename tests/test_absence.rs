@@ -0,0 +1,75 @@
+use chrono::NaiveDate;
+use gestion_temps::absence::decompter_jours;
+use gestion_temps::{GestionTempsApp, ReglesAbsences};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+
+const ABSENCES: &str = "ABS--400\tCongés payés\t16/06/2025 08:00\t16/06/2025 15:00
+ABS--401\tRTT matin\t17/06/2025 08:00\t17/06/2025 11:30
+ABS--402\tArrêt maladie\t18/06/2025 08:00\t18/06/2025 15:00
+ABS--403\tArrêt maladie\t19/06/2025 08:00\t19/06/2025 15:00";
+
+fn date(jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 6, jour).unwrap()
+}
+
+#[cfg(test)]
+mod absence_tests {
+    use super::*;
+
+    #[test]
+    fn test_type_absence() {
+        let regles = ReglesAbsences::default();
+        assert_eq!(regles.type_absence("ABS--1", "Congés payés").as_deref(), Some("Congés payés"));
+        assert_eq!(regles.type_absence("ABS--1", "RTT").as_deref(), Some("RTT"));
+        assert_eq!(regles.type_absence("ABS--1", "Récupération heures").as_deref(), Some("Récupération"));
+        assert_eq!(regles.type_absence("ABS--1", "Formation sécurité"), None);
+    }
+
+    #[test]
+    fn test_decompter_jours() {
+        assert_eq!(decompter_jours(7.0, 7.0), 1.0);
+        assert_eq!(decompter_jours(3.5, 7.0), 0.5);
+        assert_eq!(decompter_jours(2.0, 7.0), 0.5);
+        // Moins d'un quart de journée ne fait pas une demi-journée
+        assert_eq!(decompter_jours(1.0, 7.0), 0.0);
+        assert_eq!(decompter_jours(9.0, 7.0), 1.0);
+        assert_eq!(decompter_jours(7.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_absences_exclues_du_temps_travaille() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: format!("{}\n{}", HORS_CLIENTELE, ABSENCES),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.hors_clientele - 12.5).abs() < 0.01);
+        assert!(resultat.details_hors_clientele.iter().all(|d| !d.description.contains("maladie")));
+        assert!(!app.temps_par_jour.contains_key(&date(16)));
+
+        let par_type = resultat.absences_par_type();
+        assert_eq!(par_type["Congés payés"], (7.0, 1.0));
+        assert_eq!(par_type["RTT"], (3.5, 0.5));
+        assert_eq!(par_type["Maladie"], (14.0, 2.0));
+
+        assert_eq!(resultat.absences_du_jour(date(17)).count(), 1);
+    }
+
+    #[test]
+    fn test_regles_absences_configurables() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: "ABS--900\tFormation sécurité\t16/06/2025 08:00\t16/06/2025 15:00".to_string(),
+            texte_regles_absences: "Absence autorisée;;ABS--9".to_string(),
+            ..Default::default()
+        };
+        app.appliquer_regles_absences();
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert_eq!(resultat.hors_clientele, 0.0);
+        assert_eq!(resultat.absences[0].type_absence, "Absence autorisée");
+    }
+}
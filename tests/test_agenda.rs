@@ -0,0 +1,152 @@
+use gestion_temps::agenda::{evenements_des_entrees, lire_ics, vers_ics, CODE_AGENDA};
+use gestion_temps::{GestionTempsApp, ReglesCategories};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+const AGENDA: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:1@agenda\r
+DTSTART;TZID=Europe/Paris:20250616T090000\r
+DTEND;TZID=Europe/Paris:20250616T103000\r
+SUMMARY:Réunion\\, planning de la\r
+  semaine\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:2@agenda\r
+DTSTART;VALUE=DATE:20250617\r
+DTEND;VALUE=DATE:20250618\r
+SUMMARY:Salon professionnel\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:3@agenda\r
+DTSTART:20250617T140000\r
+DTEND:20250617T170000\r
+SUMMARY:Formation habilitation\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[cfg(test)]
+mod agenda_tests {
+    use super::*;
+
+    fn app_analysee() -> GestionTempsApp {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            donnees_clientele: CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.analyser_saisie();
+        app
+    }
+
+    #[test]
+    fn test_export_des_entrees() {
+        let app = app_analysee();
+        let entrees = app.entrees_retenues();
+        let evenements = evenements_des_entrees(&entrees, &ReglesCategories::default());
+
+        let formation = evenements.iter().find(|e| e.resume == "Formation sécurité").unwrap();
+        assert_eq!(formation.categorie, "Formation");
+        assert_eq!(formation.debut.format("%d/%m/%Y %H:%M").to_string(), "10/06/2025 13:00");
+
+        // Trajet puis intervention pour la première ligne clientèle
+        let trajet = evenements.iter().find(|e| e.type_entree == "trajet").unwrap();
+        assert_eq!(trajet.debut.format("%d/%m/%Y %H:%M").to_string(), "10/06/2025 08:48");
+        assert!(evenements.iter().any(|e| e.type_entree == "intervention" && e.debut == trajet.fin));
+
+        let ics = vers_ics(&evenements);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), evenements.len());
+        assert!(ics.lines().all(|ligne| ligne.len() <= 75));
+    }
+
+    #[test]
+    fn test_relecture_de_l_export() {
+        let app = app_analysee();
+        let evenements = evenements_des_entrees(&app.entrees_retenues(), &ReglesCategories::default());
+
+        let (relus, avertissements) = lire_ics(&vers_ics(&evenements));
+        assert!(avertissements.is_empty());
+        assert_eq!(relus, evenements);
+    }
+
+    #[test]
+    fn test_lecture_d_un_agenda() {
+        let (evenements, avertissements) = lire_ics(AGENDA);
+
+        assert_eq!(evenements.len(), 2);
+        assert_eq!(evenements[0].resume, "Réunion, planning de la semaine");
+        assert_eq!(
+            evenements[0].vers_ligne_hors_clientele(),
+            "AGENDA\tRéunion, planning de la semaine\t16/06/2025 09:00\t16/06/2025 10:30"
+        );
+        // L'événement sur la journée entière est écarté
+        assert_eq!(avertissements.len(), 1);
+        assert!(avertissements[0].contains("Salon professionnel"));
+    }
+
+    #[test]
+    fn test_import_dans_la_zone_hors_clientele() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_agenda_{}.ics", std::process::id()));
+        std::fs::write(&chemin, AGENDA).unwrap();
+
+        // Les interventions réexportées ne reviennent pas en hors clientèle
+        let mut export = app_analysee();
+        let chemin_export = std::env::temp_dir().join(format!("gestion_temps_export_{}.ics", std::process::id()));
+        export.exporter_agenda(&chemin_export);
+
+        let mut app = GestionTempsApp::default();
+        app.importer_agenda(&chemin);
+        app.importer_agenda(&chemin_export);
+        std::fs::remove_file(&chemin).ok();
+        std::fs::remove_file(&chemin_export).ok();
+
+        let lignes: Vec<&str> = app.donnees_hors_clientele.lines().collect();
+        let hors_clientele_exportees = HORS_CLIENTELE.lines().filter(|l| !l.contains("Pause déjeuner") && !l.trim().is_empty()).count();
+        assert_eq!(lignes.len(), 2 + hors_clientele_exportees);
+
+        app.calculer_resultats();
+        let resultat = app.resultat.unwrap();
+        assert!(resultat.details_hors_clientele.iter().any(|d| d.description == "Formation habilitation" && d.categorie == "Formation"));
+    }
+
+    #[test]
+    fn test_reimport_codes_conserves_sans_doublon() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_reimport_{}.ics", std::process::id()));
+        let mut export = app_analysee();
+        export.exporter_agenda(&chemin);
+
+        // Dans une zone vide, chaque activité retrouve son code ABS
+        let mut app = GestionTempsApp::default();
+        app.importer_agenda(&chemin);
+        let attendues: Vec<&str> = HORS_CLIENTELE.lines().filter(|l| !l.contains("Pause déjeuner") && !l.trim().is_empty()).collect();
+        let lignes: Vec<&str> = app.donnees_hors_clientele.lines().collect();
+        assert_eq!(lignes.len(), attendues.len());
+        assert!(lignes.iter().all(|l| attendues.iter().any(|a| a.trim() == *l)));
+
+        // Réimportées dans la saisie d'origine, les activités déjà présentes sont ignorées
+        export.importer_agenda(&chemin);
+        std::fs::remove_file(&chemin).ok();
+        assert_eq!(export.donnees_hors_clientele, HORS_CLIENTELE);
+        assert!(export.status_message.contains("0 événement(s) importé(s)"));
+        assert!(export.status_message.contains("déjà présent(s) ignoré(s)"));
+    }
+
+    #[test]
+    fn test_evenement_externe_importe_une_fois() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_externe_{}.ics", std::process::id()));
+        std::fs::write(&chemin, AGENDA).unwrap();
+
+        let mut app = GestionTempsApp::default();
+        app.importer_agenda(&chemin);
+        app.importer_agenda(&chemin);
+        std::fs::remove_file(&chemin).ok();
+
+        let lignes: Vec<&str> = app.donnees_hors_clientele.lines().collect();
+        assert_eq!(lignes.len(), 2);
+        assert!(lignes.iter().all(|l| l.starts_with(CODE_AGENDA)));
+    }
+}
@@ -0,0 +1,117 @@
+use gestion_temps::api::{ServeurApi, TAILLE_MAX_CORPS};
+use gestion_temps::RapportJson;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+#[cfg(test)]
+mod api_tests {
+    use super::*;
+
+    fn demarrer_serveur() -> SocketAddr {
+        let serveur = ServeurApi::demarrer("127.0.0.1:0").unwrap();
+        let adresse = serveur.adresse().unwrap();
+        std::thread::spawn(move || serveur.servir());
+        adresse
+    }
+
+    /// Envoie une requête HTTP/1.1 et renvoie le statut, les en-têtes et le corps.
+    fn requete(adresse: SocketAddr, methode: &str, chemin: &str, corps: &[u8]) -> (u16, String, Vec<u8>) {
+        let mut flux = TcpStream::connect(adresse).unwrap();
+        write!(
+            flux,
+            "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            methode,
+            chemin,
+            adresse,
+            corps.len()
+        )
+        .unwrap();
+        flux.write_all(corps).unwrap();
+
+        let mut reponse = Vec::new();
+        flux.read_to_end(&mut reponse).unwrap();
+        let fin_entetes = reponse.windows(4).position(|f| f == b"\r\n\r\n").unwrap();
+        let entetes = String::from_utf8_lossy(&reponse[..fin_entetes]).to_string();
+        let statut = entetes.split(' ').nth(1).unwrap().parse().unwrap();
+        (statut, entetes, reponse[fin_entetes + 4..].to_vec())
+    }
+
+    #[test]
+    fn test_rapport_par_zone() {
+        let adresse = demarrer_serveur();
+
+        let (statut, entetes, corps) = requete(adresse, "POST", "/rapport/clientele", CLIENTELE.as_bytes());
+        assert_eq!(statut, 200);
+        assert!(entetes.contains("application/json"));
+        let rapport = RapportJson::depuis_json(std::str::from_utf8(&corps).unwrap()).unwrap();
+        let resultat = rapport.resultat.unwrap();
+        assert_eq!(resultat.hors_clientele, 0.0);
+        assert!(resultat.travail_clientele > 0.0);
+
+        // La seconde zone complète la première au lieu de la remplacer
+        let (statut, _, corps) = requete(adresse, "POST", "/rapport/hors-clientele", HORS_CLIENTELE.as_bytes());
+        assert_eq!(statut, 200);
+        let combine = RapportJson::depuis_json(std::str::from_utf8(&corps).unwrap()).unwrap();
+        assert!((combine.total - 23.15).abs() < 0.01);
+        let resultat_combine = combine.resultat.unwrap();
+        assert!(resultat_combine.hors_clientele > 0.0);
+        assert_eq!(resultat_combine.travail_clientele, resultat.travail_clientele);
+    }
+
+    #[test]
+    fn test_export_mixte_et_periode() {
+        let adresse = demarrer_serveur();
+        let mixte = format!("{}\n{}", HORS_CLIENTELE, CLIENTELE);
+
+        let (statut, _, corps) = requete(adresse, "POST", "/rapport", mixte.as_bytes());
+        assert_eq!(statut, 200);
+        let rapport = RapportJson::depuis_json(std::str::from_utf8(&corps).unwrap()).unwrap();
+        assert!((rapport.total - 23.15).abs() < 0.01);
+
+        let (statut, _, corps) = requete(adresse, "POST", "/rapport?du=2025-06-11", mixte.as_bytes());
+        assert_eq!(statut, 200);
+        let filtre = RapportJson::depuis_json(std::str::from_utf8(&corps).unwrap()).unwrap();
+        assert!(filtre.total < rapport.total);
+        assert!(filtre.metadonnees.entrees_hors_periode > 0);
+
+        // Le dernier rapport reste disponible
+        let (statut, _, corps) = requete(adresse, "GET", "/rapport", b"");
+        assert_eq!(statut, 200);
+        assert_eq!(RapportJson::depuis_json(std::str::from_utf8(&corps).unwrap()).unwrap(), filtre);
+    }
+
+    #[test]
+    fn test_graphique_png() {
+        let adresse = demarrer_serveur();
+
+        let (statut, _, _) = requete(adresse, "GET", "/graphique.png", b"");
+        assert_eq!(statut, 404);
+
+        requete(adresse, "POST", "/rapport/clientele", CLIENTELE.as_bytes());
+        let (statut, entetes, corps) = requete(adresse, "GET", "/graphique.png", b"");
+        assert_eq!(statut, 200);
+        assert!(entetes.contains("image/png"));
+        assert_eq!(&corps[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn test_erreurs() {
+        let adresse = demarrer_serveur();
+
+        assert_eq!(requete(adresse, "GET", "/rapport", b"").0, 404);
+        assert_eq!(requete(adresse, "POST", "/rapport", b"  \n").0, 400);
+        assert_eq!(requete(adresse, "POST", "/rapport?du=11/06/2025", CLIENTELE.as_bytes()).0, 400);
+        assert_eq!(requete(adresse, "DELETE", "/rapport", b"").0, 405);
+
+        let trop_gros = vec![b'a'; TAILLE_MAX_CORPS as usize + 1];
+        assert_eq!(requete(adresse, "POST", "/rapport", &trop_gros).0, 413);
+
+        let (statut, _, corps) = requete(adresse, "GET", "/inconnu", b"");
+        assert_eq!(statut, 404);
+        let erreur: serde_json::Value = serde_json::from_slice(&corps).unwrap();
+        assert!(erreur["erreur"].as_str().unwrap().contains("/inconnu"));
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::ligne_clientele;
+use gestion_temps::{GestionTempsApp, PolitiqueArrondi, ReglesArrondi};
+
+/// Intervention chez `client` avec `travail` heures de travail et 0,5h de trajet.
+fn intervention(client: &str, travail: &str) -> String {
+    let trajet = ("10/06/2025 08:00", "10/06/2025 08:30");
+    ligne_clientele("AA-1", client, "Maintenance", trajet, ("10/06/2025 08:30", "10/06/2025 09:30"), travail, "0,5")
+}
+
+#[cfg(test)]
+mod arrondi_tests {
+    use super::*;
+
+    #[test]
+    fn test_politiques() {
+        let quart = PolitiqueArrondi::quart_d_heure_superieur();
+        assert!((quart.appliquer(1.05) - 1.25).abs() < 1e-9);
+        assert!((quart.appliquer(0.25) - 0.25).abs() < 1e-9);
+
+        let six_minutes = PolitiqueArrondi::six_minutes_plus_proches();
+        assert!((six_minutes.appliquer(1.04) - 1.0).abs() < 1e-9);
+        assert!((six_minutes.appliquer(1.06) - 1.1).abs() < 1e-9);
+
+        let minimum = PolitiqueArrondi::minimum_une_heure();
+        assert!((minimum.appliquer(0.4) - 1.0).abs() < 1e-9);
+        assert!((minimum.appliquer(1.3) - 1.3).abs() < 1e-9);
+        assert_eq!(minimum.appliquer(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_regles_depuis_texte() {
+        let regles = ReglesArrondi::depuis_texte(
+            "# arrondis de facturation\nclient;Alpha;15;supérieur;0\nclient;Beta;6;proche\ncatégorie;Formation;;;1\n",
+        )
+        .unwrap();
+
+        assert_eq!(regles.regles.len(), 3);
+        assert_eq!(regles.regles[1].politique, PolitiqueArrondi::six_minutes_plus_proches());
+        assert_eq!(regles.regles[2].politique, PolitiqueArrondi::minimum_une_heure());
+        assert_eq!(ReglesArrondi::depuis_texte(&regles.vers_texte()).unwrap(), regles);
+
+        assert!(ReglesArrondi::depuis_texte("fournisseur;Alpha;15").is_err());
+        assert!(ReglesArrondi::depuis_texte("client;Alpha;15;vers le haut").is_err());
+    }
+
+    #[test]
+    fn test_arrondi_par_client_avant_les_totaux() {
+        let mut app = GestionTempsApp {
+            donnees_clientele: [intervention("Alpha", "1,05"), intervention("Beta", "0,4"), intervention("Gamma", "0,7")].join("\n"),
+            regles_arrondi: ReglesArrondi::depuis_texte("client;alpha;15;supérieur\nclient;Beta;0;;1").unwrap(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        // 1,25 + 1 arrondis, Gamma sans règle
+        assert!((resultat.travail_clientele - 2.95).abs() < 1e-9);
+        assert!((resultat.deplacement - 1.5).abs() < 1e-9);
+        assert!((resultat.ecart_arrondi() - 0.8).abs() < 1e-9);
+
+        let alpha = &resultat.arrondis["Client alpha"];
+        assert_eq!(alpha.entrees, 1);
+        assert!((alpha.reel - 1.05).abs() < 1e-9);
+        assert!((alpha.ecart() - 0.2).abs() < 1e-9);
+        assert!(!resultat.arrondis.keys().any(|cible| cible.contains("Gamma")));
+    }
+
+    #[test]
+    fn test_arrondi_par_categorie() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: "ABS--1\tFormation sécurité\t10/06/2025 13:00\t10/06/2025 13:20\n\
+                                     ABS--2\tRéunion équipe\t11/06/2025 08:00\t11/06/2025 08:20"
+                .to_string(),
+            regles_arrondi: ReglesArrondi::depuis_texte("catégorie;Formation;0;;1").unwrap(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.par_categorie()["Formation"] - 1.0).abs() < 1e-9);
+        assert!((resultat.par_categorie()["Réunion"] - 1.0 / 3.0).abs() < 1e-6);
+        assert!((resultat.ecart_arrondi() - 2.0 / 3.0).abs() < 1e-6);
+    }
+}
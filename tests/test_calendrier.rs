@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use gestion_temps::calendrier::{jours_feries, paques};
+use gestion_temps::{Calendrier, GestionTempsApp, TypeJour};
+
+fn date(annee: i32, mois: u32, jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(annee, mois, jour).unwrap()
+}
+
+#[cfg(test)]
+mod calendrier_tests {
+    use super::*;
+
+    #[test]
+    fn test_paques() {
+        assert_eq!(paques(2024), date(2024, 3, 31));
+        assert_eq!(paques(2025), date(2025, 4, 20));
+        assert_eq!(paques(2026), date(2026, 4, 5));
+        assert_eq!(paques(2038), date(2038, 4, 25));
+    }
+
+    #[test]
+    fn test_jours_feries() {
+        let feries = jours_feries(2025, false);
+        assert_eq!(feries.len(), 11);
+        assert!(feries.contains(&(date(2025, 4, 21), "Lundi de Pâques")));
+        assert!(feries.contains(&(date(2025, 5, 29), "Ascension")));
+        assert!(feries.contains(&(date(2025, 6, 9), "Lundi de Pentecôte")));
+
+        let alsace = jours_feries(2025, true);
+        assert_eq!(alsace.len(), 13);
+        assert!(alsace.contains(&(date(2025, 4, 18), "Vendredi saint")));
+        assert!(alsace.contains(&(date(2025, 12, 26), "Saint-Étienne")));
+    }
+
+    #[test]
+    fn test_type_jour() {
+        let mut calendrier = Calendrier::default();
+        calendrier.ajouter_fermeture(date(2025, 8, 14), "Pont");
+
+        assert_eq!(calendrier.type_jour(date(2025, 6, 10)), TypeJour::Ouvre);
+        assert_eq!(calendrier.type_jour(date(2025, 6, 14)), TypeJour::WeekEnd);
+        assert_eq!(calendrier.type_jour(date(2025, 7, 14)), TypeJour::Ferie("Fête nationale".to_string()));
+        assert_eq!(calendrier.type_jour(date(2025, 8, 14)), TypeJour::Fermeture("Pont".to_string()));
+        assert_eq!(calendrier.type_jour(date(2025, 12, 26)), TypeJour::Ouvre);
+
+        calendrier.alsace_moselle = true;
+        assert!(!calendrier.type_jour(date(2025, 12, 26)).est_ouvre());
+    }
+
+    #[test]
+    fn test_heures_non_ouvrees() {
+        // Lundi de Pentecôte, samedi et un jour de fermeture
+        let hors_clientele = "ABS--1\tRéunion équipe\t09/06/2025 08:00\t09/06/2025 10:00
+ABS--2\tInventaire stock\t14/06/2025 08:00\t14/06/2025 11:00
+ABS--3\tFormation sécurité\t16/06/2025 08:00\t16/06/2025 12:00
+ABS--4\tPréparation matériel\t17/06/2025 08:00\t17/06/2025 09:30";
+
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: hors_clientele.to_string(),
+            ..Default::default()
+        };
+        app.calendrier.ajouter_fermeture(date(2025, 6, 16), "Inventaire annuel");
+        app.calculer_resultats();
+
+        let heures = app.heures_non_ouvrees();
+        assert!((heures.feries - 2.0).abs() < 0.01);
+        assert!((heures.week_end - 3.0).abs() < 0.01);
+        assert!((heures.fermetures - 4.0).abs() < 0.01);
+        assert!((heures.total() - 9.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,90 @@
+use gestion_temps::categorie::creer_histogramme;
+use gestion_temps::{GestionTempsApp, ReglesCategories};
+use std::collections::BTreeMap;
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+
+#[cfg(test)]
+mod categorie_tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_par_defaut() {
+        let regles = ReglesCategories::default();
+
+        assert_eq!(regles.categoriser("ABS--313130", "Formation sécurité"), "Formation");
+        assert_eq!(regles.categoriser("ABS--313131", "Réunion équipe"), "Réunion");
+        assert_eq!(regles.categoriser("ABS--313129", "Révision véhicule"), "Maintenance véhicule");
+        assert_eq!(regles.categoriser("ABS--313134", "Maintenance outillage"), "Préparation matériel");
+        assert_eq!(regles.categoriser("ABS--999999", "Congé payé"), "Absence");
+        assert_eq!(regles.categoriser("ABS--999999", "Divers"), "Autre");
+    }
+
+    #[test]
+    fn test_regles_depuis_texte() {
+        let regles = ReglesCategories::depuis_texte(
+            "# règles de l'agence\nAstreinte;;ABS--9\nFormation;formation, habilitation;\n",
+        )
+        .unwrap();
+
+        assert_eq!(regles.categoriser("abs--9001", "Permanence"), "Astreinte");
+        assert_eq!(regles.categoriser("ABS--1", "Habilitation électrique"), "Formation");
+        assert_eq!(ReglesCategories::depuis_texte(&regles.vers_texte()).unwrap(), regles);
+
+        assert!(ReglesCategories::depuis_texte("Vide;;").is_err());
+        assert!(ReglesCategories::depuis_texte(";formation;").is_err());
+    }
+
+    #[test]
+    fn test_sous_totaux_par_categorie() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!(resultat.details_hors_clientele.iter().all(|d| d.code.starts_with("ABS--")));
+
+        let sous_totaux = resultat.par_categorie();
+        let total: f64 = sous_totaux.values().sum();
+        assert!((total - resultat.hors_clientele).abs() < 0.01);
+        assert!(sous_totaux.contains_key("Formation"));
+        assert!(sous_totaux.contains_key("Maintenance véhicule"));
+    }
+
+    #[test]
+    fn test_appliquer_regles_recategorise() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        app.texte_regles_categories = "Interne;;ABS--".to_string();
+        app.appliquer_regles_categories();
+
+        let sous_totaux = app.resultat.as_ref().unwrap().par_categorie();
+        assert_eq!(sous_totaux.len(), 1);
+        assert!((sous_totaux["Interne"] - 12.5).abs() < 0.01);
+
+        // Des règles invalides laissent les précédentes en place
+        app.texte_regles_categories = "Cassée".to_string();
+        app.appliquer_regles_categories();
+        assert!(app.status_message.contains("invalides"));
+        assert_eq!(app.regles_categories.regles.len(), 1);
+    }
+
+    #[test]
+    fn test_histogramme_d_une_seule_categorie() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_categories_{}.png", std::process::id()));
+        let sous_totaux = BTreeMap::from([("Formation".to_string(), 3.5)]);
+        creer_histogramme(&sous_totaux, &chemin).unwrap();
+
+        // La barre unique est bien dessinée
+        let image = image::open(&chemin).unwrap().to_rgb8();
+        std::fs::remove_file(&chemin).ok();
+        let pixels_barre = image.pixels().filter(|p| p[2] > 200 && p[0] < 150 && p[1] < 150).count();
+        assert!(pixels_barre > 1000, "{} pixels de barre", pixels_barre);
+    }
+}
@@ -0,0 +1,95 @@
+use gestion_temps::classeur::{date_excel_vers_datetime, lister_feuilles, ImportClasseur};
+use gestion_temps::{fichier, GestionTempsApp, ZoneSaisie};
+use std::path::{Path, PathBuf};
+
+fn classeur(nom: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/classeurs").join(nom)
+}
+
+#[cfg(test)]
+mod classeur_tests {
+    use super::*;
+
+    #[test]
+    fn test_date_excel_vers_datetime() {
+        // 45818.3125 = 10/06/2025 07:30
+        let date = date_excel_vers_datetime(45818.3125).unwrap();
+        assert_eq!(date.format("%d/%m/%Y %H:%M").to_string(), "10/06/2025 07:30");
+        assert!(date_excel_vers_datetime(-1.0).is_none());
+    }
+
+    #[test]
+    fn test_lister_feuilles_xlsx() {
+        let feuilles = lister_feuilles(&classeur("export.xlsx")).unwrap();
+        assert_eq!(feuilles, vec!["Hors clientèle", "Clientèle"]);
+    }
+
+    #[test]
+    fn test_import_xlsx_feuille_et_entete() {
+        let mut import = ImportClasseur::ouvrir(&classeur("export.xlsx"), ZoneSaisie::HorsClientele).unwrap();
+
+        // La ligne de titre n'est pas prise pour l'en-tête : on le désigne explicitement
+        import.ligne_entete = 2;
+        import.recharger().unwrap();
+
+        let texte = import.texte();
+        assert_eq!(
+            texte.lines().next().unwrap(),
+            "ABS--313129\tRévision véhicule\t10/06/2025 07:30\t10/06/2025 11:15"
+        );
+        // La colonne "Fin" non formatée est convertie grâce à son en-tête
+        assert!(texte.contains("10/06/2025 17:00"));
+
+        let mut app = GestionTempsApp { import_classeur: Some(import), ..Default::default() };
+        app.valider_import_classeur();
+
+        let mut clientele = ImportClasseur::ouvrir(&classeur("export.xlsx"), ZoneSaisie::Clientele).unwrap();
+        clientele.feuille = 1;
+        clientele.recharger().unwrap();
+        assert_eq!(clientele.ligne_entete, 1);
+        app.import_classeur = Some(clientele);
+        app.valider_import_classeur();
+
+        app.calculer_resultats();
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.hors_clientele - 7.75).abs() < 0.01, "Hors clientèle: {}", resultat.hors_clientele);
+        assert!((resultat.travail_clientele - 1.3).abs() < 0.01);
+        assert!((resultat.deplacement - 0.6).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_import_ods_dates_iso() {
+        let import = ImportClasseur::ouvrir(&classeur("hors_clientele.ods"), ZoneSaisie::HorsClientele).unwrap();
+
+        assert_eq!(import.feuilles, vec!["Activités"]);
+        assert_eq!(import.ligne_entete, 1);
+        assert_eq!(
+            import.texte(),
+            "ABS--313140\tPréparation journée\t13/06/2025 07:00\t13/06/2025 08:00\n\
+             ABS--313141\tFormation technique\t13/06/2025 08:30\t13/06/2025 10:30"
+        );
+    }
+
+    #[test]
+    fn test_colonnes_de_durees_non_converties() {
+        let import = ImportClasseur::ouvrir(&classeur("durees.xlsx"), ZoneSaisie::HorsClientele).unwrap();
+        assert_eq!(import.ligne_entete, 1);
+
+        // "Heures", "Nb heures" et "Durée fin de trajet" restent des nombres décimaux
+        assert_eq!(
+            import.texte(),
+            "ABS--313129\tRévision véhicule\t10/06/2025 07:30\t10/06/2025 11:15\t3.75\t2\t0.5"
+        );
+    }
+
+    #[test]
+    fn test_lecture_directe_avec_dates_non_formatees() {
+        // Chemin de --json, du glisser-déposer hors zone et de la surveillance de dossier :
+        // l'en-tête est détecté, la date de fin non formatée est convertie et l'en-tête écarté
+        let contenu = fichier::lire_fichier_export(&classeur("durees.xlsx")).unwrap();
+        assert_eq!(
+            contenu.texte,
+            "ABS--313129\tRévision véhicule\t10/06/2025 07:30\t10/06/2025 11:15\t3.75\t2\t0.5"
+        );
+    }
+}
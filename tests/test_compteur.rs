@@ -0,0 +1,94 @@
+use chrono::NaiveDate;
+use gestion_temps::{CompteurHeures, FiltreDates, GestionTempsApp, MouvementCompteur, Periode, ProfilEmploye, TypeMouvement};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+fn date(mois: u32, jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, mois, jour).unwrap()
+}
+
+#[cfg(test)]
+mod compteur_tests {
+    use super::*;
+
+    #[test]
+    fn test_solde_et_report_de_periode() {
+        let mut compteur = CompteurHeures::default();
+        compteur.reporter_periode("T001", date(5, 1), date(5, 31), 6.5, date(6, 2));
+        compteur.reporter_periode("T002", date(5, 1), date(5, 31), -3.0, date(6, 2));
+        compteur.reporter_periode("T001", date(6, 1), date(6, 30), 2.0, date(7, 1));
+        assert!((compteur.solde("T001") - 8.5).abs() < 0.01);
+
+        // Recalculer la même période remplace le report précédent
+        compteur.reporter_periode("T001", date(6, 1), date(6, 30), 1.0, date(7, 2));
+        assert!((compteur.solde("T001") - 7.5).abs() < 0.01);
+
+        let historique = compteur.historique("T001");
+        assert_eq!(historique.len(), 2);
+        assert!((historique[1].1 - 7.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_compteur_texte_aller_retour() {
+        let mut compteur = CompteurHeures::default();
+        compteur.reporter_periode("T001", date(5, 1), date(5, 31), 6.5, date(6, 2));
+        compteur.mouvements.push(MouvementCompteur {
+            employe: "T001".to_string(),
+            date: date(6, 5),
+            type_mouvement: TypeMouvement::JoursRecuperes,
+            periode: None,
+            heures: -7.0,
+            libelle: "Pont; ascension".to_string(),
+        });
+
+        compteur.mouvements.push(MouvementCompteur {
+            employe: "#Dupont; Jean".to_string(),
+            date: date(6, 6),
+            type_mouvement: TypeMouvement::Ajustement,
+            periode: None,
+            heures: 1.0 / 3.0,
+            libelle: "Reprise 100%\nsur deux lignes".to_string(),
+        });
+
+        let relu = CompteurHeures::depuis_texte(&compteur.vers_texte()).unwrap();
+        assert_eq!(relu, compteur);
+        assert_eq!(relu.solde("#Dupont; Jean"), 1.0 / 3.0);
+        assert!(CompteurHeures::depuis_texte("T001;pas une date;periode;;;1;x").is_err());
+    }
+
+    #[test]
+    fn test_compteur_conserve_entre_sessions() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_compteur_{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&chemin);
+
+        let mut app = GestionTempsApp::default();
+        app.charger_compteur(chemin.clone());
+        app.ajouter_employe(ProfilEmploye {
+            nom: "Alice".to_string(),
+            matricule: "T001".to_string(),
+            ..Default::default()
+        });
+        app.donnees_hors_clientele = HORS_CLIENTELE.to_string();
+        app.donnees_clientele = CLIENTELE.to_string();
+        app.filtre_dates = FiltreDates {
+            periode: Periode::Personnalisee,
+            du: "09/06/2025".to_string(),
+            au: "13/06/2025".to_string(),
+        };
+        app.calculer_resultats();
+
+        let ecart = app.reporter_periode_au_compteur().unwrap();
+        app.ajouter_mouvement_compteur(TypeMouvement::HeuresPayees, 2.0, "Paie de juin");
+        let solde = app.compteur.solde("T001");
+        assert!((solde - (ecart - 2.0)).abs() < 0.01);
+
+        // Une nouvelle session relit le compteur enregistré
+        let mut session = GestionTempsApp::default();
+        session.charger_compteur(chemin.clone());
+        assert!((session.compteur.solde("T001") - solde).abs() < 0.01);
+        assert_eq!(session.compteur.historique("T001")[1].0.libelle, "Paie de juin");
+
+        let _ = std::fs::remove_file(&chemin);
+    }
+}
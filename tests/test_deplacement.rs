@@ -0,0 +1,81 @@
+use gestion_temps::deplacement::{bareme_kilometrique, deplacement_par_jour, statistiques_par_client, trajets_les_plus_longs};
+use gestion_temps::{GestionTempsApp, ParametresIndemnites};
+
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+#[cfg(test)]
+mod deplacement_tests {
+    use super::*;
+
+    fn app_calculee() -> GestionTempsApp {
+        let mut app = GestionTempsApp {
+            donnees_clientele: CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+        app
+    }
+
+    #[test]
+    fn test_statistiques_par_client() {
+        let app = app_calculee();
+        let entrees = app.entrees_retenues();
+
+        let statistiques = statistiques_par_client(&entrees);
+        assert_eq!(statistiques.len(), 3);
+        // Le client au plus long temps de trajet en premier
+        assert_eq!(statistiques[0].client, "Client Gamma");
+        assert_eq!(statistiques[0].interventions, 1);
+        assert!((statistiques[0].trajet_moyen() - 1.2).abs() < 0.01);
+        assert!((statistiques[0].ratio() - 0.24).abs() < 0.01);
+
+        let total: f64 = statistiques.iter().map(|s| s.deplacement).sum();
+        assert!((total - app.resultat.as_ref().unwrap().deplacement).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_deplacements_par_jour_et_plus_longs() {
+        let app = app_calculee();
+        let entrees = app.entrees_retenues();
+
+        let par_jour = deplacement_par_jour(&entrees);
+        assert_eq!(par_jour.len(), 3);
+        assert!((par_jour.values().sum::<f64>() - 2.6).abs() < 0.01);
+
+        let plus_longs = trajets_les_plus_longs(&entrees, 2);
+        assert_eq!(plus_longs.len(), 2);
+        assert!(plus_longs[0].deplacement >= plus_longs[1].deplacement);
+    }
+
+    #[test]
+    fn test_bareme_kilometrique() {
+        assert!((bareme_kilometrique(5, 1000.0) - 636.0).abs() < 0.01);
+        assert!((bareme_kilometrique(5, 10000.0) - 4965.0).abs() < 0.01);
+        assert!((bareme_kilometrique(7, 25000.0) - 11750.0).abs() < 0.01);
+        assert!((bareme_kilometrique(9, 100.0) - 69.7).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_note_de_frais() {
+        let mut app = app_calculee();
+        app.indemnites = ParametresIndemnites {
+            actif: true,
+            puissance_fiscale: 4,
+            ..Default::default()
+        };
+        app.indemnites.km_par_client.insert("Client Alpha".to_string(), 40.0);
+        app.indemnites.km_par_client.insert("Client Gamma".to_string(), 110.0);
+
+        let note = app.note_de_frais();
+        assert!((note.km_total - 150.0).abs() < 0.01);
+        assert!((note.montant - 150.0 * 0.606).abs() < 0.01);
+        assert!(note.estimation);
+        assert_eq!(note.clients_sans_distance, vec!["Client Beta".to_string()]);
+
+        // Avec 10 000 km dans l'année, la période reçoit sa part du barème annuel (4 730 €)
+        app.indemnites.km_annuels = 10000.0;
+        let note = app.note_de_frais();
+        assert!((note.montant - 4730.0 * 150.0 / 10000.0).abs() < 0.01);
+        assert!(!note.estimation);
+    }
+}
@@ -0,0 +1,64 @@
+use gestion_temps::detection::{classer_ligne, detecter_type, zones_inversees, TypeDonnees, TypeLigne};
+use gestion_temps::GestionTempsApp;
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+#[cfg(test)]
+mod detection_tests {
+    use super::*;
+
+    #[test]
+    fn test_classer_ligne() {
+        assert_eq!(
+            classer_ligne("ABS--313129\tRévision véhicule\t10/06/2025 08:00\t10/06/2025 10:00"),
+            TypeLigne::HorsClientele
+        );
+        assert_eq!(classer_ligne(CLIENTELE.lines().next().unwrap()), TypeLigne::Clientele);
+        assert_eq!(classer_ligne("Code\tDescription\tDébut\tFin"), TypeLigne::EnTete);
+        assert_eq!(classer_ligne("n'importe quoi"), TypeLigne::Invalide);
+    }
+
+    #[test]
+    fn test_detecter_type() {
+        assert_eq!(detecter_type(HORS_CLIENTELE), TypeDonnees::HorsClientele);
+        assert_eq!(detecter_type(CLIENTELE), TypeDonnees::Clientele);
+        assert_eq!(detecter_type("\n  \n"), TypeDonnees::Vide);
+        assert!(!zones_inversees(HORS_CLIENTELE, CLIENTELE));
+        assert!(zones_inversees(CLIENTELE, HORS_CLIENTELE));
+    }
+
+    #[test]
+    fn test_alerte_zones_inversees() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: CLIENTELE.to_string(),
+            donnees_clientele: HORS_CLIENTELE.to_string(),
+            ..Default::default()
+        };
+
+        assert!(!app.verifier_zones());
+        assert!(app.alerte_zones_inversees);
+
+        app.inverser_zones();
+        app.calculer_resultats();
+        assert!(!app.alerte_zones_inversees);
+        assert!((app.resultat.as_ref().unwrap().hors_clientele - 12.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_routage_automatique() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: CLIENTELE.to_string(),
+            donnees_clientele: HORS_CLIENTELE.to_string(),
+            routage_automatique: true,
+            ..Default::default()
+        };
+
+        app.calculer_avec_verification();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.hors_clientele - 12.5).abs() < 0.01);
+        assert!((resultat.travail_clientele - 8.05).abs() < 0.01);
+        assert!(app.status_message.contains("bonne zone"));
+    }
+}
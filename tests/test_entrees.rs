@@ -0,0 +1,89 @@
+use gestion_temps::{GestionTempsApp, TypeEntree};
+
+fn app_client_1() -> GestionTempsApp {
+    GestionTempsApp {
+        donnees_hors_clientele: include_str!("data/client_1/hors_client.txt").to_string(),
+        donnees_clientele: include_str!("data/client_1/client.txt").to_string(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod entrees_tests {
+    use super::*;
+
+    #[test]
+    fn test_analyser_saisie() {
+        let mut app = app_client_1();
+        app.analyser_saisie();
+
+        // 5 lignes hors clientèle (pause déjeuner exclue) et 3 interventions
+        assert_eq!(app.entrees.len(), 8);
+        assert_eq!(app.entrees[0].code, "ABS--313129");
+        assert_eq!(app.entrees[0].description, "Révision véhicule");
+        assert!((app.entrees[0].duree - 3.75).abs() < 0.01);
+
+        let intervention = &app.entrees[5];
+        assert_eq!(intervention.type_entree, TypeEntree::Clientele);
+        assert_eq!(intervention.client, "Client Alpha");
+        assert_eq!(intervention.date.unwrap().to_string(), "2025-06-10");
+        assert!(!app.entrees_obsoletes());
+    }
+
+    #[test]
+    fn test_calcul_depuis_entrees_modifiees() {
+        let mut app = app_client_1();
+        app.analyser_saisie();
+
+        // Désactiver "Révision véhicule" (3,75h) et corriger la fin de "Formation sécurité"
+        app.entrees[0].actif = false;
+        app.entrees[1].fin = "10/06/2025 16:00".to_string();
+        app.entrees[1].recalculer();
+        // Reclasser la troisième intervention en hors clientèle
+        app.entrees[7].changer_type(TypeEntree::HorsClientele);
+
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        // 12.5 - 3.75 - 1.0 + durée recalculée de l'intervention reclassée (09:00 -> 14:00 = 5h)
+        assert!((resultat.hors_clientele - 12.75).abs() < 0.01, "Hors clientèle: {}", resultat.hors_clientele);
+        assert!((resultat.travail_clientele - 3.05).abs() < 0.01);
+        assert!((resultat.deplacement - 1.4).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_texte_modifie_reanalyse() {
+        let mut app = app_client_1();
+        app.analyser_saisie();
+        app.entrees[0].actif = false;
+
+        app.donnees_clientele.clear();
+        assert!(app.entrees_obsoletes());
+        app.calculer_resultats();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.hors_clientele - 12.5).abs() < 0.01);
+        assert_eq!(resultat.travail_clientele, 0.0);
+    }
+
+    #[test]
+    fn test_intervention_sans_date_desactivee() {
+        let mut app = GestionTempsApp {
+            donnees_clientele: "WO-1\tClient\tTest\tClôturé\t-\t-\t-\t-\tdate inconnue\t-\t-\t-\t-\t-\t2,0\t0,5".to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        assert_eq!(app.entrees.len(), 1);
+        assert!(!app.entrees[0].actif);
+        assert_eq!(app.resultat.as_ref().unwrap().travail_clientele, 0.0);
+
+        // Une fois la date corrigée et la ligne réactivée, elle est comptée
+        app.entrees[0].debut = "14/06/2025 10:00".to_string();
+        app.entrees[0].recalculer();
+        app.entrees[0].actif = true;
+        app.calculer_resultats();
+        assert_eq!(app.resultat.as_ref().unwrap().travail_clientele, 2.0);
+        assert_eq!(app.temps_par_jour.values().sum::<f64>(), 2.5);
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::ligne_clientele;
+use gestion_temps::{GestionTempsApp, ReglesArrondi, TarifsClients};
+
+/// Intervention `wo` chez `client` le `jour` juin 2025.
+fn intervention(wo: &str, client: &str, type_intervention: &str, jour: u32, travail: &str, deplacement: &str) -> String {
+    let debut = format!("{:02}/06/2025 09:00", jour);
+    let fin = format!("{:02}/06/2025 10:00", jour);
+    ligne_clientele(wo, client, type_intervention, ("-", "-"), (&debut, &fin), travail, deplacement)
+}
+
+#[cfg(test)]
+mod facturation_tests {
+    use super::*;
+
+    fn app_facturee() -> GestionTempsApp {
+        let mut app = GestionTempsApp {
+            donnees_clientele: [
+                intervention("WO-2", "Alpha", "Maintenance", 11, "2", "0,5"),
+                intervention("WO-1", "Alpha", "Dépannage", 10, "1", "1"),
+                intervention("WO-3", "Beta", "Maintenance", 10, "1,5", "0"),
+                intervention("WO-4", "Gamma", "Maintenance", 12, "1", "1"),
+            ]
+            .join("\n"),
+            tarifs: TarifsClients::depuis_texte("Alpha;;60;30\nalpha;dépannage;90;30\nBeta;;50,5;0").unwrap(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+        app
+    }
+
+    #[test]
+    fn test_tarifs_depuis_texte() {
+        let tarifs = TarifsClients::depuis_texte("# tarifs 2025\nAlpha;;60;30\nAlpha;Dépannage;90;45").unwrap();
+
+        assert_eq!(tarifs.tarif("alpha", "Maintenance").unwrap().travail, 60.0);
+        assert_eq!(tarifs.tarif("Alpha", "dépannage").unwrap().deplacement, 45.0);
+        assert!(tarifs.tarif("Beta", "Maintenance").is_none());
+        assert_eq!(TarifsClients::depuis_texte(&tarifs.vers_texte()).unwrap(), tarifs);
+        assert!(TarifsClients::depuis_texte("Alpha;;soixante;30").is_err());
+    }
+
+    #[test]
+    fn test_rapport_par_client() {
+        let rapport = app_facturee().rapport_facturation();
+
+        assert_eq!(rapport.clients.len(), 3);
+        let alpha = &rapport.clients[0];
+        assert_eq!(alpha.client, "Alpha");
+        // Lignes triées par date, le dépannage au tarif de son type
+        assert_eq!(alpha.lignes[0].wo, "WO-1");
+        assert!((alpha.lignes[0].montant() - 120.0).abs() < 1e-9);
+        assert!((alpha.lignes[1].montant() - 135.0).abs() < 1e-9);
+        assert!((alpha.montant() - 255.0).abs() < 1e-9);
+
+        let gamma = &rapport.clients[2];
+        assert!(gamma.tarif_manquant());
+        assert_eq!(gamma.montant(), 0.0);
+        assert!((rapport.montant_total() - 330.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_heures_facturees_arrondies() {
+        let mut app = app_facturee();
+        app.regles_arrondi = ReglesArrondi::depuis_texte("client;Beta;0;;2").unwrap();
+
+        let rapport = app.rapport_facturation();
+        assert_eq!(rapport.clients[1].travail(), 2.0);
+        assert!((rapport.clients[1].montant() - 101.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_export_csv_et_pdf() {
+        let rapport = app_facturee().rapport_facturation();
+
+        let csv = rapport.vers_csv();
+        let lignes: Vec<&str> = csv.lines().collect();
+        assert!(lignes[0].starts_with("Client;WO;Date"));
+        assert_eq!(lignes[1], "Alpha;WO-1;10/06/2025;Dépannage;1,00;1,00;90,00;30,00;120,00");
+        assert!(lignes.contains(&"Alpha;Sous-total;;;3,00;1,50;;;255,00"));
+        assert_eq!(*lignes.last().unwrap(), "Total;;;;;;;;330,75");
+
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_facturation_{}.pdf", std::process::id()));
+        rapport.enregistrer_pdf(&chemin).unwrap();
+        let contenu = std::fs::read(&chemin).unwrap();
+        std::fs::remove_file(&chemin).ok();
+        assert!(contenu.starts_with(b"%PDF"));
+    }
+}
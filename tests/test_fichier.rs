@@ -0,0 +1,56 @@
+use gestion_temps::fichier::{csv_vers_tsv, decoder_texte};
+use gestion_temps::{GestionTempsApp, ZoneSaisie};
+
+#[cfg(test)]
+mod fichier_tests {
+    use super::*;
+
+    #[test]
+    fn test_decodage_windows_1252() {
+        // "Révision véhicule" encodé en Windows-1252 (é = 0xE9)
+        let octets = b"ABS--313129\tR\xE9vision v\xE9hicule\t10/06/2025 08:00\t10/06/2025 10:00";
+        let contenu = decoder_texte(octets);
+
+        assert_eq!(contenu.encodage, "Windows-1252");
+        assert!(contenu.texte.contains("Révision véhicule"));
+    }
+
+    #[test]
+    fn test_decodage_utf8_avec_bom() {
+        let mut octets = vec![0xEF, 0xBB, 0xBF];
+        octets.extend_from_slice("ABS--1\tRéunion équipe".as_bytes());
+        let contenu = decoder_texte(&octets);
+
+        assert_eq!(contenu.encodage, "UTF-8");
+        assert!(contenu.texte.starts_with("ABS--1"));
+    }
+
+    #[test]
+    fn test_csv_point_virgule_vers_tsv() {
+        let csv = "ABS--313129;\"Révision; véhicule\";10/06/2025 08:00;10/06/2025 10:00";
+        assert_eq!(
+            csv_vers_tsv(csv),
+            "ABS--313129\tRévision; véhicule\t10/06/2025 08:00\t10/06/2025 10:00"
+        );
+    }
+
+    #[test]
+    fn test_charger_fichier_windows_1252() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_cp1252_{}.txt", std::process::id()));
+        std::fs::write(
+            &chemin,
+            b"ABS--313129\tR\xE9vision v\xE9hicule\t10/06/2025 08:00\t10/06/2025 10:00\n",
+        )
+        .unwrap();
+
+        let mut app = GestionTempsApp::default();
+        app.charger_fichier(ZoneSaisie::HorsClientele, &chemin);
+        std::fs::remove_file(&chemin).unwrap();
+
+        assert!(app.status_message.contains("Windows-1252"));
+        app.calculer_resultats();
+        let resultat = app.resultat.as_ref().unwrap();
+        assert_eq!(resultat.details_hors_clientele[0].description, "Révision véhicule");
+        assert!((resultat.hors_clientele - 2.0).abs() < 0.01);
+    }
+}
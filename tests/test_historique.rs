@@ -0,0 +1,206 @@
+use chrono::NaiveDate;
+use gestion_temps::historique::Regroupement;
+use gestion_temps::{lot, FiltreDates, GestionTempsApp, Historique, Periode, ProfilEmploye};
+use std::path::Path;
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+#[cfg(test)]
+mod historique_tests {
+    use super::*;
+
+    fn app_avec_historique() -> GestionTempsApp {
+        GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            donnees_clientele: CLIENTELE.to_string(),
+            historique: Some(Historique::en_memoire().unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn depuis_le_11_juin() -> FiltreDates {
+        FiltreDates { periode: Periode::Personnalisee, du: "11/06/2025".to_string(), au: String::new() }
+    }
+
+    #[test]
+    fn test_enregistrement_et_reouverture() {
+        let mut app = app_avec_historique();
+        app.calculer_et_enregistrer();
+        let resultat = app.resultat.clone();
+        let temps_par_jour = app.temps_par_jour.clone();
+
+        assert_eq!(app.vue_historique.calculs.len(), 1);
+        let calcul = &app.vue_historique.calculs[0];
+        assert!((calcul.total() - 23.15).abs() < 0.01);
+        assert_eq!(calcul.du, NaiveDate::from_ymd_opt(2025, 6, 10));
+        assert_eq!(calcul.au, NaiveDate::from_ymd_opt(2025, 6, 12));
+
+        let id = calcul.id;
+        app.donnees_hors_clientele.clear();
+        app.donnees_clientele.clear();
+        app.entrees.clear();
+        app.resultat = None;
+        app.temps_par_jour.clear();
+        app.rouvrir_calcul(id);
+
+        assert_eq!(app.donnees_clientele, CLIENTELE);
+        assert_eq!(app.resultat, resultat);
+        assert_eq!(app.temps_par_jour, temps_par_jour);
+        assert!(!app.entrees_obsoletes());
+    }
+
+    #[test]
+    fn test_reouverture_dans_le_dossier_de_l_employe() {
+        let mut app = GestionTempsApp { historique: Some(Historique::en_memoire().unwrap()), ..Default::default() };
+        let alice = app.ajouter_employe(ProfilEmploye { nom: "Alice".to_string(), ..Default::default() });
+        app.donnees_hors_clientele = HORS_CLIENTELE.to_string();
+        app.donnees_clientele = CLIENTELE.to_string();
+        app.calculer_et_enregistrer();
+        let id = app.vue_historique.calculs[0].id;
+
+        let bruno = app.ajouter_employe(ProfilEmploye { nom: "Bruno".to_string(), ..Default::default() });
+        app.donnees_clientele = "saisie de Bruno".to_string();
+        app.rouvrir_calcul(id);
+
+        assert_eq!(app.employe_actif, Some(alice));
+        assert_eq!(app.donnees_clientele, CLIENTELE);
+        assert!(app.status_message.contains("pour Alice"));
+        assert_eq!(app.employes[bruno].donnees_clientele, "saisie de Bruno");
+
+        // Sans le dossier de son employé, le calcul est rouvert en saisie libre
+        app.supprimer_employe(alice);
+        app.rouvrir_calcul(id);
+        assert_eq!(app.employe_actif, None);
+        assert!(app.status_message.contains("Alice n'est pas dans l'espace de travail"));
+        assert!(app.employes[0].donnees_hors_clientele.is_empty());
+    }
+
+    #[test]
+    fn test_recalcul_non_enregistre() {
+        let mut app = app_avec_historique();
+        app.calculer_et_enregistrer();
+
+        // Filtre, tableau modifié ou surveillance : le calcul est refait sans nouvelle ligne
+        app.filtre_dates = depuis_le_11_juin();
+        app.calculer_resultats();
+        app.calculer_resultats();
+        assert_eq!(app.vue_historique.calculs.len(), 1);
+        assert_eq!(app.historique.as_ref().unwrap().lister().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_calcul_de_l_equipe_enregistre() {
+        let mut app = GestionTempsApp { historique: Some(Historique::en_memoire().unwrap()), ..Default::default() };
+        for nom in ["Alice", "Bruno"] {
+            app.ajouter_employe(ProfilEmploye { nom: nom.to_string(), ..Default::default() });
+            app.donnees_hors_clientele = HORS_CLIENTELE.to_string();
+            app.donnees_clientele = CLIENTELE.to_string();
+        }
+        app.calculer_tous_les_employes();
+
+        let calculs = &app.vue_historique.calculs;
+        assert_eq!(calculs.len(), 2);
+        assert!(calculs.iter().all(|c| (c.total() - 23.15).abs() < 0.01));
+
+        // Chaque calcul se rouvre dans le dossier de son employé
+        let id_alice = calculs.iter().find(|c| c.employe == "Alice").unwrap().id;
+        app.rouvrir_calcul(id_alice);
+        assert!(app.status_message.contains("pour Alice"));
+    }
+
+    #[test]
+    fn test_traitement_par_lot_enregistre() {
+        let racine = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+        let mut historique = Historique::en_memoire().unwrap();
+        let rapport = lot::traiter_lot(&racine, &racine.join(lot::DOSSIER_RAPPORTS), &Default::default(), Some(&mut historique))
+            .unwrap();
+
+        let calculs = historique.lister().unwrap();
+        assert_eq!(calculs.len(), rapport.dossiers.len());
+        let client_1 = calculs.iter().find(|c| c.employe == "client_1").unwrap();
+        assert!((client_1.total() - 23.15).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_reouverture_d_une_periode() {
+        let mut app = GestionTempsApp { filtre_dates: depuis_le_11_juin(), ..app_avec_historique() };
+        app.calculer_et_enregistrer();
+        let id = app.vue_historique.calculs[0].id;
+
+        app.filtre_dates = FiltreDates::default();
+        app.rouvrir_calcul(id);
+        assert_eq!(app.filtre_dates.periode, Periode::Personnalisee);
+        assert_eq!(app.filtre_dates.du, "11/06/2025");
+        assert!(app.filtre_dates.au.is_empty());
+    }
+
+    #[test]
+    fn test_comparaison_de_deux_periodes() {
+        let mut app = app_avec_historique();
+        app.calculer_et_enregistrer();
+        app.filtre_dates = depuis_le_11_juin();
+        app.calculer_et_enregistrer();
+
+        let (b, a) = (app.vue_historique.calculs[0].id, app.vue_historique.calculs[1].id);
+        let lignes = app.historique.as_ref().unwrap().comparer(a, b).unwrap();
+
+        let ligne = |libelle: &str| lignes.iter().find(|l| l.libelle == libelle).unwrap();
+        assert!((ligne("Total").a - 23.15).abs() < 0.01);
+        assert!(ligne("Total").ecart() < 0.0);
+        assert_eq!(ligne("Client Client Alpha").b, 0.0);
+        assert_eq!(ligne("Client Client Gamma").ecart(), 0.0);
+        assert_eq!(ligne("Catégorie Formation").b, 0.0);
+    }
+
+    #[test]
+    fn test_totaux_mensuels_sans_double_compte() {
+        let mut app = app_avec_historique();
+        app.calculer_et_enregistrer();
+        let resultat = app.resultat.clone().unwrap();
+
+        // Le second calcul recouvre les 11 et 12 juin : ces jours ne sont comptés qu'une fois
+        app.filtre_dates = depuis_le_11_juin();
+        app.calculer_et_enregistrer();
+
+        let historique = app.historique.as_ref().unwrap();
+        let clients = historique.totaux_mensuels(Regroupement::Client).unwrap();
+        assert_eq!(clients.len(), 3);
+        assert!(clients.iter().all(|t| t.mois == "2025-06"));
+        let travail: f64 = clients.iter().map(|t| t.heures).sum();
+        assert!((travail - resultat.travail_clientele).abs() < 0.01);
+        let deplacement: f64 = clients.iter().map(|t| t.deplacement).sum();
+        assert!((deplacement - resultat.deplacement).abs() < 0.01);
+
+        let categories = historique.totaux_mensuels(Regroupement::Categorie).unwrap();
+        let hors_clientele: f64 = categories.iter().map(|t| t.heures).sum();
+        assert!((hors_clientele - resultat.hors_clientele).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_base_conservee_sur_disque() {
+        let chemin = std::env::temp_dir().join(format!("gestion_temps_historique_{}.sqlite", std::process::id()));
+        std::fs::remove_file(&chemin).ok();
+
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            donnees_clientele: CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.ouvrir_historique(&chemin);
+        app.calculer_et_enregistrer();
+        app.calculer_et_enregistrer();
+        drop(app);
+
+        let historique = Historique::ouvrir(&chemin).unwrap();
+        let calculs = historique.lister().unwrap();
+        assert_eq!(calculs.len(), 2);
+        assert!(calculs[0].id > calculs[1].id);
+        let calcul = historique.charger(calculs[1].id).unwrap().unwrap();
+        assert_eq!(calcul.donnees_hors_clientele, HORS_CLIENTELE);
+        assert!(historique.charger(calculs[0].id + 1).unwrap().is_none());
+
+        drop(historique);
+        std::fs::remove_file(&chemin).ok();
+    }
+}
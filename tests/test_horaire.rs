@@ -0,0 +1,119 @@
+use chrono::NaiveDate;
+use gestion_temps::horaire::comparer_horaire;
+use gestion_temps::{Calendrier, FiltreDates, GestionTempsApp, HoraireHebdo, Periode, ProfilEmploye, TypeJour};
+use std::collections::HashMap;
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+fn date(jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 6, jour).unwrap()
+}
+
+fn horaire_quatre_jours() -> HoraireHebdo {
+    HoraireHebdo { heures: [8.0, 8.0, 8.0, 8.0, 7.0, 0.0, 0.0] }
+}
+
+#[cfg(test)]
+mod horaire_tests {
+    use super::*;
+
+    #[test]
+    fn test_horaire_depuis_contrat() {
+        let horaire = HoraireHebdo::depuis_contrat(39.0);
+        assert!((horaire.total() - 39.0).abs() < 0.01);
+        assert!((horaire.heures_du_jour(date(13)) - 7.8).abs() < 0.01);
+        assert_eq!(horaire.heures_du_jour(date(14)), 0.0);
+    }
+
+    #[test]
+    fn test_profil_par_defaut_coherent() {
+        let profil = ProfilEmploye::default();
+        assert_eq!(profil.heures_contrat, 35.0);
+        assert_eq!(profil.heures_contrat, profil.horaire.total());
+    }
+
+    #[test]
+    fn test_comparer_horaire_solde_cumule() {
+        let temps_par_jour = HashMap::from([(date(10), 9.0), (date(11), 6.5)]);
+
+        let lignes = comparer_horaire(&horaire_quatre_jours(), &Calendrier::default(), &temps_par_jour, date(9), date(13));
+        assert_eq!(lignes.len(), 5);
+
+        // Lundi de Pentecôte : rien de prévu
+        assert_eq!(lignes[0].type_jour, TypeJour::Ferie("Lundi de Pentecôte".to_string()));
+        assert_eq!(lignes[0].prevu, 0.0);
+
+        assert!((lignes[1].ecart - 1.0).abs() < 0.01);
+        assert!((lignes[2].ecart + 1.5).abs() < 0.01);
+        assert!((lignes[3].ecart + 8.0).abs() < 0.01);
+        assert!((lignes[4].prevu - 7.0).abs() < 0.01);
+        assert!((lignes[4].solde + 15.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ecarts_de_l_employe_actif() {
+        let mut app = GestionTempsApp::default();
+        app.ajouter_employe(ProfilEmploye {
+            nom: "Alice".to_string(),
+            horaire: horaire_quatre_jours(),
+            ..Default::default()
+        });
+        app.donnees_hors_clientele = HORS_CLIENTELE.to_string();
+        app.donnees_clientele = CLIENTELE.to_string();
+        app.filtre_dates = FiltreDates {
+            periode: Periode::Personnalisee,
+            du: "09/06/2025".to_string(),
+            au: "13/06/2025".to_string(),
+        };
+        app.calculer_resultats();
+
+        let lignes = app.ecarts_horaire();
+        assert_eq!(lignes.len(), 5);
+        assert_eq!(lignes.first().unwrap().date, date(9));
+
+        let realise: f64 = app.temps_par_jour.values().sum();
+        let prevu = 8.0 * 3.0 + 7.0;
+        assert!((lignes.last().unwrap().solde - (realise - prevu)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ecarts_sans_filtre_sur_les_jours_saisis() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        assert!(app.ecarts_horaire().is_empty());
+
+        app.calculer_resultats();
+        let lignes = app.ecarts_horaire();
+        assert_eq!(lignes.first().unwrap().date, *app.temps_par_jour.keys().min().unwrap());
+        assert_eq!(lignes.last().unwrap().date, *app.temps_par_jour.keys().max().unwrap());
+        assert!(lignes.iter().all(|l| (l.prevu - 7.0).abs() < 0.01));
+    }
+
+    #[test]
+    fn test_jour_de_conge_sans_deficit() {
+        // Lundi 16 : 7h prévues, couvertes par les congés ; mardi 17 : 7h travaillées
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: "ABS--400\tCongés payés\t16/06/2025 08:00\t16/06/2025 15:00\n\
+                                     ABS--401\tFormation\t17/06/2025 08:00\t17/06/2025 15:00"
+                .to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+        assert!(!app.temps_par_jour.contains_key(&date(16)));
+
+        let lignes = app.ecarts_horaire();
+        assert_eq!(lignes.len(), 2);
+        assert!((lignes[0].realise - 7.0).abs() < 0.01);
+        assert!(lignes[0].ecart.abs() < 0.01);
+        assert!(lignes[1].solde.abs() < 0.01);
+        assert_eq!(app.realise_et_prevu(date(16)), (lignes[0].realise, lignes[0].prevu));
+
+        // Rien n'est débité du compteur
+        let ecart = app.reporter_periode_au_compteur().unwrap();
+        assert!(ecart.abs() < 0.01);
+        assert!(app.compteur.solde("").abs() < 0.01);
+    }
+}
@@ -1,5 +1,6 @@
 
-use gestion_temps::GestionTempsApp;
+use gestion_temps::{lot, GestionTempsApp, ProfilEmploye, ReglagesCalcul, ReglesArrondi};
+use std::path::Path;
 
 #[cfg(test)]
 mod integration_tests {
@@ -7,10 +8,11 @@ mod integration_tests {
 
     #[test]
     fn test_workflow_complet_client_1() {
-        let mut app = GestionTempsApp::default();
-        
-        app.donnees_hors_clientele = include_str!("data/client_1/hors_client.txt").to_string();
-        app.donnees_clientele = include_str!("data/client_1/client.txt").to_string();
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: include_str!("data/client_1/hors_client.txt").to_string(),
+            donnees_clientele: include_str!("data/client_1/client.txt").to_string(),
+            ..Default::default()
+        };
 
         // Exécuter le calcul
         app.calculer_resultats();
@@ -34,10 +36,11 @@ mod integration_tests {
     #[test]
     fn test_workflow_complet_client_2() {
 
-        let mut app = GestionTempsApp::default();
-        
-        app.donnees_hors_clientele = include_str!("data/client_2/hors_client.txt").to_string();
-        app.donnees_clientele = include_str!("data/client_2/client.txt").to_string();
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: include_str!("data/client_2/hors_client.txt").to_string(),
+            donnees_clientele: include_str!("data/client_2/client.txt").to_string(),
+            ..Default::default()
+        };
 
         // Exécuter le calcul
         app.calculer_resultats();
@@ -62,10 +65,11 @@ mod integration_tests {
       #[test]
     fn test_workflow_complet_client_3() {
 
-        let mut app = GestionTempsApp::default();
-        
-        app.donnees_hors_clientele = include_str!("data/client_3/hors_client.txt").to_string();
-        app.donnees_clientele = include_str!("data/client_3/client.txt").to_string();
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: include_str!("data/client_3/hors_client.txt").to_string(),
+            donnees_clientele: include_str!("data/client_3/client.txt").to_string(),
+            ..Default::default()
+        };
 
         // Exécuter le calcul
         app.calculer_resultats();
@@ -86,4 +90,151 @@ mod integration_tests {
         assert!((total - 16.0).abs() < 0.01, "Durée totale incorrecte");
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_vue_equipe_plusieurs_employes() {
+        let mut app = GestionTempsApp::default();
+
+        let premier = app.ajouter_employe(ProfilEmploye {
+            nom: "Alice".to_string(),
+            matricule: "T001".to_string(),
+            heures_contrat: 35.0,
+            equipe: "Nord".to_string(),
+            ..Default::default()
+        });
+        app.donnees_hors_clientele = include_str!("data/client_1/hors_client.txt").to_string();
+        app.donnees_clientele = include_str!("data/client_1/client.txt").to_string();
+
+        let second = app.ajouter_employe(ProfilEmploye {
+            nom: "Bruno".to_string(),
+            matricule: "T002".to_string(),
+            heures_contrat: 39.0,
+            equipe: "Sud".to_string(),
+            ..Default::default()
+        });
+        // Le nouvel employé démarre avec une saisie vide
+        assert!(app.donnees_hors_clientele.is_empty());
+        app.donnees_hors_clientele = include_str!("data/client_2/hors_client.txt").to_string();
+        app.donnees_clientele = include_str!("data/client_2/client.txt").to_string();
+
+        app.calculer_tous_les_employes();
+
+        let vue = app.vue_equipe();
+        assert_eq!(vue.len(), 2);
+        assert_eq!(vue[premier].nom, "Alice");
+        assert!((vue[premier].total - 23.15).abs() < 0.01, "Total Alice incorrect");
+        assert_eq!(vue[second].equipe, "Sud");
+        assert!((vue[second].total - 9.5).abs() < 0.01, "Total Bruno incorrect");
+
+        // Revenir sur le premier employé recharge ses données
+        app.selectionner_employe(premier);
+        assert!(app.donnees_hors_clientele.contains("Révision véhicule"));
+        assert!((app.resultat.as_ref().unwrap().hors_clientele - 12.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_premier_employe_reprend_la_saisie() {
+        let mut app = GestionTempsApp {
+            donnees_clientele: include_str!("data/client_1/client.txt").to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let premier = app.ajouter_employe(ProfilEmploye { nom: "Alice".to_string(), ..Default::default() });
+        assert_eq!(app.employe_actif, Some(premier));
+        assert!(app.donnees_clientele.contains("Client Alpha"));
+        assert_eq!(app.employes[premier].donnees_clientele, app.donnees_clientele);
+        assert!(app.employes[premier].resultat.is_some());
+    }
+
+    #[test]
+    fn test_supprimer_employe_actif() {
+        let mut app = GestionTempsApp::default();
+        app.ajouter_employe(ProfilEmploye { nom: "Alice".to_string(), ..Default::default() });
+        let second = app.ajouter_employe(ProfilEmploye { nom: "Bruno".to_string(), ..Default::default() });
+        app.donnees_clientele = include_str!("data/client_3/client.txt").to_string();
+
+        app.supprimer_employe(second);
+
+        assert_eq!(app.employes.len(), 1);
+        assert_eq!(app.employe_actif, None);
+        assert!(app.donnees_clientele.is_empty());
+    }
+
+    #[test]
+    fn test_traitement_lot_dossier_data() {
+        let racine = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+        let rapport = lot::traiter_lot(&racine, &racine.join(lot::DOSSIER_RAPPORTS), &ReglagesCalcul::default(), None).expect("lecture du dossier de test");
+
+        let noms: Vec<&str> = rapport.dossiers.iter().map(|d| d.nom.as_str()).collect();
+        assert_eq!(noms, vec!["client_1", "client_2", "client_3"]);
+        assert!(rapport.dossiers_en_erreur().is_empty(), "Aucun problème attendu");
+
+        let (h, t, d) = rapport.totaux();
+        assert!((h + t + d - (23.15 + 9.5 + 16.0)).abs() < 0.01, "Total consolidé incorrect");
+
+        let sortie = std::env::temp_dir().join(format!("gestion_temps_lot_{}", std::process::id()));
+        rapport.ecrire_rapports(&sortie).expect("écriture des rapports");
+        assert!(sortie.join("rapport_client_2.txt").exists());
+        let consolide = std::fs::read_to_string(sortie.join(lot::FICHIER_RAPPORT_CONSOLIDE)).unwrap();
+        assert!(consolide.contains("Total général: 48.65 heures"));
+        std::fs::remove_dir_all(&sortie).unwrap();
+    }
+
+    #[test]
+    fn test_traitement_lot_avec_les_regles() {
+        let racine = std::env::temp_dir().join(format!("gestion_temps_lot_regles_{}", std::process::id()));
+        let employe = racine.join("alice");
+        std::fs::create_dir_all(&employe).unwrap();
+        std::fs::write(employe.join(lot::FICHIER_HORS_CLIENTELE), include_str!("data/client_1/hors_client.txt")).unwrap();
+        std::fs::write(employe.join(lot::FICHIER_CLIENTELE), include_str!("data/client_1/client.txt")).unwrap();
+
+        let sortie = racine.join(lot::DOSSIER_RAPPORTS);
+        let sans_regle = lot::traiter_lot(&racine, &sortie, &ReglagesCalcul::default(), None).unwrap();
+        let app = GestionTempsApp {
+            regles_arrondi: ReglesArrondi::depuis_texte("client;Client Alpha;0;;10").unwrap(),
+            ..Default::default()
+        };
+        let avec_regle = lot::traiter_lot(&racine, &sortie, &app.reglages_calcul(), None).unwrap();
+        std::fs::remove_dir_all(&racine).unwrap();
+
+        // L'intervention chez Client Alpha compte au minimum 10h
+        assert!((sans_regle.dossiers[0].total() - 23.15).abs() < 0.01);
+        assert!(avec_regle.dossiers[0].total() > sans_regle.dossiers[0].total() + 5.0);
+        assert!(!avec_regle.dossiers[0].resultat.as_ref().unwrap().arrondis.is_empty());
+    }
+
+    #[test]
+    fn test_traitement_lot_fichier_manquant() {
+        let racine = std::env::temp_dir().join(format!("gestion_temps_lot_incomplet_{}", std::process::id()));
+        let periode = racine.join("periode_juin");
+        std::fs::create_dir_all(&periode).unwrap();
+        std::fs::write(periode.join(lot::FICHIER_HORS_CLIENTELE), include_str!("data/client_3/hors_client.txt")).unwrap();
+
+        let rapport = lot::traiter_lot(&racine, &racine.join(lot::DOSSIER_RAPPORTS), &ReglagesCalcul::default(), None).unwrap();
+        std::fs::remove_dir_all(&racine).unwrap();
+
+        let en_erreur = rapport.dossiers_en_erreur();
+        assert_eq!(en_erreur.len(), 1);
+        assert!(en_erreur[0].problemes[0].contains(lot::FICHIER_CLIENTELE));
+        assert!((en_erreur[0].total() - 7.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_traitement_lot_ignore_le_dossier_de_sortie() {
+        let racine = std::env::temp_dir().join(format!("gestion_temps_lot_sortie_{}", std::process::id()));
+        let employe = racine.join("alice");
+        std::fs::create_dir_all(&employe).unwrap();
+        std::fs::write(employe.join(lot::FICHIER_HORS_CLIENTELE), include_str!("data/client_1/hors_client.txt")).unwrap();
+        std::fs::write(employe.join(lot::FICHIER_CLIENTELE), include_str!("data/client_1/client.txt")).unwrap();
+
+        // Sortie personnalisée à l'intérieur de la racine, sur deux niveaux
+        let sortie = racine.join("exports").join("juin");
+        lot::traiter_lot(&racine, &sortie, &ReglagesCalcul::default(), None).unwrap().ecrire_rapports(&sortie).unwrap();
+        let second_passage = lot::traiter_lot(&racine, &sortie, &ReglagesCalcul::default(), None).unwrap();
+        std::fs::remove_dir_all(&racine).unwrap();
+
+        let noms: Vec<&str> = second_passage.dossiers.iter().map(|d| d.nom.as_str()).collect();
+        assert_eq!(noms, vec!["alice"]);
+    }
+}
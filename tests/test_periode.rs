@@ -0,0 +1,88 @@
+use chrono::NaiveDate;
+use gestion_temps::periode::{borne_valide, dans_periode};
+use gestion_temps::{FiltreDates, GestionTempsApp, Periode};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+fn date(annee: i32, mois: u32, jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(annee, mois, jour).unwrap()
+}
+
+#[cfg(test)]
+mod periode_tests {
+    use super::*;
+
+    fn filtre(periode: Periode) -> FiltreDates {
+        FiltreDates { periode, ..Default::default() }
+    }
+
+    #[test]
+    fn test_bornes_predefinies() {
+        // Mercredi 15 janvier 2025
+        let aujourdhui = date(2025, 1, 15);
+
+        assert_eq!(filtre(Periode::Toutes).bornes(aujourdhui), None);
+        assert_eq!(filtre(Periode::CetteSemaine).bornes(aujourdhui), Some((date(2025, 1, 13), date(2025, 1, 19))));
+        assert_eq!(filtre(Periode::SemaineDerniere).bornes(aujourdhui), Some((date(2025, 1, 6), date(2025, 1, 12))));
+        assert_eq!(filtre(Periode::CeMois).bornes(aujourdhui), Some((date(2025, 1, 1), date(2025, 1, 31))));
+        assert_eq!(filtre(Periode::MoisDernier).bornes(aujourdhui), Some((date(2024, 12, 1), date(2024, 12, 31))));
+        assert_eq!(filtre(Periode::CeMois).bornes(date(2024, 12, 31)), Some((date(2024, 12, 1), date(2024, 12, 31))));
+    }
+
+    #[test]
+    fn test_periode_personnalisee() {
+        let filtre = FiltreDates {
+            periode: Periode::Personnalisee,
+            du: "11/06/2025".to_string(),
+            au: String::new(),
+        };
+        let bornes = filtre.bornes(date(2025, 1, 1));
+
+        assert!(!dans_periode(bornes, Some(date(2025, 6, 10))));
+        assert!(dans_periode(bornes, Some(date(2025, 6, 11))));
+        assert!(dans_periode(bornes, Some(date(2030, 1, 1))));
+        assert!(!dans_periode(bornes, None));
+        assert!(dans_periode(None, None));
+    }
+
+    #[test]
+    fn test_borne_valide() {
+        assert!(borne_valide(""));
+        assert!(borne_valide("  "));
+        assert!(borne_valide("11/06/2025"));
+        assert!(!borne_valide("11/6/25x"));
+        assert!(!borne_valide("31/02/2025"));
+    }
+
+    #[test]
+    fn test_filtre_applique_au_calcul() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            donnees_clientele: CLIENTELE.to_string(),
+            filtre_dates: FiltreDates {
+                periode: Periode::Personnalisee,
+                du: "10/06/2025".to_string(),
+                au: "10/06/2025".to_string(),
+            },
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        assert_eq!(app.temps_par_jour.len(), 1);
+        assert!(app.temps_par_jour.contains_key(&date(2025, 6, 10)));
+        assert!(app.entrees_hors_periode > 0);
+        assert!(app.status_message.contains("hors période"));
+
+        let resultat = app.resultat.as_ref().unwrap();
+        let total = resultat.hors_clientele + resultat.travail_clientele + resultat.deplacement;
+        assert!((total - app.temps_par_jour[&date(2025, 6, 10)]).abs() < 0.01);
+        assert!(resultat.details_hors_clientele.iter().all(|d| d.debut.as_deref().unwrap().starts_with("10/06/2025")));
+
+        // Sans filtre on retrouve les totaux complets
+        app.filtre_dates = FiltreDates::default();
+        app.calculer_resultats();
+        assert_eq!(app.entrees_hors_periode, 0);
+        assert!((app.resultat.as_ref().unwrap().hors_clientele - 12.5).abs() < 0.01);
+    }
+}
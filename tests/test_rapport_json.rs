@@ -0,0 +1,86 @@
+use chrono::NaiveDate;
+use gestion_temps::rapport_json::{schema_json, FICHIER_SCHEMA_JSON, VERSION_SCHEMA};
+use gestion_temps::{lot, FiltreDates, GestionTempsApp, Periode, RapportJson, ReglagesCalcul};
+use std::path::Path;
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+#[cfg(test)]
+mod rapport_json_tests {
+    use super::*;
+
+    fn app_calculee(filtre_dates: FiltreDates) -> GestionTempsApp {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            donnees_clientele: CLIENTELE.to_string(),
+            filtre_dates,
+            ..Default::default()
+        };
+        app.calculer_resultats();
+        app
+    }
+
+    #[test]
+    fn test_contenu_du_rapport() {
+        let app = app_calculee(FiltreDates::default());
+        let rapport = app.rapport_json();
+
+        assert_eq!(rapport.version_schema, VERSION_SCHEMA);
+        assert!((rapport.total - 23.15).abs() < 0.01);
+        assert_eq!(rapport.resultat, app.resultat);
+        assert_eq!(rapport.entrees, app.entrees);
+        assert_eq!(rapport.metadonnees.periode, None);
+        assert_eq!(rapport.metadonnees.lignes_clientele, 3);
+
+        let dates: Vec<NaiveDate> = rapport.temps_par_jour.iter().map(|j| j.date).collect();
+        assert!(dates.windows(2).all(|paire| paire[0] < paire[1]));
+        let heures: f64 = rapport.temps_par_jour.iter().map(|j| j.heures).sum();
+        assert!((heures - rapport.total).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_periode_et_relecture() {
+        let filtre = FiltreDates { periode: Periode::Personnalisee, du: "11/06/2025".to_string(), au: String::new() };
+        let rapport = app_calculee(filtre).rapport_json();
+
+        let periode = rapport.metadonnees.periode.as_ref().unwrap();
+        assert_eq!(periode.du, NaiveDate::from_ymd_opt(2025, 6, 11));
+        assert_eq!(periode.au, None);
+        assert!(rapport.metadonnees.entrees_hors_periode > 0);
+
+        let json = rapport.vers_json().unwrap();
+        let valeur: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(valeur["version_schema"], VERSION_SCHEMA);
+        assert_eq!(valeur["metadonnees"]["periode"]["du"], "2025-06-11");
+        assert_eq!(valeur["entrees"][0]["type_entree"], "HorsClientele");
+        assert_eq!(RapportJson::depuis_json(&json).unwrap(), rapport);
+    }
+
+    #[test]
+    fn test_schema_a_jour() {
+        // En cas d'échec : cargo run -- --schema-json schema/rapport.schema.json
+        let schema_livre = std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(FICHIER_SCHEMA_JSON)).unwrap();
+        assert_eq!(schema_livre.trim_end(), schema_json());
+
+        let schema: serde_json::Value = serde_json::from_str(&schema_json()).unwrap();
+        let requis = schema["required"].as_array().unwrap();
+        for champ in ["version_schema", "metadonnees", "temps_par_jour", "entrees", "diagnostics"] {
+            assert!(requis.iter().any(|r| r == champ), "champ requis manquant: {}", champ);
+        }
+    }
+
+    #[test]
+    fn test_rapport_json_du_traitement_par_lot() {
+        let racine = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+        let rapport = lot::traiter_lot(&racine, &racine.join(lot::DOSSIER_RAPPORTS), &ReglagesCalcul::default(), None).unwrap();
+
+        let sortie = std::env::temp_dir().join(format!("gestion_temps_lot_json_{}", std::process::id()));
+        rapport.ecrire_rapports(&sortie).unwrap();
+        let json = std::fs::read_to_string(sortie.join("rapport_client_1.json")).unwrap();
+        std::fs::remove_dir_all(&sortie).unwrap();
+
+        let rapport_client = RapportJson::depuis_json(&json).unwrap();
+        assert!((rapport_client.total - 23.15).abs() < 0.01);
+    }
+}
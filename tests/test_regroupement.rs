@@ -0,0 +1,82 @@
+use chrono::NaiveDate;
+use gestion_temps::regroupement::{regrouper_details, trier_groupes, CleRegroupement, ColonneTri};
+use gestion_temps::{GestionTempsApp, VueDetails};
+
+const HORS_CLIENTELE: &str = "ABS--1\tRéunion équipe\t02/06/2025 08:00\t02/06/2025 09:00
+ABS--2\tFormation sécurité\t03/06/2025 08:00\t03/06/2025 11:00
+ABS--3\tréunion équipe\t05/06/2025 08:00\t05/06/2025 08:30
+ABS--4\tRéunion équipe\t04/06/2025 14:00\t04/06/2025 15:00";
+
+fn date(jour: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(2025, 6, jour)
+}
+
+#[cfg(test)]
+mod regroupement_tests {
+    use super::*;
+
+    fn app_calculee() -> GestionTempsApp {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+        app
+    }
+
+    #[test]
+    fn test_regrouper_par_description() {
+        let app = app_calculee();
+        let details = &app.resultat.as_ref().unwrap().details_hors_clientele;
+
+        let groupes = regrouper_details(details, CleRegroupement::Description);
+        assert_eq!(groupes.len(), 2);
+
+        let reunion = &groupes[0];
+        assert_eq!(reunion.libelle, "Réunion équipe");
+        assert_eq!(reunion.nombre, 3);
+        assert!((reunion.duree - 2.5).abs() < 0.01);
+        assert_eq!(reunion.premiere, date(2));
+        assert_eq!(reunion.derniere, date(5));
+    }
+
+    #[test]
+    fn test_regrouper_par_categorie() {
+        let app = app_calculee();
+        let details = &app.resultat.as_ref().unwrap().details_hors_clientele;
+
+        let groupes = regrouper_details(details, CleRegroupement::Categorie);
+        let libelles: Vec<&str> = groupes.iter().map(|g| g.libelle.as_str()).collect();
+        assert_eq!(libelles, ["Réunion", "Formation"]);
+    }
+
+    #[test]
+    fn test_tri_des_groupes() {
+        let app = app_calculee();
+        let details = &app.resultat.as_ref().unwrap().details_hors_clientele;
+        let mut groupes = regrouper_details(details, CleRegroupement::Description);
+
+        trier_groupes(&mut groupes, ColonneTri::Duree, false);
+        assert_eq!(groupes[0].libelle, "Formation sécurité");
+
+        trier_groupes(&mut groupes, ColonneTri::Nombre, false);
+        assert_eq!(groupes[0].libelle, "Réunion équipe");
+
+        trier_groupes(&mut groupes, ColonneTri::Derniere, true);
+        assert_eq!(groupes[0].libelle, "Formation sécurité");
+    }
+
+    #[test]
+    fn test_vue_details_inverse_le_tri() {
+        let mut vue = VueDetails::default();
+        assert_eq!(vue.tri, ColonneTri::Duree);
+        assert!(!vue.croissant);
+
+        vue.trier_par(ColonneTri::Duree);
+        assert!(vue.croissant);
+
+        vue.trier_par(ColonneTri::Libelle);
+        assert_eq!(vue.tri, ColonneTri::Libelle);
+        assert!(vue.croissant);
+    }
+}
@@ -0,0 +1,85 @@
+use gestion_temps::{FormulaireSaisie, GestionTempsApp, TypeEntree};
+
+#[cfg(test)]
+mod saisie_manuelle_tests {
+    use super::*;
+
+    fn formulaire_hors_clientele(description: &str, debut: &str, fin: &str) -> FormulaireSaisie {
+        FormulaireSaisie {
+            description: description.to_string(),
+            debut: debut.to_string(),
+            fin: fin.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_saisie_manuelle_sans_export() {
+        let mut app = GestionTempsApp::default();
+
+        let reunion = formulaire_hors_clientele("Réunion équipe", "16/06/2025 08:00", "16/06/2025 09:30");
+        app.ajouter_entree_manuelle(reunion.vers_entree().unwrap()).unwrap();
+
+        let intervention = FormulaireSaisie {
+            type_entree: TypeEntree::Clientele,
+            client: "Client Zeta".to_string(),
+            code: "WO-0001".to_string(),
+            debut: "16/06/2025 10:00".to_string(),
+            travail: 2.5,
+            deplacement: 0.75,
+            ..Default::default()
+        };
+        let entree = intervention.vers_entree().unwrap();
+        assert_eq!(entree.fin, "16/06/2025 12:30");
+        app.ajouter_entree_manuelle(entree).unwrap();
+
+        app.calculer_resultats();
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.hors_clientele - 1.5).abs() < 0.01);
+        assert!((resultat.travail_clientele - 2.5).abs() < 0.01);
+        assert!((resultat.deplacement - 0.75).abs() < 0.01);
+        assert_eq!(app.temps_par_jour.len(), 1);
+    }
+
+    #[test]
+    fn test_saisie_manuelle_chevauchement_refuse() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: include_str!("data/client_1/hors_client.txt").to_string(),
+            ..Default::default()
+        };
+
+        // "Formation sécurité" occupe le 10/06 de 13:00 à 17:00
+        let entree = formulaire_hors_clientele("Appel client", "10/06/2025 16:30", "10/06/2025 17:30")
+            .vers_entree()
+            .unwrap();
+        let erreur = app.ajouter_entree_manuelle(entree).unwrap_err();
+        assert!(erreur.contains("Formation sécurité"), "{}", erreur);
+
+        let entree = formulaire_hors_clientele("Appel client", "10/06/2025 17:00", "10/06/2025 17:30")
+            .vers_entree()
+            .unwrap();
+        assert!(app.ajouter_entree_manuelle(entree).is_ok());
+    }
+
+    #[test]
+    fn test_saisie_manuelle_conservee_apres_modification_du_texte() {
+        let mut app = GestionTempsApp::default();
+        let entree = formulaire_hors_clientele("Inventaire", "18/06/2025 14:00", "18/06/2025 16:00")
+            .vers_entree()
+            .unwrap();
+        app.ajouter_entree_manuelle(entree).unwrap();
+
+        app.donnees_hors_clientele = include_str!("data/client_2/hors_client.txt").to_string();
+        app.calculer_resultats();
+
+        // 5,5h collées + 2h saisies à la main
+        assert!((app.resultat.as_ref().unwrap().hors_clientele - 7.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_formulaire_invalide() {
+        assert!(formulaire_hors_clientele("", "18/06/2025 14:00", "18/06/2025 16:00").vers_entree().is_err());
+        assert!(formulaire_hors_clientele("Test", "18/06/2025 14:00", "18/06/2025 13:00").vers_entree().is_err());
+        assert!(formulaire_hors_clientele("Test", "demain", "18/06/2025 16:00").vers_entree().is_err());
+    }
+}
@@ -0,0 +1,64 @@
+use gestion_temps::detection::TypeLigne;
+use gestion_temps::saisie_unifiee::repartir_lignes;
+use gestion_temps::{GestionTempsApp, ProfilEmploye};
+
+#[cfg(test)]
+mod saisie_unifiee_tests {
+    use super::*;
+
+    fn collage_mixte() -> String {
+        format!(
+            "Code\tDescription\tDébut\tFin\n{}\n{}\nligne parasite",
+            include_str!("data/client_2/client.txt").trim_end(),
+            include_str!("data/client_2/hors_client.txt").trim_end()
+        )
+    }
+
+    #[test]
+    fn test_repartir_lignes_mixtes() {
+        let repartition = repartir_lignes(&collage_mixte());
+
+        assert_eq!(repartition.lignes[0].1, TypeLigne::EnTete);
+        assert_eq!(repartition.nombre(TypeLigne::Clientele), 2);
+        assert_eq!(repartition.nombre(TypeLigne::HorsClientele), 5);
+        assert_eq!(repartition.nombre(TypeLigne::Invalide), 1);
+        assert!(repartition.hors_clientele.starts_with("ABS--313140"));
+    }
+
+    #[test]
+    fn test_calcul_saisie_unifiee() {
+        let mut app = GestionTempsApp {
+            saisie_unifiee: true,
+            donnees_melangees: collage_mixte(),
+            ..Default::default()
+        };
+
+        app.calculer_saisie_unifiee();
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.hors_clientele - 5.5).abs() < 0.01);
+        assert!((resultat.travail_clientele - 3.0).abs() < 0.01);
+        assert!((resultat.deplacement - 1.0).abs() < 0.01);
+        assert_eq!(app.diagnostics.len(), 1);
+        assert!(app.diagnostics[0].contains("ligne parasite"));
+    }
+
+    #[test]
+    fn test_collage_combine_propre_a_chaque_employe() {
+        let mut app = GestionTempsApp { saisie_unifiee: true, ..Default::default() };
+        let alice = app.ajouter_employe(ProfilEmploye { nom: "Alice".to_string(), ..Default::default() });
+        app.donnees_melangees = collage_mixte();
+        app.calculer_saisie_unifiee();
+
+        let bob = app.ajouter_employe(ProfilEmploye { nom: "Bob".to_string(), ..Default::default() });
+        assert!(app.donnees_melangees.is_empty());
+        app.calculer_saisie_unifiee();
+        assert!(app.donnees_clientele.is_empty());
+        assert!(app.donnees_hors_clientele.is_empty());
+
+        app.selectionner_employe(alice);
+        assert_eq!(app.donnees_melangees, collage_mixte());
+        assert!(app.employes[bob].donnees_melangees.is_empty());
+        assert!(app.employes[bob].donnees_clientele.is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use gestion_temps::surveillance::{lire_dossier, DELAI_STABILITE};
+use gestion_temps::{GestionTempsApp, ProfilEmploye};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+#[cfg(test)]
+mod surveillance_tests {
+    use super::*;
+
+    fn dossier_temporaire(nom: &str) -> PathBuf {
+        let dossier = std::env::temp_dir().join(format!("gestion_temps_{}_{}", nom, std::process::id()));
+        std::fs::remove_dir_all(&dossier).ok();
+        std::fs::create_dir_all(&dossier).unwrap();
+        dossier
+    }
+
+    /// Attend que la surveillance ait recalculé les résultats.
+    fn attendre_recalcul(app: &mut GestionTempsApp) -> bool {
+        let debut = Instant::now();
+        while debut.elapsed() < Duration::from_secs(5) {
+            if app.verifier_surveillance() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    #[test]
+    fn test_lecture_du_dossier() {
+        let dossier = dossier_temporaire("lecture_dossier");
+        // Un fichier mélangé, un fichier d'un seul type et un fichier ignoré
+        std::fs::write(dossier.join("b_export.txt"), format!("{}\n{}", CLIENTELE, HORS_CLIENTELE)).unwrap();
+        std::fs::write(dossier.join("a_hors.tsv"), HORS_CLIENTELE.lines().next().unwrap()).unwrap();
+        std::fs::write(dossier.join("notes.md"), "à ignorer").unwrap();
+
+        let contenu = lire_dossier(&dossier).unwrap();
+        std::fs::remove_dir_all(&dossier).ok();
+
+        assert_eq!(contenu.fichiers, vec!["a_hors.tsv", "b_export.txt"]);
+        assert!(contenu.problemes.is_empty());
+        assert_eq!(contenu.clientele.lines().count(), 3);
+        assert_eq!(contenu.hors_clientele.lines().count(), HORS_CLIENTELE.lines().count() + 1);
+        assert!(contenu.hors_clientele.starts_with(HORS_CLIENTELE.lines().next().unwrap()));
+    }
+
+    #[test]
+    fn test_recalcul_automatique_et_notification() {
+        let dossier = dossier_temporaire("surveillance");
+        std::fs::write(dossier.join("hors_client.txt"), HORS_CLIENTELE).unwrap();
+
+        let mut app = GestionTempsApp::default();
+        app.demarrer_surveillance(&dossier);
+        let hors_clientele = app.resultat.as_ref().unwrap().hors_clientele;
+        assert!(hors_clientele > 0.0);
+        assert!(app.notification_surveillance.is_none());
+
+        std::fs::write(dossier.join("client.txt"), CLIENTELE).unwrap();
+        // Rien n'est recalculé avant la fin du délai de stabilité
+        let debut = Instant::now();
+        while debut.elapsed() < DELAI_STABILITE / 2 {
+            assert!(!app.verifier_surveillance());
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(attendre_recalcul(&mut app));
+
+        let resultat = app.resultat.clone().unwrap();
+        assert!((resultat.hors_clientele + resultat.travail_clientele + resultat.deplacement - 23.15).abs() < 0.01);
+        assert!(!app.temps_par_jour.is_empty());
+        let notification = app.notification_surveillance.clone().unwrap();
+        assert_eq!(notification.ancien_total, Some(hors_clientele));
+        assert!(notification.fichiers.contains(&"client.txt".to_string()));
+        assert!(notification.message().contains("client.txt"));
+
+        app.arreter_surveillance();
+        std::fs::remove_dir_all(&dossier).ok();
+        assert!(app.surveillance.is_none());
+    }
+
+    #[test]
+    fn test_dossier_inexistant() {
+        let mut app = GestionTempsApp::default();
+        app.demarrer_surveillance(&std::env::temp_dir().join("gestion_temps_dossier_absent"));
+        assert!(app.surveillance.is_none());
+        assert!(app.status_message.contains("Impossible de surveiller"));
+    }
+
+    #[test]
+    fn test_surveillance_en_saisie_libre() {
+        let dossier = dossier_temporaire("surveillance_employe");
+        std::fs::write(dossier.join("client.txt"), CLIENTELE).unwrap();
+
+        let mut app = GestionTempsApp::default();
+        let alice = app.ajouter_employe(ProfilEmploye { nom: "Alice".to_string(), ..Default::default() });
+        app.donnees_hors_clientele = HORS_CLIENTELE.to_string();
+
+        // Le dossier d'Alice est rangé et n'est pas modifié par les recalculs
+        app.demarrer_surveillance(&dossier);
+        assert_eq!(app.employe_actif, None);
+        assert_eq!(app.donnees_clientele, CLIENTELE);
+        assert_eq!(app.employes[alice].donnees_hors_clientele, HORS_CLIENTELE);
+        assert!(app.employes[alice].donnees_clientele.is_empty());
+
+        // Sélectionner un employé arrête la surveillance
+        app.selectionner_employe(alice);
+        assert!(!app.verifier_surveillance());
+        assert!(app.surveillance.is_none());
+        assert_eq!(app.donnees_hors_clientele, HORS_CLIENTELE);
+        std::fs::remove_dir_all(&dossier).ok();
+    }
+}
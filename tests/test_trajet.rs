@@ -0,0 +1,124 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::ligne_clientele;
+use gestion_temps::trajet::{repartir_trajets, total_trajets};
+use gestion_temps::{GestionTempsApp, ReglesTrajet};
+
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+/// Ligne d'intervention avec trajet aller `trajet` et intervention `intervention`.
+fn ligne(client: &str, trajet: (&str, &str), intervention: (&str, &str), travail: &str, deplacement: &str) -> String {
+    ligne_clientele("AA-1", client, "Maintenance", trajet, intervention, travail, deplacement)
+}
+
+fn date(jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 6, jour).unwrap()
+}
+
+#[cfg(test)]
+mod trajet_tests {
+    use super::*;
+
+    fn app_avec(lignes: &[String]) -> GestionTempsApp {
+        let mut app = GestionTempsApp {
+            donnees_clientele: lignes.join("\n"),
+            regles_trajet: ReglesTrajet { actif: true, ..Default::default() },
+            ..Default::default()
+        };
+        app.calculer_resultats();
+        app
+    }
+
+    #[test]
+    fn test_horaires_de_trajet_lus() {
+        let mut app = GestionTempsApp {
+            donnees_clientele: CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.analyser_saisie();
+
+        assert_eq!(app.entrees[0].debut_trajet, "10/06/2025 08:48");
+        assert_eq!(app.entrees[0].fin_trajet, "10/06/2025 09:24");
+    }
+
+    #[test]
+    fn test_franchise_pendant_l_horaire() {
+        // Trajet de 0,6h pendant l'horaire : 0,5h de franchise, le reste compté
+        let app = app_avec(&[ligne("Alpha", ("10/06/2025 08:48", "10/06/2025 09:24"), ("10/06/2025 09:24", "10/06/2025 10:41"), "1,3", "0,6")]);
+
+        let par_jour = app.repartition_trajets();
+        let jour = &par_jour[&date(10)];
+        assert!((jour.non_compte - 0.5).abs() < 0.01);
+        assert!((jour.compte - 0.1).abs() < 0.01);
+        assert_eq!(jour.compense, 0.0);
+    }
+
+    #[test]
+    fn test_trajet_hors_horaire_compense() {
+        // Départ à 6h pour une intervention à 7h30, retour dans l'horaire
+        let app = app_avec(&[
+            ligne("Alpha", ("11/06/2025 06:00", "11/06/2025 07:30"), ("11/06/2025 07:30", "11/06/2025 09:00"), "1,5", "1,5"),
+            ligne("Beta", ("11/06/2025 10:00", "11/06/2025 11:00"), ("11/06/2025 11:00", "11/06/2025 12:00"), "1", "1"),
+        ]);
+
+        let total = total_trajets(&app.repartition_trajets());
+        assert!((total.total() - 2.5).abs() < 0.01);
+        assert!((total.non_compte - 0.5).abs() < 0.01);
+        // 1,5h hors horaire dont 0,5h de franchise
+        assert!((total.compense - 1.0).abs() < 0.01);
+        assert!((total.contrepartie - 0.5).abs() < 0.01);
+        assert!((total.compte - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_franchise_par_jour_et_horaires_inconnus() {
+        let regles = ReglesTrajet { actif: true, franchise_quotidienne: 0.25, ..Default::default() };
+        let app = app_avec(&[
+            ligne("Alpha", ("-", "-"), ("10/06/2025 09:00", "10/06/2025 10:00"), "1", "0,5"),
+            ligne("Beta", ("-", "-"), ("11/06/2025 09:00", "11/06/2025 10:00"), "1", "0,5"),
+        ]);
+
+        let par_jour = repartir_trajets(&app.entrees_retenues(), &regles);
+        assert_eq!(par_jour.len(), 2);
+        let total = total_trajets(&par_jour);
+        assert!((total.non_compte - 0.5).abs() < 0.01);
+        assert!((total.compte - 0.5).abs() < 0.01);
+        assert_eq!(total.compense, 0.0);
+    }
+
+    #[test]
+    fn test_total_ne_retient_que_le_trajet_compte() {
+        let app = app_avec(&[
+            ligne("Alpha", ("11/06/2025 06:00", "11/06/2025 07:30"), ("11/06/2025 07:30", "11/06/2025 09:00"), "1,5", "1,5"),
+            ligne("Beta", ("11/06/2025 10:00", "11/06/2025 11:00"), ("11/06/2025 11:00", "11/06/2025 12:00"), "1", "1"),
+        ]);
+
+        let resultat = app.resultat.as_ref().unwrap();
+        assert!((resultat.deplacement - 1.0).abs() < 0.01);
+        let trajets = resultat.trajets.as_ref().unwrap();
+        assert!((trajets.non_compte - 0.5).abs() < 0.01);
+        assert!((trajets.compense - 1.0).abs() < 0.01);
+        // 2,5h d'intervention et 1h de trajet compté
+        assert!((app.temps_par_jour[&date(11)] - 3.5).abs() < 0.01);
+        assert!((app.rapport_json().total - 3.5).abs() < 0.01);
+
+        // Sans règles, tout le trajet est compté
+        let mut sans_regles = GestionTempsApp { donnees_clientele: app.donnees_clientele.clone(), ..Default::default() };
+        sans_regles.calculer_resultats();
+        let resultat = sans_regles.resultat.as_ref().unwrap();
+        assert!((resultat.deplacement - 2.5).abs() < 0.01);
+        assert!(resultat.trajets.is_none());
+    }
+
+    #[test]
+    fn test_retour_suppose_pendant_l_horaire() {
+        // Aller de 6h à 7h hors horaire ; les 2h déclarées comprennent le retour, sans horaires connus
+        let regles = ReglesTrajet { actif: true, franchise_quotidienne: 0.0, ..Default::default() };
+        let app = app_avec(&[ligne("Alpha", ("11/06/2025 06:00", "11/06/2025 07:00"), ("11/06/2025 08:00", "11/06/2025 10:00"), "2", "2")]);
+
+        let total = total_trajets(&repartir_trajets(&app.entrees_retenues(), &regles));
+        assert!((total.compense - 1.0).abs() < 0.01);
+        assert!((total.compte - 1.0).abs() < 0.01);
+    }
+}
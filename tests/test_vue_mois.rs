@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use gestion_temps::vue_mois::{charge_du_jour, semaines_du_mois};
+use gestion_temps::{Charge, GestionTempsApp};
+
+const HORS_CLIENTELE: &str = include_str!("data/client_1/hors_client.txt");
+const CLIENTELE: &str = include_str!("data/client_1/client.txt");
+
+fn date(mois: u32, jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, mois, jour).unwrap()
+}
+
+#[cfg(test)]
+mod vue_mois_tests {
+    use super::*;
+
+    #[test]
+    fn test_semaines_du_mois() {
+        // Juin 2025 commence un dimanche et finit un lundi
+        let semaines = semaines_du_mois(date(6, 15));
+
+        assert_eq!(semaines.len(), 6);
+        assert_eq!(semaines[0][..6], [None; 6]);
+        assert_eq!(semaines[0][6], Some(date(6, 1)));
+        assert_eq!(semaines[5][0], Some(date(6, 30)));
+        assert_eq!(semaines.iter().flatten().flatten().count(), 30);
+    }
+
+    #[test]
+    fn test_charge_du_jour() {
+        assert_eq!(charge_du_jour(0.0, 0.0), Charge::Aucune);
+        assert_eq!(charge_du_jour(5.0, 7.0), Charge::Sous);
+        assert_eq!(charge_du_jour(7.2, 7.0), Charge::Normale);
+        assert_eq!(charge_du_jour(9.0, 7.0), Charge::Sur);
+        // Tout travail un jour sans horaire prévu dépasse l'horaire
+        assert_eq!(charge_du_jour(2.0, 0.0), Charge::Sur);
+    }
+
+    #[test]
+    fn test_grille_et_entrees_du_jour() {
+        let mut app = GestionTempsApp {
+            donnees_hors_clientele: HORS_CLIENTELE.to_string(),
+            donnees_clientele: CLIENTELE.to_string(),
+            ..Default::default()
+        };
+        app.calculer_resultats();
+
+        let dernier_jour = *app.temps_par_jour.keys().max().unwrap();
+        assert_eq!(app.mois_affiche(), Some(date(6, 1)));
+        assert_ne!(app.charge(dernier_jour), Charge::Aucune);
+        assert_eq!(app.charge(date(6, 2)), Charge::Aucune);
+
+        let entrees = app.entrees_du_jour(date(6, 10));
+        assert!(!entrees.is_empty());
+        assert!(entrees.iter().all(|e| e.date == Some(date(6, 10))));
+        // Dans l'ordre de début
+        assert!(entrees.windows(2).all(|paire| {
+            let debut = |texte: &str| chrono::NaiveDateTime::parse_from_str(texte, "%d/%m/%Y %H:%M").ok();
+            debut(&paire[0].debut) <= debut(&paire[1].debut)
+        }));
+        let (realise, prevu) = app.realise_et_prevu(date(6, 10));
+        assert!((realise - app.temps_par_jour[&date(6, 10)]).abs() < 1e-9);
+        assert_eq!(prevu, 7.0);
+    }
+}